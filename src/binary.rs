@@ -10,6 +10,7 @@ pub(crate) mod read;
 pub enum U8 {}
 
 /// Signed 8-bit binary type.
+#[allow(unused)]
 #[derive(Copy, Clone)]
 pub enum I8 {}
 
@@ -30,10 +31,12 @@ pub enum U24Be {}
 pub enum U32Be {}
 
 /// Signed 32-bit big endian binary type.
+#[allow(unused)]
 #[derive(Copy, Clone)]
 pub enum I32Be {}
 
 /// Signed 64-bit binary type.
+#[allow(unused)]
 #[derive(Copy, Clone)]
 pub enum I64Be {}
 
@@ -65,11 +68,14 @@ mod size {
     use core::mem;
 
     pub const U8: usize = mem::size_of::<u8>();
+    #[allow(unused)]
     pub const I8: usize = mem::size_of::<i8>();
     pub const U16: usize = mem::size_of::<u16>();
     pub const I16: usize = mem::size_of::<i16>();
     pub const U24: usize = 3;
     pub const U32: usize = mem::size_of::<u32>();
+    #[allow(unused)]
     pub const I32: usize = mem::size_of::<i32>();
+    #[allow(unused)]
     pub const I64: usize = mem::size_of::<i64>();
 }
@@ -148,7 +148,9 @@ impl<'a> ReadScope<'a> {
     }
 
     pub fn offset(&self, offset: usize) -> ReadScope<'a> {
-        let base = self.base + offset;
+        // The offset comes from the data being read so it may be out of bounds, in which case
+        // the scope is empty and the base is only informational
+        let base = self.base.saturating_add(offset);
         let data = self.data.get(offset..).unwrap_or(&[]);
         ReadScope { base, data }
     }
@@ -157,7 +159,7 @@ impl<'a> ReadScope<'a> {
         if offset < self.data.len() || length == 0 {
             let data = self.data.get(offset..).unwrap_or(&[]);
             if length <= data.len() {
-                let base = self.base + offset;
+                let base = self.base.saturating_add(offset);
                 let data = &data[0..length];
                 Ok(ReadScope { base, data })
             } else {
@@ -169,7 +171,7 @@ impl<'a> ReadScope<'a> {
     }
 
     pub fn ctxt(&self) -> ReadCtxt<'a> {
-        ReadCtxt::new(self.clone())
+        ReadCtxt::new(*self)
     }
 
     pub fn read<T: ReadBinaryDep<Args<'a> = ()>>(&self) -> Result<T::HostType<'a>, ParseError> {
@@ -376,7 +378,7 @@ impl<'a> ReadCtxt<'a> {
         Ok(ReadArray {
             scope,
             length,
-            args: args,
+            args,
         })
     }
 
@@ -476,7 +478,7 @@ impl<'a, T: ReadFixedSizeDep> CheckIndex for ReadArray<'a, T> {
     }
 }
 
-impl<'a, 'b, T: ReadUnchecked> IntoIterator for &'b ReadArray<'a, T> {
+impl<'a, T: ReadUnchecked> IntoIterator for &ReadArray<'a, T> {
     type Item = T::HostType;
     type IntoIter = ReadArrayIter<'a, T>;
     fn into_iter(self) -> ReadArrayIter<'a, T> {
@@ -677,4 +679,16 @@ mod tests {
         let scope = ReadScope::new(&[1, 2, 3]);
         assert!(scope.offset_length(99, 0).is_ok());
     }
+
+    // Offsets read from a file can be close to usize::MAX on 32-bit targets
+    #[test]
+    fn test_offset_overflow() {
+        let scope = ReadScope::new(&[1, 2, 3]).offset(1);
+        assert!(scope.offset(usize::MAX).data().is_empty());
+        assert!(scope.offset_length(usize::MAX, 0).is_ok());
+        assert_eq!(
+            scope.offset_length(usize::MAX, 1).err(),
+            Some(ParseError::BadOffset)
+        );
+    }
 }
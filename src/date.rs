@@ -0,0 +1,124 @@
+//! Formatting of Mac OS timestamps.
+//!
+//! The conversion to a calendar date is done here rather than with a date library so that it
+//! is available in `no_std` builds and renders identically in all of them.
+
+use core::fmt;
+
+/// Seconds in a day
+const DAY: u32 = 86400;
+
+/// Days from 1 March 0000 (proleptic Gregorian) to 1 January 1904, the Mac OS epoch
+const EPOCH_DAYS: u32 = 695361;
+
+/// A Mac OS timestamp that displays as a calendar date and time.
+///
+/// The wrapped value is seconds since midnight, 1 January 1904. Mac OS stores timestamps in local
+/// time, so no time zone is shown. Formatting doesn't allocate.
+///
+/// ```
+/// use macbinary::DisplayMacDate;
+///
+/// assert_eq!(DisplayMacDate(0).to_string(), "1904-01-01 00:00:00");
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DisplayMacDate(pub u32);
+
+/// A date and time broken down into its calendar fields.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct CivilDateTime {
+    pub year: u32,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl CivilDateTime {
+    /// Convert a Mac OS timestamp to a calendar date and time.
+    pub(crate) fn from_mac(timestamp: u32) -> CivilDateTime {
+        let secs = timestamp % DAY;
+        // Days since 1 March 0000, starting the year in March puts the leap day at the end
+        let days = timestamp / DAY + EPOCH_DAYS;
+        let era = days / 146097;
+        let day_of_era = days % 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = era * 400 + year_of_era + u32::from(month <= 2);
+
+        // The casts can't truncate as each value is bounded by the arithmetic above
+        CivilDateTime {
+            year,
+            month: month as u8,
+            day: day as u8,
+            hour: (secs / 3600) as u8,
+            minute: (secs / 60 % 60) as u8,
+            second: (secs % 60) as u8,
+        }
+    }
+}
+
+impl fmt::Display for DisplayMacDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let date = CivilDateTime::from_mac(self.0);
+        write!(
+            f,
+            "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+            date.year, date.month, date.day, date.hour, date.minute, date.second
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(DisplayMacDate(0).to_string(), "1904-01-01 00:00:00");
+        // UNIX epoch
+        assert_eq!(
+            DisplayMacDate(2082844800).to_string(),
+            "1970-01-01 00:00:00"
+        );
+        // Leap day
+        assert_eq!(
+            DisplayMacDate(3034676730).to_string(),
+            "2000-02-29 13:45:30"
+        );
+        // Date Test.bin
+        assert_eq!(
+            DisplayMacDate(3762669652).to_string(),
+            "2023-03-26 10:00:52"
+        );
+        // The last representable time, after which the timestamp wraps
+        assert_eq!(DisplayMacDate(u32::MAX).to_string(), "2040-02-06 06:28:15");
+    }
+
+    #[test]
+    fn test_from_mac() {
+        // 1904 is a leap year
+        assert_eq!(
+            CivilDateTime::from_mac(59 * DAY),
+            CivilDateTime {
+                year: 1904,
+                month: 2,
+                day: 29,
+                hour: 0,
+                minute: 0,
+                second: 0
+            }
+        );
+        assert_eq!(CivilDateTime::from_mac(365 * DAY).month, 12);
+        assert_eq!(CivilDateTime::from_mac(366 * DAY).year, 1905);
+    }
+}
@@ -0,0 +1,127 @@
+//! Content digests of forks, for identifying files with the same content.
+//!
+//! The digests cover only the fork bytes, so two MacBinary files that wrap the same forks under
+//! a different name, dates, or other metadata have the same digests.
+
+use core::fmt;
+
+use crc::{Crc, CRC_32_ISO_HDLC};
+#[cfg(feature = "sha256")]
+use sha2::{Digest as _, Sha256};
+
+static CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+
+/// A digest algorithm.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Algo {
+    /// CRC-32 (ISO-HDLC), as used by zip and gzip
+    Crc32,
+    /// SHA-256
+    #[cfg(feature = "sha256")]
+    Sha256,
+}
+
+/// A digest computed by [MacBinary::fork_digest](crate::MacBinary::fork_digest) or
+/// [MacBinary::content_digest](crate::MacBinary::content_digest).
+///
+/// Displays as lowercase hex.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Digest {
+    /// CRC-32 (ISO-HDLC)
+    Crc32(u32),
+    /// SHA-256
+    #[cfg(feature = "sha256")]
+    Sha256([u8; 32]),
+}
+
+enum Hasher {
+    Crc32(crc::Digest<'static, u32>),
+    #[cfg(feature = "sha256")]
+    Sha256(Sha256),
+}
+
+impl Hasher {
+    fn new(algo: Algo) -> Hasher {
+        match algo {
+            Algo::Crc32 => Hasher::Crc32(CRC32.digest()),
+            #[cfg(feature = "sha256")]
+            Algo::Sha256 => Hasher::Sha256(Sha256::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Crc32(digest) => digest.update(data),
+            #[cfg(feature = "sha256")]
+            Hasher::Sha256(hasher) => hasher.update(data),
+        }
+    }
+
+    fn finalize(self) -> Digest {
+        match self {
+            Hasher::Crc32(digest) => Digest::Crc32(digest.finalize()),
+            #[cfg(feature = "sha256")]
+            Hasher::Sha256(hasher) => Digest::Sha256(hasher.finalize().into()),
+        }
+    }
+}
+
+/// Digest of `data`.
+pub(crate) fn digest(algo: Algo, data: &[u8]) -> Digest {
+    let mut hasher = Hasher::new(algo);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+/// Digest of the forks, each preceded by its length as a big-endian `u32`.
+///
+/// The lengths keep the boundary between the forks significant, so moving bytes from one fork to
+/// the other changes the digest.
+pub(crate) fn framed_digest(algo: Algo, forks: &[&[u8]]) -> Digest {
+    let mut hasher = Hasher::new(algo);
+    for fork in forks {
+        // Fork lengths are read from u32 fields so this can't truncate
+        hasher.update(&(fork.len() as u32).to_be_bytes());
+        hasher.update(fork);
+    }
+    hasher.finalize()
+}
+
+impl fmt::Display for Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Digest::Crc32(crc) => write!(f, "{:08x}", crc),
+            #[cfg(feature = "sha256")]
+            Digest::Sha256(hash) => hash.iter().try_for_each(|byte| write!(f, "{:02x}", byte)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        // Standard check value
+        assert_eq!(digest(Algo::Crc32, b"123456789"), Digest::Crc32(0xcbf43926));
+        assert_eq!(digest(Algo::Crc32, b"123456789").to_string(), "cbf43926");
+    }
+
+    #[test]
+    fn test_framed_digest() {
+        let a = framed_digest(Algo::Crc32, &[b"ab", b"c"]);
+        let b = framed_digest(Algo::Crc32, &[b"a", b"bc"]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    #[cfg(feature = "sha256")]
+    fn test_sha256() {
+        assert_eq!(
+            digest(Algo::Sha256, b"abc").to_string(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Identification of the program that encoded a MacBinary file.
+//!
+//! Encoders differ in the version numbers they write and in how they treat the parts of the
+//! format that readers ignore, such as the reserved header bytes and the padding after the last
+//! fork. [MacBinary::encoder_fingerprint_with](crate::MacBinary::encoder_fingerprint_with)
+//! gathers these [Observations] and matches them against a table of [EncoderRule]s to name a
+//! likely encoder.
+//!
+//! No rules for specific programs are included, see [RULES]. Rules derived from known sample
+//! files can be supplied by the caller.
+//!
+//! The result is a hint, not proof: different encoders can produce identical files, and files
+//! may have been modified since they were encoded.
+
+/// A program that encodes MacBinary files.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Encoder {
+    /// An encoder named by a rule
+    Other(&'static str),
+    /// No rule matched
+    Unknown,
+}
+
+/// Characteristics of a MacBinary file that vary between encoders.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Observations {
+    /// Version of the program that wrote the file (byte 122)
+    pub version: u8,
+    /// Minimum version needed to read the file (byte 123)
+    pub min_version: u8,
+    /// The MacBinary III `mBIN` signature is present
+    pub has_signature: bool,
+    /// The unused bytes 108–115 are zero
+    pub reserved_zeroed: bool,
+    /// The resource fork is followed by all of its padding
+    ///
+    /// Some encoders leave off the padding after the last fork.
+    pub rsrc_padded: bool,
+    /// The stored header CRC matches the computed one
+    pub crc_matches: bool,
+}
+
+/// A rule that identifies an encoder from [Observations].
+///
+/// Each field that is `Some` must equal the corresponding observation for the rule to match.
+/// Fields that are `None` match any value.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EncoderRule {
+    /// The encoder identified by this rule
+    pub encoder: Encoder,
+    /// Required [Observations::version]
+    pub version: Option<u8>,
+    /// Required [Observations::min_version]
+    pub min_version: Option<u8>,
+    /// Required [Observations::has_signature]
+    pub has_signature: Option<bool>,
+    /// Required [Observations::reserved_zeroed]
+    pub reserved_zeroed: Option<bool>,
+    /// Required [Observations::rsrc_padded]
+    pub rsrc_padded: Option<bool>,
+    /// Required [Observations::crc_matches]
+    pub crc_matches: Option<bool>,
+}
+
+/// The encoder a file was likely produced by, along with the observations that led to it.
+///
+/// Returned by [MacBinary::encoder_fingerprint](crate::MacBinary::encoder_fingerprint).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct EncoderHint {
+    /// The likely encoder, or [Encoder::Unknown]
+    pub encoder: Encoder,
+    /// The characteristics of the file
+    pub observations: Observations,
+}
+
+/// The default rules, tried in order.
+///
+/// This is empty, so the default fingerprint is always [Encoder::Unknown]. A rule should only be
+/// added with a cited reference or a sample file from the encoder it names, as a wrong
+/// attribution is worse than none.
+pub static RULES: &[EncoderRule] = &[];
+
+impl EncoderRule {
+    /// A rule for `encoder` that matches any observations.
+    pub const fn new(encoder: Encoder) -> EncoderRule {
+        EncoderRule {
+            encoder,
+            version: None,
+            min_version: None,
+            has_signature: None,
+            reserved_zeroed: None,
+            rsrc_padded: None,
+            crc_matches: None,
+        }
+    }
+
+    /// Returns `true` if the observations satisfy this rule.
+    pub fn matches(&self, observations: &Observations) -> bool {
+        fn check<T: PartialEq>(required: Option<T>, observed: T) -> bool {
+            match required {
+                Some(required) => required == observed,
+                None => true,
+            }
+        }
+
+        check(self.version, observations.version)
+            && check(self.min_version, observations.min_version)
+            && check(self.has_signature, observations.has_signature)
+            && check(self.reserved_zeroed, observations.reserved_zeroed)
+            && check(self.rsrc_padded, observations.rsrc_padded)
+            && check(self.crc_matches, observations.crc_matches)
+    }
+}
+
+impl EncoderHint {
+    /// Identify the encoder using the first rule in `rules` that matches `observations`.
+    pub fn from_rules(observations: Observations, rules: &[EncoderRule]) -> EncoderHint {
+        let encoder = rules
+            .iter()
+            .find(|rule| rule.matches(&observations))
+            .map(|rule| rule.encoder)
+            .unwrap_or(Encoder::Unknown);
+        EncoderHint {
+            encoder,
+            observations,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_rules() {
+        let observations = Observations {
+            version: 129,
+            min_version: 129,
+            has_signature: false,
+            reserved_zeroed: true,
+            rsrc_padded: false,
+            crc_matches: true,
+        };
+        assert_eq!(
+            EncoderHint::from_rules(observations, RULES).encoder,
+            Encoder::Unknown
+        );
+
+        let rules = [
+            EncoderRule {
+                version: Some(130),
+                ..EncoderRule::new(Encoder::Other("never"))
+            },
+            EncoderRule {
+                rsrc_padded: Some(false),
+                ..EncoderRule::new(Encoder::Other("unpadded"))
+            },
+        ];
+        assert_eq!(
+            EncoderHint::from_rules(observations, &rules).encoder,
+            Encoder::Other("unpadded")
+        );
+    }
+}
@@ -19,8 +19,66 @@ pub enum ParseError {
     BadIndex,
     /// A value overflowed its storage type
     Overflow,
+    /// The data is shorter than the lengths in the header require
+    Truncated {
+        /// The minimum length required by the header
+        expected: u64,
+        /// The length of the data
+        actual: u64,
+    },
     /// CRC did not match expected value
-    CrcMismatch,
+    CrcMismatch {
+        /// The CRC stored in the data
+        expected: u16,
+        /// The CRC calculated from the data
+        actual: u16,
+    },
+}
+
+/// Errors that originate when constructing a [FourCC](crate::FourCC)
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum FourCCError {
+    /// The code was not exactly four bytes long
+    BadLength(usize),
+    /// The byte at `index` is not a printable character
+    BadByte {
+        /// Index of the byte in the code
+        index: usize,
+        /// The value of the byte
+        byte: u8,
+    },
+    /// The string starts with `0x` but isn't the hex form of a code, e.g. `0x54450054`
+    BadHex,
+    /// The string contains a character that isn't part of the MacRoman character set
+    NotMacRoman {
+        /// Byte offset of the character in the string
+        index: usize,
+        /// The character
+        ch: char,
+    },
+}
+
+/// A byte that isn't part of the MacRoman character set, found when decoding text with
+/// [DecodePolicy::Error](crate::DecodePolicy::Error)
+#[cfg(not(feature = "no_std"))]
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct MacRomanError {
+    /// Offset of the byte in the text
+    pub position: usize,
+    /// The value of the byte
+    pub byte: u8,
+}
+
+/// Errors that originate when extracting data from a MacBinary file
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug)]
+pub enum ExtractError {
+    /// The MacBinary data could not be parsed
+    Parse(ParseError),
+    /// The data was parsed but the requested resource was not present
+    NotFound,
+    /// Writing the extracted data failed
+    Io(std::io::Error),
 }
 
 impl From<ReadEof> for ParseError {
@@ -44,7 +102,75 @@ impl fmt::Display for ParseError {
             ParseError::BadOffset => write!(f, "invalid data offset"),
             ParseError::BadIndex => write!(f, "invalid data index"),
             ParseError::Overflow => write!(f, "a value overflowed its range"),
-            ParseError::CrcMismatch => write!(f, "CRC mismatch"),
+            ParseError::Truncated { expected, actual } => write!(
+                f,
+                "data appears truncated: expected at least {} bytes, found {}",
+                expected, actual
+            ),
+            ParseError::CrcMismatch { expected, actual } => write!(
+                f,
+                "CRC mismatch: expected 0x{:04x}, calculated 0x{:04x}",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl fmt::Display for FourCCError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FourCCError::BadLength(len) => {
+                write!(f, "four-character code must be 4 bytes, not {}", len)
+            }
+            FourCCError::BadByte { index, byte } => write!(
+                f,
+                "four-character code contains non-printable byte 0x{:02x} at index {}",
+                byte, index
+            ),
+            FourCCError::BadHex => {
+                f.write_str("four-character code in hex must be 0x followed by 8 hex digits")
+            }
+            FourCCError::NotMacRoman { index, ch } => write!(
+                f,
+                "four-character code contains non-MacRoman character {:?} at index {}",
+                ch, index
+            ),
+        }
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl fmt::Display for MacRomanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "byte 0x{:02x} at position {} is not a MacRoman character",
+            self.byte, self.position
+        )
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl From<ParseError> for ExtractError {
+    fn from(error: ParseError) -> Self {
+        ExtractError::Parse(error)
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl From<std::io::Error> for ExtractError {
+    fn from(error: std::io::Error) -> Self {
+        ExtractError::Io(error)
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtractError::Parse(error) => write!(f, "parse error: {}", error),
+            ExtractError::NotFound => write!(f, "resource not found"),
+            ExtractError::Io(error) => write!(f, "I/O error: {}", error),
         }
     }
 }
@@ -52,3 +178,20 @@ impl fmt::Display for ParseError {
 // FIXME: Enable on no_std when https://github.com/rust-lang/rust/issues/103765 is stable
 #[cfg(not(feature = "no_std"))]
 impl std::error::Error for ParseError {}
+
+#[cfg(not(feature = "no_std"))]
+impl std::error::Error for FourCCError {}
+
+#[cfg(not(feature = "no_std"))]
+impl std::error::Error for MacRomanError {}
+
+#[cfg(not(feature = "no_std"))]
+impl std::error::Error for ExtractError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ExtractError::Parse(error) => Some(error),
+            ExtractError::NotFound => None,
+            ExtractError::Io(error) => Some(error),
+        }
+    }
+}
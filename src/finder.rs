@@ -0,0 +1,470 @@
+//! Finder metadata types.
+//!
+//! ### Reference:
+//!
+//! [Inside Macintosh: Macintosh Toolbox Essentials](https://archive.org/details/inside-macintosh-1992-1994/1992-macintosh_toolbox_essentials/)
+//! File Information Record 7-47 (pp. 631)
+
+use core::fmt;
+use core::ops::{BitAnd, BitOr};
+
+use crate::Version;
+
+/// The Finder flags of a file (`fdFlags`).
+///
+/// This combines the high byte (header byte 73) and low byte (header byte 101) of the flags
+/// stored in a MacBinary header into the 16-bit value used by the Finder.
+///
+/// ### Versions
+///
+/// The meaning of some bits changed over time. When constructed with [Self::for_version]:
+///
+/// - MacBinary I only stores the high byte. Of that byte only the bits that have kept their
+///   meaning since the original Finder are interpreted: [Self::IS_INVISIBLE], [Self::HAS_BUNDLE],
+///   and [Self::HAS_BEEN_INITED]. The other bits were used differently at the time (e.g. the
+///   bit now used for [Self::IS_ALIAS] was the file locked bit) so they are not interpreted,
+///   and are available from [Self::legacy_raw] instead. The low byte always reads as zero.
+/// - MacBinary II and III store both bytes and all bits are interpreted.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinderFlags {
+    bits: u16,
+    legacy: u16,
+}
+
+impl FinderFlags {
+    /// The file is an alias file
+    pub const IS_ALIAS: FinderFlags = FinderFlags::from_bits(0x8000);
+    /// The file is invisible
+    pub const IS_INVISIBLE: FinderFlags = FinderFlags::from_bits(0x4000);
+    /// The file contains a bundle resource
+    pub const HAS_BUNDLE: FinderFlags = FinderFlags::from_bits(0x2000);
+    /// The file can't be renamed
+    pub const NAME_LOCKED: FinderFlags = FinderFlags::from_bits(0x1000);
+    /// The file is a stationery pad
+    pub const IS_STATIONERY: FinderFlags = FinderFlags::from_bits(0x0800);
+    /// The file has a custom icon
+    pub const HAS_CUSTOM_ICON: FinderFlags = FinderFlags::from_bits(0x0400);
+    /// The Finder has recorded the file's bundle information
+    pub const HAS_BEEN_INITED: FinderFlags = FinderFlags::from_bits(0x0100);
+    /// The file contains no `'INIT'` resources
+    pub const HAS_NO_INITS: FinderFlags = FinderFlags::from_bits(0x0080);
+    /// The application can be run by more than one user at once
+    pub const IS_SHARED: FinderFlags = FinderFlags::from_bits(0x0040);
+    /// The application requires switching to 24-bit mode (System 6) to launch
+    pub const REQUIRES_SWITCH_LAUNCH: FinderFlags = FinderFlags::from_bits(0x0020);
+    /// The three bits holding the file's color (label), see [Self::color]
+    pub const COLOR: FinderFlags = FinderFlags::from_bits(0x000E);
+
+    const NAMED: [(FinderFlags, &'static str); 10] = [
+        (Self::IS_ALIAS, "IS_ALIAS"),
+        (Self::IS_INVISIBLE, "IS_INVISIBLE"),
+        (Self::HAS_BUNDLE, "HAS_BUNDLE"),
+        (Self::NAME_LOCKED, "NAME_LOCKED"),
+        (Self::IS_STATIONERY, "IS_STATIONERY"),
+        (Self::HAS_CUSTOM_ICON, "HAS_CUSTOM_ICON"),
+        (Self::HAS_BEEN_INITED, "HAS_BEEN_INITED"),
+        (Self::HAS_NO_INITS, "HAS_NO_INITS"),
+        (Self::IS_SHARED, "IS_SHARED"),
+        (Self::REQUIRES_SWITCH_LAUNCH, "REQUIRES_SWITCH_LAUNCH"),
+    ];
+
+    /// The bits interpreted in MacBinary I files, see [Versions](#versions).
+    const MACBINARY_I: u16 = 0x6100;
+
+    /// Construct from the raw 16-bit flags value.
+    ///
+    /// All bits are retained, including those without named constants.
+    pub const fn from_bits(bits: u16) -> FinderFlags {
+        FinderFlags { bits, legacy: 0 }
+    }
+
+    /// Construct from the raw 16-bit flags value of a file encoded with `version`.
+    ///
+    /// Bits that the version didn't define with their current meaning are moved to
+    /// [Self::legacy_raw], see [Versions](#versions).
+    pub const fn for_version(bits: u16, version: Version) -> FinderFlags {
+        match version {
+            Version::I => FinderFlags {
+                bits: bits & Self::MACBINARY_I,
+                legacy: bits & !Self::MACBINARY_I,
+            },
+            Version::II | Version::III => Self::from_bits(bits),
+        }
+    }
+
+    /// No flags set.
+    pub const fn empty() -> FinderFlags {
+        Self::from_bits(0)
+    }
+
+    /// The 16-bit flags value.
+    ///
+    /// This excludes the bits in [Self::legacy_raw].
+    pub const fn bits(self) -> u16 {
+        self.bits
+    }
+
+    /// The raw bits that were not interpreted because they had a different meaning in the
+    /// MacBinary version of the file.
+    ///
+    /// This is always zero for MacBinary II and III.
+    pub const fn legacy_raw(self) -> u16 {
+        self.legacy
+    }
+
+    /// Returns `true` if no flags are set.
+    pub const fn is_empty(self) -> bool {
+        self.bits == 0
+    }
+
+    /// Returns `true` if all the flags in `other` are set.
+    pub const fn contains(self, other: FinderFlags) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// The color (label) of the file, from 0 to 7.
+    pub const fn color(self) -> u8 {
+        ((self.bits & Self::COLOR.bits) >> 1) as u8
+    }
+}
+
+impl BitOr for FinderFlags {
+    type Output = FinderFlags;
+
+    fn bitor(self, rhs: FinderFlags) -> FinderFlags {
+        FinderFlags {
+            bits: self.bits | rhs.bits,
+            legacy: self.legacy | rhs.legacy,
+        }
+    }
+}
+
+impl BitAnd for FinderFlags {
+    type Output = FinderFlags;
+
+    fn bitand(self, rhs: FinderFlags) -> FinderFlags {
+        FinderFlags {
+            bits: self.bits & rhs.bits,
+            legacy: self.legacy & rhs.legacy,
+        }
+    }
+}
+
+impl From<FinderFlags> for u16 {
+    fn from(flags: FinderFlags) -> u16 {
+        flags.bits
+    }
+}
+
+/// Lists the names of the set flags, followed by the color, any other bits in hex, and any legacy
+/// bits, e.g. `FinderFlags(IS_INVISIBLE | HAS_BEEN_INITED | COLOR(2))`.
+impl fmt::Debug for FinderFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FinderFlags(")?;
+        let mut remaining = self.bits & !Self::COLOR.bits;
+        let mut first = true;
+        let mut sep = |f: &mut fmt::Formatter<'_>| {
+            let res = if first { Ok(()) } else { f.write_str(" | ") };
+            first = false;
+            res
+        };
+        for (flag, name) in Self::NAMED {
+            if self.contains(flag) {
+                sep(f)?;
+                f.write_str(name)?;
+                remaining &= !flag.bits;
+            }
+        }
+        if self.color() != 0 {
+            sep(f)?;
+            write!(f, "COLOR({})", self.color())?;
+        }
+        if remaining != 0 {
+            sep(f)?;
+            write!(f, "{:#06x}", remaining)?;
+        }
+        if self.legacy != 0 {
+            sep(f)?;
+            write!(f, "legacy: {:#06x}", self.legacy)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// The extended Finder flags of a file.
+///
+/// In Mac OS 8.5 and later the `fdScript` and `fdXFlags` bytes of the extended Finder information
+/// were combined into a 16-bit `extendedFinderFlags` field, with `fdScript` as the high byte.
+/// When its high bit ([Self::ARE_INVALID]) is set the remaining bits hold a script code rather
+/// than flags.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedFinderFlags(u16);
+
+impl ExtendedFinderFlags {
+    /// The other extended flags are not valid, the field holds a script code
+    pub const ARE_INVALID: ExtendedFinderFlags = ExtendedFinderFlags(0x8000);
+    /// The file has a custom badge resource
+    pub const HAS_CUSTOM_BADGE: ExtendedFinderFlags = ExtendedFinderFlags(0x0100);
+    /// The file is busy, e.g. being synchronized
+    pub const OBJECT_IS_BUSY: ExtendedFinderFlags = ExtendedFinderFlags(0x0080);
+    /// The file has routing information, used by the Finder to route it when dropped on a
+    /// system folder
+    pub const HAS_ROUTING_INFO: ExtendedFinderFlags = ExtendedFinderFlags(0x0004);
+
+    const NAMED: [(ExtendedFinderFlags, &'static str); 4] = [
+        (Self::ARE_INVALID, "ARE_INVALID"),
+        (Self::HAS_CUSTOM_BADGE, "HAS_CUSTOM_BADGE"),
+        (Self::OBJECT_IS_BUSY, "OBJECT_IS_BUSY"),
+        (Self::HAS_ROUTING_INFO, "HAS_ROUTING_INFO"),
+    ];
+
+    /// Construct from the raw 16-bit value, `fdScript` followed by `fdXFlags`.
+    pub const fn from_bits(bits: u16) -> ExtendedFinderFlags {
+        ExtendedFinderFlags(bits)
+    }
+
+    /// No flags set.
+    pub const fn empty() -> ExtendedFinderFlags {
+        ExtendedFinderFlags(0)
+    }
+
+    /// The raw 16-bit value.
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if no flags are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all the flags in `other` are set.
+    pub const fn contains(self, other: ExtendedFinderFlags) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for ExtendedFinderFlags {
+    type Output = ExtendedFinderFlags;
+
+    fn bitor(self, rhs: ExtendedFinderFlags) -> ExtendedFinderFlags {
+        ExtendedFinderFlags(self.0 | rhs.0)
+    }
+}
+
+/// Lists the names of the set flags, followed by any other bits in hex, e.g.
+/// `ExtendedFinderFlags(HAS_ROUTING_INFO)`.
+impl fmt::Debug for ExtendedFinderFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ExtendedFinderFlags(")?;
+        let mut remaining = self.0;
+        for (flag, name) in Self::NAMED {
+            if self.contains(flag) {
+                if remaining != self.0 {
+                    f.write_str(" | ")?;
+                }
+                f.write_str(name)?;
+                remaining &= !flag.0;
+            }
+        }
+        if remaining != 0 {
+            if remaining != self.0 {
+                f.write_str(" | ")?;
+            }
+            write!(f, "{:#06x}", remaining)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// The Finder label of a file.
+///
+/// The variants are named after the default label colors. The label names, used by [Display],
+/// are the System 7 defaults. They could be renamed by the user but this isn't recorded with the
+/// file.
+///
+/// [Display]: fmt::Display
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Label {
+    /// No label
+    #[default]
+    None,
+    /// Gray, "Project 2"
+    Gray,
+    /// Green, "Project 1"
+    Green,
+    /// Purple, "Personal"
+    Purple,
+    /// Blue, "Cool"
+    Blue,
+    /// Yellow, "In Progress"
+    Yellow,
+    /// Red, "Hot"
+    Red,
+    /// Orange, "Essential"
+    Orange,
+}
+
+impl Label {
+    /// Construct from the 3-bit label value.
+    ///
+    /// Only the lowest three bits of `bits` are used.
+    pub const fn from_bits(bits: u8) -> Label {
+        match bits & 0x7 {
+            0 => Label::None,
+            1 => Label::Gray,
+            2 => Label::Green,
+            3 => Label::Purple,
+            4 => Label::Blue,
+            5 => Label::Yellow,
+            6 => Label::Red,
+            _ => Label::Orange,
+        }
+    }
+
+    /// The 3-bit label value.
+    pub const fn as_bits(self) -> u8 {
+        self as u8
+    }
+}
+
+impl From<FinderFlags> for Label {
+    fn from(flags: FinderFlags) -> Label {
+        Label::from_bits(flags.color())
+    }
+}
+
+impl fmt::Display for Label {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Label::None => "None",
+            Label::Gray => "Project 2",
+            Label::Green => "Project 1",
+            Label::Purple => "Personal",
+            Label::Blue => "Cool",
+            Label::Yellow => "In Progress",
+            Label::Red => "Hot",
+            Label::Orange => "Essential",
+        };
+        f.pad(name)
+    }
+}
+
+/// The location of a file in the Finder.
+///
+/// The values are signed: the Finder uses negative coordinates for items that have never been
+/// positioned, and negative folder IDs for special locations such as the desktop (-2) and the
+/// trash (-3).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FinderPosition {
+    /// Vertical position of the file's icon within its window (`fdLocation.v`)
+    pub v: i16,
+    /// Horizontal position of the file's icon within its window (`fdLocation.h`)
+    pub h: i16,
+    /// Window or folder ID (`fdFldr`)
+    pub folder_id: i16,
+}
+
+impl FinderPosition {
+    /// Returns `true` if the icon has a position in its window.
+    ///
+    /// The Finder stores `(-1, -1)` for icons it should place automatically.
+    pub fn is_positioned(&self) -> bool {
+        (self.v, self.h) != (-1, -1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finder_flags() {
+        let flags = FinderFlags::IS_INVISIBLE | FinderFlags::IS_STATIONERY;
+        assert_eq!(flags.bits(), 0x4800);
+        assert!(flags.contains(FinderFlags::IS_INVISIBLE));
+        assert!(!flags.contains(FinderFlags::IS_ALIAS));
+        assert_eq!(
+            flags & FinderFlags::IS_STATIONERY,
+            FinderFlags::IS_STATIONERY
+        );
+        assert!(FinderFlags::empty().is_empty());
+        assert_eq!(FinderFlags::from_bits(0x000C).color(), 6);
+    }
+
+    #[test]
+    fn test_finder_flags_debug() {
+        assert_eq!(format!("{:?}", FinderFlags::empty()), "FinderFlags()");
+        assert_eq!(
+            format!("{:?}", FinderFlags::from_bits(0x4104)),
+            "FinderFlags(IS_INVISIBLE | HAS_BEEN_INITED | COLOR(2))"
+        );
+        // isOnDesk and a reserved bit
+        assert_eq!(
+            format!("{:?}", FinderFlags::from_bits(0x0201)),
+            "FinderFlags(0x0201)"
+        );
+        assert_eq!(
+            format!("{:?}", FinderFlags::for_version(0xC100, Version::I)),
+            "FinderFlags(IS_INVISIBLE | HAS_BEEN_INITED | legacy: 0x8000)"
+        );
+    }
+
+    #[test]
+    fn test_extended_finder_flags() {
+        let flags = ExtendedFinderFlags::from_bits(0x0184);
+        assert!(flags.contains(ExtendedFinderFlags::HAS_CUSTOM_BADGE));
+        assert!(flags
+            .contains(ExtendedFinderFlags::OBJECT_IS_BUSY | ExtendedFinderFlags::HAS_ROUTING_INFO));
+        assert!(!flags.contains(ExtendedFinderFlags::ARE_INVALID));
+        assert_eq!(
+            format!("{:?}", flags),
+            "ExtendedFinderFlags(HAS_CUSTOM_BADGE | OBJECT_IS_BUSY | HAS_ROUTING_INFO)"
+        );
+        assert_eq!(
+            format!("{:?}", ExtendedFinderFlags::from_bits(0x0006)),
+            "ExtendedFinderFlags(HAS_ROUTING_INFO | 0x0002)"
+        );
+        assert_eq!(
+            format!("{:?}", ExtendedFinderFlags::empty()),
+            "ExtendedFinderFlags()"
+        );
+    }
+
+    #[test]
+    fn test_label() {
+        for bits in 0..8 {
+            assert_eq!(Label::from_bits(bits).as_bits(), bits);
+        }
+        assert_eq!(Label::from_bits(0xF2), Label::Green);
+        assert_eq!(Label::from(FinderFlags::from_bits(0x010C)), Label::Red);
+        assert_eq!(Label::Orange.to_string(), "Essential");
+        assert_eq!(Label::None.to_string(), "None");
+    }
+
+    #[test]
+    fn test_finder_flags_for_version() {
+        // isAlias (locked in MacBinary I), isInvisible, isStationery (bozo in MacBinary I),
+        // hasBeenInited, hasNoINITs
+        let bits = 0xC980;
+
+        let flags = FinderFlags::for_version(bits, Version::I);
+        assert_eq!(flags.bits(), 0x4100);
+        assert_eq!(flags.legacy_raw(), 0x8880);
+        assert!(flags.contains(FinderFlags::IS_INVISIBLE | FinderFlags::HAS_BEEN_INITED));
+        assert!(!flags.contains(FinderFlags::IS_ALIAS));
+        assert!(!flags.contains(FinderFlags::IS_STATIONERY));
+
+        for version in [Version::II, Version::III] {
+            let flags = FinderFlags::for_version(bits, version);
+            assert_eq!(flags, FinderFlags::from_bits(bits));
+            assert_eq!(flags.legacy_raw(), 0);
+            assert!(flags.contains(FinderFlags::IS_ALIAS | FinderFlags::IS_STATIONERY));
+            assert!(flags.contains(FinderFlags::HAS_NO_INITS));
+        }
+    }
+}
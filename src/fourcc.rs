@@ -0,0 +1,5 @@
+//! Four-character codes.
+//!
+//! See [FourCC](crate::FourCC).
+
+pub mod consts;
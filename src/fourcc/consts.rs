@@ -0,0 +1,37 @@
+//! Named constants for common type, creator, and resource type codes.
+//!
+//! ```
+//! use macbinary::fourcc::consts::TEXT;
+//! use macbinary::FourCC;
+//!
+//! assert_eq!(TEXT, FourCC::new(*b"TEXT"));
+//! ```
+
+use crate::FourCC;
+
+/// Plain text file type
+pub const TEXT: FourCC = FourCC::new(*b"TEXT");
+/// Application file type
+pub const APPL: FourCC = FourCC::new(*b"APPL");
+/// MacBinary III signature
+pub const MBIN: FourCC = FourCC::new(*b"mBIN");
+/// Icon list resource type, `'ICN#'`
+pub const ICN_HASH: FourCC = FourCC::new(*b"ICN#");
+/// String resource type, `'STR '`
+pub const STR: FourCC = FourCC::new(*b"STR ");
+/// String list resource type, `'STR#'`
+pub const STR_HASH: FourCC = FourCC::new(*b"STR#");
+/// Version resource type
+pub const VERS: FourCC = FourCC::new(*b"vers");
+/// Application memory requirements resource type
+pub const SIZE: FourCC = FourCC::new(*b"SIZE");
+/// Bundle resource type
+pub const BNDL: FourCC = FourCC::new(*b"BNDL");
+/// File reference resource type
+pub const FREF: FourCC = FourCC::new(*b"FREF");
+/// Sound resource type, `'snd '`
+pub const SND: FourCC = FourCC::new(*b"snd ");
+/// QuickDraw picture resource type and file type
+pub const PICT: FourCC = FourCC::new(*b"PICT");
+/// TrueType font resource type
+pub const SFNT: FourCC = FourCC::new(*b"sfnt");
@@ -3,6 +3,10 @@
 
 //! MacBinary and resource fork parser
 //!
+//! MacBinary files are parsed with [parse]. Resource forks that aren't wrapped in MacBinary,
+//! such as those copied from `file/..namedfork/rsrc`, are parsed with [parse_resource_fork], and
+//! [detect_resource_fork] tells the two apart.
+//!
 //! ### Specifications:
 //!
 //! - [MacBinary I](https://web.archive.org/web/20050307030202/http://www.lazerware.com/formats/macbinary/macbinary.html)
@@ -12,48 +16,345 @@
 //! #### Other references:
 //!
 //! - [Detecting MacBinary format](https://entropymine.wordpress.com/2019/02/13/detecting-macbinary-format/)
+//!
+//! ### Thread safety
+//!
+//! All public types are [Send] and [Sync]. Parsed values only borrow the input, so a
+//! [MacBinary] or [ResourceFork] can be shared between threads for as long as the input lives.
+//! Any caching added to these types must keep this guarantee, e.g. by using `OnceLock` rather
+//! than `OnceCell`.
 
 // TODO
 // - no_std/WASM
 // - zero-copy, ttf-parser style
 
-use core::fmt::{self, Display, Formatter};
+use core::fmt::{self, Display, Formatter, Write as _};
+use core::str::FromStr;
+#[cfg(not(feature = "no_std"))]
+use std::io::Write;
 
-use crc::{Crc, CRC_16_XMODEM};
+use crc::{Crc, Digest, CRC_16_XMODEM};
 #[cfg(feature = "no_std")]
 use heapless::String;
 
 use crate::binary::read::{ReadBinary, ReadBinaryDep, ReadCtxt, ReadFrom, ReadScope};
 use crate::binary::{NumFrom, U32Be};
-use crate::macroman::FromMacRoman;
+use crate::digest::Algo;
+use crate::encoder::{EncoderHint, EncoderRule, Observations};
+use crate::fourcc::consts::MBIN;
+use crate::macroman::{char_to_macroman, macroman_to_char, DebugMacRoman, FromMacRoman};
+use crate::resource::types::{TypedResource, VersionResource};
 
 pub(crate) mod binary;
+mod date;
+pub mod digest;
+pub mod encoder;
 pub(crate) mod error;
+mod finder;
+pub mod fourcc;
+#[cfg(all(feature = "macos", target_os = "macos", not(feature = "no_std")))]
+pub mod macos;
 mod macroman;
+#[cfg(not(feature = "no_std"))]
+mod owned;
+pub mod prelude;
 pub mod resource;
+#[cfg(not(feature = "no_std"))]
+pub mod snapshot;
 #[cfg(test)]
 mod test;
+#[cfg(not(feature = "no_std"))]
+pub mod validate;
 #[cfg(target_family = "wasm")]
 mod wasm;
 
-const MBIN_SIG: u32 = u32::from_be_bytes(*b"mBIN");
+/// Size of a MacBinary block.
+///
+/// The header is one block long, and the secondary header and forks that follow it are each
+/// padded to a multiple of the block size.
+pub const BLOCK_SIZE: usize = 128;
+/// Number of header bytes covered by the CRC
+const CRC_LEN: usize = 124;
+
+static CRC: Crc<u16> = Crc::<u16>::new(&CRC_16_XMODEM);
 
-pub use crate::error::ParseError;
-pub use crate::resource::ResourceFork;
+pub use crate::date::DisplayMacDate;
+#[cfg(not(feature = "no_std"))]
+pub use crate::error::{ExtractError, MacRomanError};
+pub use crate::error::{FourCCError, ParseError};
+pub use crate::finder::{ExtendedFinderFlags, FinderFlags, FinderPosition, Label};
+#[cfg(not(feature = "no_std"))]
+pub use crate::macroman::{decode_with_policy, DecodePolicy};
+#[cfg(not(feature = "no_std"))]
+pub use crate::owned::{OwnedMacBinary, OwnedResourceFork};
+#[cfg(not(feature = "no_std"))]
+pub use crate::resource::{ForkStats, ResourceSize, TypeStats};
+pub use crate::resource::{ResourceFork, ResourceForkHeader};
+#[cfg(not(feature = "no_std"))]
+use crate::validate::{Issue, Issues, Severity};
 
 /// A four-character code
 ///
 /// A 32-bit number that typically holds 4 8-bit ASCII characters, used for type and creator
 /// codes, and resource types. Eg. `mBIN` `SIZE` `ICON` `APPL`.
-#[derive(Copy, Clone, Eq, PartialEq)]
+///
+/// Codes can be compared with byte arrays and strings, e.g. `item.resource_type() == *b"ICN#"`
+/// or `item.resource_type() == "ICN#"`. They are ordered by their bytes.
+///
+/// With the `serde` feature codes are serialized as strings, see the `Serialize` implementation.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub struct FourCC(pub u32);
 
+/// One of the two forks of a file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ForkKind {
+    /// The data fork
+    Data,
+    /// The resource fork
+    Resource,
+}
+
 /// A parsed MacBinary file containing metadata, data fork (if present), and resource fork (if present)
 pub struct MacBinary<'a> {
+    /// The complete input, from the start of the header
+    data: &'a [u8],
     version: Version,
     header: Header<'a>,
+    /// The secondary header, without the padding that follows it
+    secondary_header: Option<&'a [u8]>,
     data_fork: &'a [u8],
     rsrc_fork: &'a [u8],
+    /// Padding following the data fork
+    data_padding: &'a [u8],
+    /// Padding following the resource fork, possibly truncated by the end of the file
+    rsrc_padding: &'a [u8],
+    secondary_header_ignored: bool,
+    /// CRC calculated from the header data
+    computed_crc: u16,
+    /// The options the file was parsed with
+    #[cfg_attr(feature = "no_std", allow(unused))]
+    options: ParseOptions,
+}
+
+/// The metadata needed to construct a [MacBinary] with [MacBinary::from_parts].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct HeaderInfo<'a> {
+    /// MacBinary version
+    pub version: Version,
+    /// Raw file name bytes, in MacRoman
+    pub filename: &'a [u8],
+    /// Finder metadata of the file
+    pub finder_info: FinderInfo,
+}
+
+/// Metadata read by [probe] without reading the forks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Probe<'a> {
+    /// Metadata from the header
+    pub info: HeaderInfo<'a>,
+    /// Declared length of the data fork
+    pub data_fork_len: u32,
+    /// Declared length of the resource fork
+    pub rsrc_fork_len: u32,
+    /// The header of the resource fork
+    ///
+    /// `None` if there is no resource fork, or the input ends before the end of its header.
+    pub rsrc_header: Option<ResourceForkHeader>,
+}
+
+/// A contiguous region of a MacBinary file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Region<'a> {
+    /// Offset of the region from the start of the file
+    pub offset: usize,
+    /// The bytes of the region
+    pub data: &'a [u8],
+}
+
+/// A map of all the regions of a MacBinary file.
+///
+/// The regions are contiguous and, in field order, cover the whole input. Regions that aren't
+/// present in the file are empty, with an offset where they would start.
+///
+/// Returned by [MacBinary::regions].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Regions<'a> {
+    /// The 128-byte header
+    pub header: Region<'a>,
+    /// The secondary header, including the padding that follows it
+    ///
+    /// This is empty if the secondary header length was ignored, see
+    /// [MacBinary::secondary_header_ignored].
+    pub secondary_header: Region<'a>,
+    /// The data fork
+    pub data_fork: Region<'a>,
+    /// Padding following the data fork
+    pub data_padding: Region<'a>,
+    /// The resource fork
+    pub rsrc_fork: Region<'a>,
+    /// Padding following the resource fork, which may be truncated or missing
+    pub rsrc_padding: Region<'a>,
+    /// The Get Info comment, which may be truncated or missing
+    pub comment: Region<'a>,
+    /// Any data following the comment
+    pub trailing: Region<'a>,
+}
+
+/// The location of non-zero bytes in the padding following a fork.
+///
+/// Returned by [MacBinary::nonzero_padding].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct NonZeroPadding<'a> {
+    /// File offset of the first non-zero padding byte
+    pub offset: usize,
+    /// Up to [Self::PREVIEW_LEN] bytes of padding starting at `offset`
+    pub preview: &'a [u8],
+}
+
+/// Finder metadata for a file.
+///
+/// This collects the fields needed to recreate the Finder's view of a file when extracting it,
+/// e.g. to an AppleDouble file or extended attributes. The flags and position fields are stored
+/// as they appear in the `FInfo` and `FXInfo` records.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct FinderInfo {
+    /// File type code
+    pub file_type: FourCC,
+    /// File creator code
+    pub file_creator: FourCC,
+    /// Finder flags (`fdFlags`)
+    pub finder_flags: u16,
+    /// Vertical position of the file's icon within its window
+    pub vpos: i16,
+    /// Horizontal position of the file's icon within its window
+    pub hpos: i16,
+    /// Window or folder ID (`fdFldr`)
+    pub folder_id: i16,
+    /// Script of the file name (`fdScript`)
+    pub script: u8,
+    /// Extended Finder flags (`fdXFlags`)
+    pub extended_finder_flags: u8,
+    /// Creation date (Mac OS timestamp)
+    pub created: u32,
+    /// Last modified date (Mac OS timestamp)
+    pub modified: u32,
+    /// Whether the file is protected
+    pub protected: bool,
+}
+
+/// Summary statistics about a parsed MacBinary file.
+///
+/// Created with [MacBinary::stats].
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    any(feature = "serde", all(target_family = "wasm", feature = "wasm-full")),
+    derive(serde::Serialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+pub struct Stats {
+    /// MacBinary version of the file
+    pub version: Version,
+    /// Length of the data fork in bytes
+    pub data_fork_len: u32,
+    /// Length of the resource fork in bytes
+    pub rsrc_fork_len: u32,
+    /// Number of padding bytes following the data fork
+    pub data_padding_len: u32,
+    /// Number of padding bytes following the resource fork
+    pub rsrc_padding_len: u32,
+    /// Number of resource types, `None` if there is no resource fork or it could not be parsed
+    pub resource_type_count: Option<usize>,
+    /// Number of resources, `None` if there is no resource fork or it could not be parsed
+    pub resource_count: Option<usize>,
+    /// Length of the resource name list in bytes, `None` if there is no resource fork or it
+    /// could not be parsed
+    pub name_list_len: Option<usize>,
+    /// Whether the header declares a Get Info comment
+    pub has_comment: bool,
+    /// Whether the header declares a secondary header
+    pub has_secondary_header: bool,
+    /// Whether the CRC stored in the header matches the CRC calculated from it
+    ///
+    /// MacBinary I headers don't have a CRC so this is usually `false` for them.
+    pub crc_matches: bool,
+}
+
+/// An owned copy of the metadata in a MacBinary header.
+///
+/// Unlike [MacBinary] this doesn't borrow the input, so it can be kept after the input is
+/// dropped. Created with [MacBinary::metadata].
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Metadata {
+    /// MacBinary version of the file
+    pub version: Version,
+    /// File name, decoded from MacRoman
+    pub filename: String,
+    /// File type code
+    pub file_type: FourCC,
+    /// File creator code
+    pub file_creator: FourCC,
+    /// Finder flags
+    pub finder_flags: FinderFlags,
+    /// Creation date (UNIX timestamp), see [MacBinary::created_unix]
+    pub created: i64,
+    /// Last modified date (UNIX timestamp), see [MacBinary::modified_unix]
+    pub modified: i64,
+    /// Length of the data fork in bytes, as declared in the header
+    pub data_fork_len: u32,
+    /// Length of the resource fork in bytes, as declared in the header
+    pub rsrc_fork_len: u32,
+    /// Script of the file name (`fdScript`)
+    pub script: u8,
+    /// Whether the file is protected
+    pub protected: bool,
+    /// Finder label
+    pub label: Label,
+    /// Position of the file's icon and the folder containing it
+    pub position: FinderPosition,
+}
+
+/// Options for [MacBinary::filename_sanitized].
+///
+/// By default control characters are removed and the name isn't truncated.
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct SanitizeOptions {
+    max_len: Option<usize>,
+    control_replacement: Option<char>,
+}
+
+/// A file name made safe to use as a path component, returned by [MacBinary::filename_sanitized].
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SanitizedFilename {
+    /// The sanitized name
+    pub name: String,
+    /// Whether the name differs from [MacBinary::filename]
+    pub changed: bool,
+}
+
+/// Incremental calculation of the MacBinary II/III header CRC.
+///
+/// This allows the CRC to be verified as a header arrives in chunks. The CRC covers the first 124
+/// bytes of the header. Any bytes supplied after those are ignored, so the whole header can be
+/// passed to [HeaderCrc::update].
+pub struct HeaderCrc {
+    digest: Digest<'static, u16>,
+    remaining: usize,
+}
+
+/// Options that control how strictly MacBinary data is parsed.
+///
+/// By default only the constraints needed to parse the file are checked, and data that can't be
+/// parsed, such as a truncated fork, is reported as an error. [ParseOptions::strict] enables the
+/// remaining checks of the specification.
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct ParseOptions {
+    strict: bool,
+    ignore_bad_secondary_header: bool,
+    allow_truncated: bool,
 }
 
 /// MacBinary header
@@ -66,10 +367,15 @@ struct Header<'a> {
     file_type: FourCC,
     file_creator: FourCC,
     finder_flags: u8,
-    vpos: u16,
-    hpos: u16,
-    window_or_folder_id: u16,
-    protected: bool,
+    /// Byte 74, must be zero
+    zero_fill1: u8,
+    vpos: i16,
+    hpos: i16,
+    window_or_folder_id: i16,
+    /// Byte 81, the protected flag is the low-order bit
+    protected: u8,
+    /// Byte 82, must be zero
+    zero_fill2: u8,
     created: u32,
     modified: u32,
     comment_len: u16,
@@ -90,13 +396,24 @@ struct Header<'a> {
     /// https://developer.apple.com/library/archive/documentation/mac/pdf/MacintoshToolboxEssentials.pdf
     script: u8,
     extended_finder_flags: u8,
+    /// Bytes 108-115, unused
+    reserved: &'a [u8],
+    /// Length of total files when packed files are unpacked
+    unpacked_total_len: u32,
     version: u8,
     min_version: u8,
     crc: u16,
+    /// Reserved for computer type and OS ID
+    os_id: u16,
 }
 
 /// MacBinary version.
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone)]
+#[cfg_attr(
+    any(feature = "serde", all(target_family = "wasm", feature = "wasm-full")),
+    derive(serde::Serialize)
+)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
 pub enum Version {
     /// MacBinary I
     I = 1,
@@ -109,10 +426,10 @@ pub enum Version {
 /// Determine if the supplied data looks like MacBinary data.
 pub fn detect(data: &[u8]) -> Option<Version> {
     // All MacBinary files start with a 128-byte header and the first byte is zero
-    (data.len() >= 128 && data[0] == 0).then_some(())?;
+    (data.len() >= BLOCK_SIZE && data[0] == 0).then_some(())?;
 
     // To determine if a header is a valid MacBinary header, first take advantage of the new MacBinary III signature located at offset 102
-    if ReadScope::new(&data[102..][..4]).read::<FourCC>() == Ok(FourCC(MBIN_SIG)) {
+    if ReadScope::new(&data[102..][..4]).read::<FourCC>() == Ok(MBIN) {
         return Some(Version::III);
     }
 
@@ -122,7 +439,7 @@ pub fn detect(data: &[u8]) -> Option<Version> {
     }
 
     let crc = u16::from_be_bytes(data[124..][..2].try_into().unwrap());
-    if crc == calc_crc(&data[..124]) {
+    if crc == calc_crc(&data[..CRC_LEN]) {
         return Some(Version::II);
     }
 
@@ -134,9 +451,9 @@ pub fn detect(data: &[u8]) -> Option<Version> {
     let data_fork_len = u32::from_be_bytes(data[83..][..4].try_into().unwrap());
     let rsrc_fork_len = u32::from_be_bytes(data[87..][..4].try_into().unwrap());
     let macbinary1 = data[101..=125].iter().all(|byte| *byte == 0)
-        && (1..=63).contains(&data[1])
-        && data_fork_len <= 0x007F_FFFF
-        && rsrc_fork_len <= 0x007F_FFFF;
+        && (1..=Version::I.max_filename_len()).contains(&data[1])
+        && data_fork_len <= Version::I.max_fork_len()
+        && rsrc_fork_len <= Version::I.max_fork_len();
 
     if macbinary1 {
         Some(Version::I)
@@ -145,12 +462,431 @@ pub fn detect(data: &[u8]) -> Option<Version> {
     }
 }
 
+/// Determine if the supplied data looks like a complete MacBinary file.
+///
+/// In addition to the checks made by [detect], the data must be long enough to hold the
+/// secondary header and forks declared in the header. The padding after the resource fork and
+/// the Get Info comment that follows it aren't required, as they're often left off.
+///
+/// Use [detect] when only the start of the file is available, such as when it's being streamed.
+pub fn detect_complete(data: &[u8]) -> Option<Version> {
+    let version = detect(data)?;
+    let secondary_header_len = u16::from_be_bytes(data[120..][..2].try_into().unwrap());
+    let data_fork_len = u32::from_be_bytes(data[83..][..4].try_into().unwrap());
+    let rsrc_fork_len = u32::from_be_bytes(data[87..][..4].try_into().unwrap());
+    let secondary_header_len = match version {
+        Version::I => 0,
+        Version::II | Version::III => secondary_header_len,
+    };
+    let required = required_len(secondary_header_len, data_fork_len, rsrc_fork_len).ok()?;
+    (required <= data.len() as u64).then_some(version)
+}
+
+/// The minimum length of a file with the supplied header fields.
+///
+/// This includes the header, secondary header, and data fork with their padding, and the
+/// resource fork without its padding.
+fn required_len(
+    secondary_header_len: u16,
+    data_fork_len: u32,
+    rsrc_fork_len: u32,
+) -> Result<u64, ParseError> {
+    Ok(BLOCK_SIZE as u64
+        + u64::from(pad_to_block(secondary_header_len)?)
+        + pad_to_block(u64::from(data_fork_len))?
+        + u64::from(rsrc_fork_len))
+}
+
+/// The total length of the MacBinary file with the supplied 128-byte header.
+///
+/// This is the length of the header, secondary header, data fork, resource fork, and Get Info
+/// comment, each padded to a multiple of [BLOCK_SIZE]. Only the header is needed, so it can be
+/// used to skip over a file without reading its forks, e.g. when several files are stored one
+/// after another. See [MacBinary::encoded_len].
+///
+/// Returns [ParseError::BadVersion] if the header isn't a MacBinary header, or
+/// [ParseError::Overflow] if the lengths in the header add up to more than can be represented.
+pub fn encoded_len_from_header(header: &[u8]) -> Result<usize, ParseError> {
+    let Some(version) = detect(header) else {
+        return Err(ParseError::BadVersion);
+    };
+    ReadScope::new(header)
+        .read::<Header<'_>>()?
+        .encoded_len(version)
+}
+
 /// Parse a MacBinary encoded file.
 pub fn parse(data: &[u8]) -> Result<MacBinary<'_>, ParseError> {
+    parse_with_options(data, ParseOptions::default())
+}
+
+/// Parse a MacBinary encoded file using the supplied options.
+pub fn parse_with_options(data: &[u8], options: ParseOptions) -> Result<MacBinary<'_>, ParseError> {
+    let Some(version) = detect(data) else {
+        return Err(ParseError::BadVersion); // FIXME: Better error type
+    };
+    ReadScope::new(data).read_dep::<MacBinary<'_>>((version, options))
+}
+
+/// Determine if the supplied data looks like a raw resource fork, without a MacBinary header.
+///
+/// The resource data and resource map described by the fork header must lie within `data`
+/// without overlapping, the map must start with a copy of the fork header, and the map must be
+/// readable. A map whose copy of the header is zeroed is also accepted, as some tools don't fill
+/// it in.
+pub fn detect_resource_fork(data: &[u8]) -> bool {
+    let Ok(header) = ReadScope::new(data).read::<ResourceForkHeader>() else {
+        return false;
+    };
+    let len = data.len() as u64;
+    let data_start = u64::from(header.data_offset);
+    let data_end = data_start + u64::from(header.data_len);
+    let map_start = u64::from(header.map_offset);
+    let map_end = map_start + u64::from(header.map_len);
+    // Both areas follow the header, and the map holds at least its header and the type count
+    if data_start < 16 || map_start < 16 || header.map_len < 30 {
+        return false;
+    }
+    if data_end > len || map_end > len || (data_start < map_end && map_start < data_end) {
+        return false;
+    }
+    let map_copy = &data[usize::num_from(header.map_offset)..][..16];
+    if map_copy != &data[..16] && map_copy.iter().any(|&byte| byte != 0) {
+        return false;
+    }
+    ResourceFork::new(data).is_ok()
+}
+
+/// Parse a raw resource fork, without a MacBinary header.
+///
+/// This is the same as [ResourceFork::new].
+pub fn parse_resource_fork(data: &[u8]) -> Result<ResourceFork<'_>, ParseError> {
+    ResourceFork::new(data)
+}
+
+/// Read the header of a MacBinary file and the header of its resource fork, without reading the
+/// forks.
+///
+/// This only needs the start of the file: the 128-byte header, and optionally enough of the
+/// following data to include the 16-byte header of the resource fork. It can be used to decide
+/// whether a file is worth passing to [parse], e.g. to find files with resources when only the
+/// start of each file is available.
+///
+/// The header is checked in the same way as [parse].
+pub fn probe(data: &[u8]) -> Result<Probe<'_>, ParseError> {
     let Some(version) = detect(data) else {
-        return Err(ParseError::BadVersion) // FIXME: Better error type
+        return Err(ParseError::BadVersion);
+    };
+    let header = ReadScope::new(data).read::<Header<'_>>()?;
+    let crc = calc_crc(&data[..CRC_LEN]);
+    if version >= Version::II && crc != header.crc {
+        return Err(ParseError::CrcMismatch {
+            expected: header.crc,
+            actual: crc,
+        });
+    }
+    version.check_header(&header, ParseOptions::default().strict)?;
+
+    let rsrc_header = if header.rsrc_fork_len >= 16 {
+        let rsrc_offset = u64::from(pad_to_block(header.secondary_header_len)?)
+            + pad_to_block(u64::from(header.data_fork_len))?;
+        usize::try_from(rsrc_offset)
+            .ok()
+            .and_then(|offset| data.get(BLOCK_SIZE..)?.get(offset..))
+            .and_then(|rsrc| ReadScope::new(rsrc).read::<ResourceForkHeader>().ok())
+    } else {
+        None
     };
-    ReadScope::new(data).read_dep::<MacBinary<'_>>(version)
+
+    Ok(Probe {
+        info: HeaderInfo {
+            version,
+            filename: header.filename,
+            finder_info: header.finder_info(),
+        },
+        data_fork_len: header.data_fork_len,
+        rsrc_fork_len: header.rsrc_fork_len,
+        rsrc_header,
+    })
+}
+
+/// Check that the CRC stored in a MacBinary II/III header matches its contents.
+pub fn verify_header_crc(header: &[u8; 128]) -> Result<(), ParseError> {
+    let stored = u16::from_be_bytes([header[124], header[125]]);
+    let crc = calc_crc(&header[..CRC_LEN]);
+    if crc == stored {
+        Ok(())
+    } else {
+        Err(ParseError::CrcMismatch {
+            expected: stored,
+            actual: crc,
+        })
+    }
+}
+
+impl HeaderCrc {
+    /// Start a new CRC calculation.
+    pub fn new() -> Self {
+        HeaderCrc {
+            digest: CRC.digest(),
+            remaining: CRC_LEN,
+        }
+    }
+
+    /// Add the next chunk of header data to the calculation.
+    pub fn update(&mut self, data: &[u8]) {
+        let len = data.len().min(self.remaining);
+        self.digest.update(&data[..len]);
+        self.remaining -= len;
+    }
+
+    /// Returns `true` once all the bytes covered by the CRC have been supplied.
+    pub fn is_complete(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Finish the calculation, returning the CRC.
+    pub fn finalize(self) -> u16 {
+        self.digest.finalize()
+    }
+}
+
+impl Default for HeaderCrc {
+    fn default() -> Self {
+        HeaderCrc::new()
+    }
+}
+
+impl Version {
+    /// The version number: 1, 2, or 3.
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+
+    /// The value that encoders write to the version byte of the header for this version.
+    ///
+    /// This is 129 for MacBinary II and 130 for MacBinary III. MacBinary I headers don't have a
+    /// version byte, the field is zero.
+    pub const fn header_version_byte(self) -> u8 {
+        match self {
+            Version::I => 0,
+            Version::II => 129,
+            Version::III => 130,
+        }
+    }
+
+    /// The maximum fork length allowed by this version of MacBinary.
+    ///
+    /// MacBinary I limits forks to 0x7FFFFF bytes, later versions use the full range of the
+    /// 32-bit length field.
+    pub const fn max_fork_len(self) -> u32 {
+        match self {
+            Version::I => 0x007F_FFFF,
+            Version::II | Version::III => u32::MAX,
+        }
+    }
+
+    /// The maximum file name length allowed by this version of MacBinary.
+    ///
+    /// MacBinary II and III limit file names to 31 bytes, the length of an HFS file name.
+    /// MacBinary I allows names up to the 63 byte size of the name field.
+    pub const fn max_filename_len(self) -> u8 {
+        match self {
+            Version::I => 63,
+            Version::II | Version::III => 31,
+        }
+    }
+
+    /// Check the header fields that have version specific constraints.
+    ///
+    /// This is used when parsing. `detect` applies the same limits to MacBinary I candidates.
+    fn check_header(self, header: &Header<'_>, strict: bool) -> Result<(), ParseError> {
+        let filename_len = header.filename.len();
+        if filename_len == 0 || filename_len > usize::from(self.max_filename_len()) {
+            return Err(ParseError::BadValue);
+        }
+
+        if strict {
+            let max_fork_len = self.max_fork_len();
+            if header.data_fork_len > max_fork_len
+                || header.rsrc_fork_len > max_fork_len
+                || header.zero_fill1 != 0
+                || header.zero_fill2 != 0
+            {
+                return Err(ParseError::BadValue);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a MacBinary encoded file and write its data fork to `out`.
+///
+/// Returns the number of bytes written. If the file is truncated an error is returned and nothing
+/// is written.
+#[cfg(not(feature = "no_std"))]
+pub fn extract_data_fork<W: Write>(data: &[u8], out: &mut W) -> Result<usize, ExtractError> {
+    extract_data_fork_with_options(data, ParseOptions::default(), out)
+}
+
+/// Parse a MacBinary encoded file using the supplied options and write its data fork to `out`.
+///
+/// Returns the number of bytes written. With [ParseOptions::allow_truncated] whatever part of
+/// the data fork is present is written, which may be nothing.
+#[cfg(not(feature = "no_std"))]
+pub fn extract_data_fork_with_options<W: Write>(
+    data: &[u8],
+    options: ParseOptions,
+    out: &mut W,
+) -> Result<usize, ExtractError> {
+    let file = parse_with_options(data, options)?;
+    out.write_all(file.data_fork())?;
+    Ok(file.data_fork().len())
+}
+
+/// Parse a MacBinary encoded file and write the data of the resource with the supplied type and
+/// id to `out`.
+///
+/// Returns the number of bytes written. If the file has no resource fork or the resource isn't
+/// present in it [ExtractError::NotFound] is returned.
+#[cfg(not(feature = "no_std"))]
+pub fn extract_resource<W: Write>(
+    data: &[u8],
+    rsrc_type: FourCC,
+    rsrc_id: i16,
+    out: &mut W,
+) -> Result<usize, ExtractError> {
+    let file = parse(data)?;
+    let rsrc = file.resource_fork()?.ok_or(ExtractError::NotFound)?;
+    let resource = rsrc
+        .get_resource(rsrc_type, rsrc_id)
+        .ok_or(ExtractError::NotFound)?;
+    out.write_all(resource.data())?;
+    Ok(resource.data().len())
+}
+
+impl ParseOptions {
+    /// Create a new set of the default, lenient, parsing options.
+    pub fn new() -> Self {
+        ParseOptions::default()
+    }
+
+    /// Enforce all of the constraints the specification places on the header.
+    ///
+    /// By default only the constraints needed to parse the file are checked. When strict parsing
+    /// is enabled the fork length limits of the version (see [Version::max_fork_len]) are
+    /// enforced and the zero fill bytes at offsets 74 and 82 must be zero.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Retry without the secondary header if skipping it fails.
+    ///
+    /// The secondary header length is meant for future expansion and writers must set it to
+    /// zero. When this option is enabled and skipping the declared secondary header runs past
+    /// the end of the data, parsing is retried once assuming there is no secondary header.
+    /// [MacBinary::secondary_header_ignored] reports when this happened.
+    pub fn ignore_bad_secondary_header(mut self, ignore: bool) -> Self {
+        self.ignore_bad_secondary_header = ignore;
+        self
+    }
+
+    /// Accept files that end part way through a fork.
+    ///
+    /// By default a fork that extends past the end of the data is an error. When this option is
+    /// enabled such forks hold whatever data is present, which may be none.
+    /// [MacBinary::data_fork_complete] and [MacBinary::resource_fork_complete] report whether each
+    /// fork was read in full.
+    pub fn allow_truncated(mut self, allow: bool) -> Self {
+        self.allow_truncated = allow;
+        self
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl SanitizeOptions {
+    /// Create the default sanitizing options.
+    pub fn new() -> Self {
+        SanitizeOptions::default()
+    }
+
+    /// Truncate the name to at most `max_len` bytes of UTF-8.
+    ///
+    /// The name is only ever truncated between characters.
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+
+    /// Replace control characters with `replacement` instead of removing them.
+    pub fn control_replacement(mut self, replacement: char) -> Self {
+        self.control_replacement = Some(replacement);
+        self
+    }
+}
+
+/// Make `name` safe to use as a path component.
+#[cfg(not(feature = "no_std"))]
+fn sanitize_filename(name: &str, options: SanitizeOptions) -> SanitizedFilename {
+    let max_len = options.max_len.unwrap_or(usize::MAX);
+    let mut sanitized = String::with_capacity(name.len());
+    for ch in name.chars() {
+        let ch = match ch {
+            // The HFS+ convention, a / in a Mac file name is shown as : in paths
+            '/' => Some(':'),
+            ch if ch.is_control() => options.control_replacement,
+            ch => Some(ch),
+        };
+        let Some(ch) = ch else {
+            continue;
+        };
+        if sanitized.len() + ch.len_utf8() > max_len {
+            break;
+        }
+        sanitized.push(ch);
+    }
+    SanitizedFilename {
+        changed: sanitized != name,
+        name: sanitized,
+    }
+}
+
+impl Header<'_> {
+    fn finder_info(&self) -> FinderInfo {
+        FinderInfo {
+            file_type: self.file_type,
+            file_creator: self.file_creator,
+            finder_flags: u16::from_be_bytes([self.finder_flags, self.finder_flags2]),
+            vpos: self.vpos,
+            hpos: self.hpos,
+            folder_id: self.window_or_folder_id,
+            script: self.script,
+            extended_finder_flags: self.extended_finder_flags,
+            created: self.created,
+            modified: self.modified,
+            protected: self.protected & 1 != 0,
+        }
+    }
+
+    /// The total length of a file with this header, including the padding of each part.
+    fn encoded_len(&self, version: Version) -> Result<usize, ParseError> {
+        // MacBinary I doesn't have a secondary header or comment
+        let (secondary_header_len, comment_len) = match version {
+            Version::I => (0, 0),
+            Version::II | Version::III => (self.secondary_header_len, self.comment_len),
+        };
+        [
+            usize::from(pad_to_block(secondary_header_len)?),
+            usize::num_from(pad_to_block(self.data_fork_len)?),
+            usize::num_from(pad_to_block(self.rsrc_fork_len)?),
+            usize::from(pad_to_block(comment_len)?),
+        ]
+        .into_iter()
+        .try_fold(BLOCK_SIZE, usize::checked_add)
+        .ok_or(ParseError::Overflow)
+    }
 }
 
 impl ReadBinary for Header<'_> {
@@ -159,10 +895,11 @@ impl ReadBinary for Header<'_> {
     fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
         // old version number, must be kept at zero for compatibility
         let _ = ctxt.read_u8()?;
-        // Length of filename (must be in the range 1-31)
+        // Length of filename (must be in the range 1-31, 1-63 for MacBinary I). The version
+        // specific limit is checked by `Version::check_header`.
         let filename_len = ctxt.read_u8()?;
-        ctxt.check((1..=31).contains(&filename_len))?; // TODO: 1-63?
-                                                       // filename (only "length" bytes are significant).
+        ctxt.check((1..=63).contains(&filename_len))?;
+        // filename (only "length" bytes are significant).
         let filename_data = ctxt.read_slice(63)?;
         // file type (normally expressed as four characters)
         let file_type = ctxt.read::<FourCC>()?;
@@ -171,17 +908,17 @@ impl ReadBinary for Header<'_> {
         // original Finder flags Bit 7 - isAlias. Bit 6 - isInvisible. Bit 5 - hasBundle. Bit 4 - nameLocked. Bit 3 - isStationery. Bit 2 - hasCustomIcon. Bit 1 - reserved. Bit 0 - hasBeenInited.
         let finder_flags = ctxt.read_u8()?;
         // zero fill, must be zero for compatibility
-        let _ = ctxt.read_u8()?;
+        let zero_fill1 = ctxt.read_u8()?;
         // file's vertical position within its window.
-        let vpos = ctxt.read_u16be()?;
+        let vpos = ctxt.read_i16be()?;
         // file's horizontal position within its window.
-        let hpos = ctxt.read_u16be()?;
+        let hpos = ctxt.read_i16be()?;
         // file's window or folder ID.
-        let window_or_folder_id = ctxt.read_u16be()?;
+        let window_or_folder_id = ctxt.read_i16be()?;
         // "Protected" flag (in low order bit).
         let protected = ctxt.read_u8()?;
         // zero fill, must be zero for compatibility
-        let _ = ctxt.read_u8()?;
+        let zero_fill2 = ctxt.read_u8()?;
         // Data Fork length (bytes, zero if no Data Fork).
         let data_fork_len = ctxt.read_u32be()?;
         // Resource Fork length (bytes, zero if no R.F.).
@@ -201,9 +938,9 @@ impl ReadBinary for Header<'_> {
         // extended Finder flags (from the fdXFlags field of an fxInfo record)
         let extended_finder_flags = ctxt.read_u8()?;
         // Bytes 108-115 unused (must be zeroed by creators, must be ignored by readers)
-        let _ = ctxt.read_slice(8)?;
+        let reserved = ctxt.read_slice(8)?;
         // Length of total files when packed files are unpacked. As of the writing of this document, this field has never been used.
-        let _ = ctxt.read_u32be()?;
+        let unpacked_total_len = ctxt.read_u32be()?;
         // Length of a secondary header. If this is non-zero, skip this many bytes (rounded up to the next multiple of 128). This is for future expansion only, when sending files with MacBinary, this word should be zero.
         let secondary_header_len = ctxt.read_u16be()?;
         // Version number of MacBinary III that the uploading program is written for (the version is 130 for MacBinary III)
@@ -214,17 +951,19 @@ impl ReadBinary for Header<'_> {
         // CRC of previous 124 bytes
         let crc = ctxt.read_u16be()?;
         // Reserved for computer type and OS ID (this field will be zero for the current Macintosh).
-        let _ = ctxt.read_u16be()?;
+        let os_id = ctxt.read_u16be()?;
 
         Ok(Header {
             filename: &filename_data[..usize::from(filename_len)],
             file_type,
             file_creator,
             finder_flags,
+            zero_fill1,
             vpos,
             hpos,
             window_or_folder_id,
-            protected: protected != 0,
+            protected,
+            zero_fill2,
             data_fork_len,
             rsrc_fork_len,
             created,
@@ -234,23 +973,27 @@ impl ReadBinary for Header<'_> {
             signature,
             script,
             extended_finder_flags,
+            reserved,
+            unpacked_total_len,
             secondary_header_len,
             version,
             min_version,
             crc,
+            os_id,
         })
     }
 }
 
 impl ReadBinaryDep for MacBinary<'_> {
-    type Args<'a> = Version;
+    type Args<'a> = (Version, ParseOptions);
     type HostType<'a> = MacBinary<'a>;
 
     fn read_dep<'a>(
         ctxt: &mut ReadCtxt<'a>,
-        version: Version,
+        (version, options): (Version, ParseOptions),
     ) -> Result<Self::HostType<'a>, ParseError> {
-        let crc_data = ctxt.scope().data().get(..124).ok_or(ParseError::BadEof)?;
+        let data = ctxt.scope().data();
+        let crc_data = data.get(..CRC_LEN).ok_or(ParseError::BadEof)?;
 
         // The binary format consists of a 128-byte header containing all the information necessary
         // to reproduce the document's directory entry on the receiving Macintosh; followed by the
@@ -263,39 +1006,299 @@ impl ReadBinaryDep for MacBinary<'_> {
         // Check the CRC
         let crc = calc_crc(crc_data);
         if version >= Version::II && crc != header.crc {
-            return Err(ParseError::CrcMismatch);
+            return Err(ParseError::CrcMismatch {
+                expected: header.crc,
+                actual: crc,
+            });
         }
 
-        // Skip secondary header if present, rounding up to next multiple of 128
-        let _ = ctxt.read_slice(usize::from(next_u16_multiple_of_128(
+        version.check_header(&header, options.strict)?;
+
+        let mut secondary_header_ignored = false;
+        let forks = match read_forks(
+            &mut ctxt.clone(),
+            &header,
             header.secondary_header_len,
-        )?))?;
+            options.allow_truncated,
+        ) {
+            Ok(forks) => forks,
+            // Writers are required to set the secondary header length to zero, so if the
+            // declared value leads nowhere try again without it.
+            Err(_) if options.ignore_bad_secondary_header && header.secondary_header_len != 0 => {
+                secondary_header_ignored = true;
+                read_forks(ctxt, &header, 0, options.allow_truncated)?
+            }
+            Err(err) => return Err(err),
+        };
+
+        Ok(MacBinary {
+            data,
+            version,
+            header,
+            secondary_header: forks.secondary_header,
+            data_fork: forks.data_fork,
+            rsrc_fork: forks.rsrc_fork,
+            data_padding: forks.data_padding,
+            rsrc_padding: forks.rsrc_padding,
+            secondary_header_ignored,
+            computed_crc: crc,
+            options,
+        })
+    }
+}
 
-        // Read the data fork
-        let data_fork = ctxt.read_slice(usize::num_from(header.data_fork_len))?;
+/// The forks that follow the header, along with the padding after each one
+struct Forks<'a> {
+    secondary_header: Option<&'a [u8]>,
+    data_fork: &'a [u8],
+    data_padding: &'a [u8],
+    rsrc_fork: &'a [u8],
+    rsrc_padding: &'a [u8],
+}
 
-        // Skip padding
-        let padding = next_u32_multiple_of_128(header.data_fork_len)? - header.data_fork_len;
-        let _ = ctxt.read_slice(usize::num_from(padding))?;
+/// Read the data and resource forks that follow the header
+///
+/// When `allow_truncated` is `true` forks and padding that extend past the end of the data are
+/// shortened to what is present.
+fn read_forks<'a>(
+    ctxt: &mut ReadCtxt<'a>,
+    header: &Header<'a>,
+    secondary_header_len: u16,
+    allow_truncated: bool,
+) -> Result<Forks<'a>, ParseError> {
+    let input_len = BLOCK_SIZE + ctxt.scope().data().len();
 
-        // Read the resource fork
-        let rsrc_fork = ctxt.read_slice(usize::num_from(header.rsrc_fork_len))?;
+    // Read secondary header if present, the padding rounds it up to next multiple of 128
+    let secondary_header = ctxt.read_slice(usize::from(pad_to_block(secondary_header_len)?))?;
 
-        Ok(MacBinary {
+    // Forks that run past the end of the input are reported as truncation, even when the length
+    // is absurd, as a file cut off part way through can't be told apart from a corrupt length
+    if !allow_truncated {
+        let expected = required_len(
+            secondary_header_len,
+            header.data_fork_len,
+            header.rsrc_fork_len,
+        )?;
+        let actual = input_len as u64;
+        if expected > actual {
+            return Err(ParseError::Truncated { expected, actual });
+        }
+    }
+    let secondary_header =
+        (secondary_header_len != 0).then(|| &secondary_header[..usize::from(secondary_header_len)]);
+
+    let mut read = |len: u32| {
+        let len = usize::num_from(len);
+        let len = if allow_truncated {
+            len.min(ctxt.scope().data().len())
+        } else {
+            len
+        };
+        ctxt.read_slice(len)
+    };
+
+    // Read the data fork
+    let data_fork = read(header.data_fork_len)?;
+    let data_padding = read(padding_len(header.data_fork_len))?;
+
+    // Read the resource fork
+    let rsrc_fork = read(header.rsrc_fork_len)?;
+
+    // The padding after the last fork is often left off, so take whatever is present
+    let rsrc_padding = ctxt.scope().data();
+    let rsrc_padding = &rsrc_padding[..rsrc_padding
+        .len()
+        .min(usize::num_from(padding_len(header.rsrc_fork_len)))];
+
+    Ok(Forks {
+        secondary_header,
+        data_fork,
+        data_padding,
+        rsrc_fork,
+        rsrc_padding,
+    })
+}
+
+impl<'a> MacBinary<'a> {
+    /// Construct a `MacBinary` from metadata and fork data that aren't in MacBinary format.
+    ///
+    /// This is useful when the forks and metadata come from another container, such as an HFS
+    /// disk image, and provides the same accessors as a parsed file. As there is no encoded
+    /// header the stored and computed CRCs are both zero and [Self::regions] are all empty.
+    ///
+    /// Returns [ParseError::BadValue] if the file name length isn't valid for the version and
+    /// [ParseError::Overflow] if a fork is too large for the version.
+    pub fn from_parts(
+        info: HeaderInfo<'a>,
+        data_fork: &'a [u8],
+        rsrc_fork: &'a [u8],
+    ) -> Result<MacBinary<'a>, ParseError> {
+        let fork_len = |fork: &[u8]| {
+            u32::try_from(fork.len())
+                .ok()
+                .filter(|&len| len <= info.version.max_fork_len())
+                .ok_or(ParseError::Overflow)
+        };
+        let finder = info.finder_info;
+        let [finder_flags, finder_flags2] = finder.finder_flags.to_be_bytes();
+        let version = info.version.header_version_byte();
+        // MacBinary II readers can read MacBinary III files
+        let (signature, min_version) = match info.version {
+            Version::I => (FourCC(0), 0),
+            Version::II => (FourCC(0), version),
+            Version::III => (MBIN, Version::II.header_version_byte()),
+        };
+        let header = Header {
+            filename: info.filename,
+            secondary_header_len: 0,
+            data_fork_len: fork_len(data_fork)?,
+            rsrc_fork_len: fork_len(rsrc_fork)?,
+            file_type: finder.file_type,
+            file_creator: finder.file_creator,
+            finder_flags,
+            zero_fill1: 0,
+            vpos: finder.vpos,
+            hpos: finder.hpos,
+            window_or_folder_id: finder.folder_id,
+            protected: u8::from(finder.protected),
+            zero_fill2: 0,
+            created: finder.created,
+            modified: finder.modified,
+            comment_len: 0,
+            finder_flags2,
+            signature,
+            script: finder.script,
+            extended_finder_flags: finder.extended_finder_flags,
+            reserved: &[0; 8],
+            unpacked_total_len: 0,
             version,
+            min_version,
+            crc: 0,
+            os_id: 0,
+        };
+        info.version.check_header(&header, true)?;
+
+        Ok(MacBinary {
+            data: &[],
+            version: info.version,
             header,
+            secondary_header: None,
             data_fork,
             rsrc_fork,
+            data_padding: &[],
+            rsrc_padding: &[],
+            secondary_header_ignored: false,
+            computed_crc: 0,
+            options: ParseOptions::default(),
         })
     }
+
+    /// Copy the file into an [OwnedMacBinary] that doesn't borrow the data it was parsed from.
+    #[cfg(not(feature = "no_std"))]
+    pub fn into_owned(self) -> OwnedMacBinary {
+        OwnedMacBinary::from_macbinary(&self)
+    }
+
+    /// A map of the regions of the file, with their offsets.
+    ///
+    /// This doesn't copy any data. All regions are empty for a `MacBinary` constructed with
+    /// [Self::from_parts].
+    pub fn regions(&self) -> Regions<'a> {
+        if self.data.is_empty() {
+            let empty = Region {
+                offset: 0,
+                data: &[],
+            };
+            return Regions {
+                header: empty,
+                secondary_header: empty,
+                data_fork: empty,
+                data_padding: empty,
+                rsrc_fork: empty,
+                rsrc_padding: empty,
+                comment: empty,
+                trailing: empty,
+            };
+        }
+
+        let secondary_header_len = if self.secondary_header_ignored {
+            0
+        } else {
+            // The secondary header length was checked when the file was parsed so this won't fail
+            usize::from(pad_to_block(self.header.secondary_header_len).unwrap_or(0))
+        };
+        let comment_start = BLOCK_SIZE
+            + secondary_header_len
+            + self.data_fork.len()
+            + self.data_padding.len()
+            + self.rsrc_fork.len()
+            + self.rsrc_padding.len();
+        // If the resource fork padding is truncated there's nothing left for the comment
+        let comment_len = usize::from(self.header.comment_len).min(self.data.len() - comment_start);
+
+        let mut rest = self.data;
+        let mut offset = 0;
+        let mut next = |len: usize| {
+            let (data, tail) = rest.split_at(len);
+            let region = Region { offset, data };
+            rest = tail;
+            offset += len;
+            region
+        };
+
+        Regions {
+            header: next(BLOCK_SIZE),
+            secondary_header: next(secondary_header_len),
+            data_fork: next(self.data_fork.len()),
+            data_padding: next(self.data_padding.len()),
+            rsrc_fork: next(self.rsrc_fork.len()),
+            rsrc_padding: next(self.rsrc_padding.len()),
+            comment: next(comment_len),
+            trailing: next(self.data.len() - comment_start - comment_len),
+        }
+    }
 }
 
-impl MacBinary<'_> {
+impl<'a> MacBinary<'a> {
     /// Returns the version of this MacBinary file.
     pub fn version(&self) -> Version {
         self.version
     }
 
+    /// The version of MacBinary the program that wrote this file was written for (byte 122).
+    ///
+    /// This is 129 for MacBinary II and 130 for MacBinary III. Returns 0 for MacBinary I files,
+    /// where this byte is part of the zero fill.
+    pub fn writer_version(&self) -> u8 {
+        match self.version {
+            Version::I => 0,
+            Version::II | Version::III => self.header.version,
+        }
+    }
+
+    /// The minimum version of MacBinary needed to read this file (byte 123).
+    ///
+    /// This is normally 129, for compatibility with MacBinary II. Returns 0 for MacBinary I
+    /// files, where this byte is part of the zero fill.
+    pub fn min_reader_version(&self) -> u8 {
+        match self.version {
+            Version::I => 0,
+            Version::II | Version::III => self.header.min_version,
+        }
+    }
+
+    /// The minimum version of MacBinary needed to read this file.
+    ///
+    /// Returns `None` if [Self::min_reader_version] isn't a known version number.
+    pub fn requires_version(&self) -> Option<Version> {
+        match self.min_reader_version() {
+            0 => Some(Version::I),
+            129 => Some(Version::II),
+            130 => Some(Version::III),
+            _ => None,
+        }
+    }
+
     /// The file name of the file encoded in this MacBinary file.
     #[cfg(not(feature = "no_std"))]
     pub fn filename(&self) -> String {
@@ -312,6 +1315,28 @@ impl MacBinary<'_> {
         String::from_macroman(self.header.filename)
     }
 
+    /// The file name of the file encoded in this MacBinary file, decoded with `policy`.
+    ///
+    /// [Self::filename] is the same as using [DecodePolicy::default].
+    #[cfg(not(feature = "no_std"))]
+    pub fn filename_with_policy(&self, policy: DecodePolicy) -> Result<String, MacRomanError> {
+        // TODO: Handle non-macroman script
+        decode_with_policy(self.header.filename, policy).map(|name| name.into_owned())
+    }
+
+    /// The file name of the file encoded in this MacBinary file, made safe to use as a path
+    /// component.
+    ///
+    /// Mac OS file names can contain `/`, which is replaced by `:` as HFS+ does, and control
+    /// characters, which are removed or replaced according to `options`. The name can also be
+    /// truncated to a maximum length.
+    ///
+    /// The result notes whether the name was changed so that tools can warn about it.
+    #[cfg(not(feature = "no_std"))]
+    pub fn filename_sanitized(&self, options: SanitizeOptions) -> SanitizedFilename {
+        sanitize_filename(&self.filename(), options)
+    }
+
     /// The file name of the file encoded in this MacBinary file.
     ///
     /// The raw name can't be longer than 63 bytes in length. However,
@@ -325,7 +1350,10 @@ impl MacBinary<'_> {
     }
 
     /// The raw filename bytes
-    pub fn filename_bytes(&self) -> &[u8] {
+    ///
+    /// These are the bytes as stored, without any conversion from the encoding indicated by the
+    /// script byte. They borrow from the input data.
+    pub fn filename_bytes(&self) -> &'a [u8] {
         self.header.filename
     }
 
@@ -349,171 +1377,2817 @@ impl MacBinary<'_> {
         mactime(self.header.modified)
     }
 
-    /// Data fork data
-    pub fn data_fork(&self) -> &[u8] {
-        self.data_fork
+    /// File creation date (UNIX timestamp)
+    ///
+    /// Unlike [Self::created] dates before 1970 are negative, rather than wrapping around to the
+    /// future.
+    pub fn created_unix(&self) -> i64 {
+        mactime_i64(self.header.created)
     }
 
-    /// Resource fork data
-    pub fn resource_fork_raw(&self) -> &[u8] {
-        self.rsrc_fork
+    /// File last modified date (UNIX timestamp)
+    ///
+    /// Unlike [Self::modified] dates before 1970 are negative, rather than wrapping around to
+    /// the future.
+    pub fn modified_unix(&self) -> i64 {
+        mactime_i64(self.header.modified)
     }
 
-    /// Parsed resource fork
+    /// File creation date.
     ///
-    /// Note: Not all files have resource fork data. This method will return None if the resource
-    /// fork is empty.
-    pub fn resource_fork(&self) -> Result<Option<ResourceFork<'_>>, ParseError> {
-        if self.rsrc_fork.is_empty() {
-            return Ok(None);
-        }
+    /// Mac OS stores dates in local time without a time zone, so the date is returned as if it
+    /// were UTC.
+    #[cfg(feature = "time")]
+    pub fn created_datetime(&self) -> time::OffsetDateTime {
+        datetime(self.header.created)
+    }
+
+    /// File last modified date.
+    ///
+    /// Mac OS stores dates in local time without a time zone, so the date is returned as if it
+    /// were UTC.
+    #[cfg(feature = "time")]
+    pub fn modified_datetime(&self) -> time::OffsetDateTime {
+        datetime(self.header.modified)
+    }
+
+    /// File creation date (UNIX timestamp), `None` if it isn't set.
+    ///
+    /// A date of zero in the header means the date is unknown. [Self::created] converts it to a
+    /// date in 1904 rather than treating it specially.
+    pub fn created_opt(&self) -> Option<u32> {
+        (self.header.created != 0).then(|| mactime(self.header.created))
+    }
+
+    /// File last modified date (UNIX timestamp), `None` if it isn't set.
+    ///
+    /// A date of zero in the header means the date is unknown. [Self::modified] converts it to a
+    /// date in 1904 rather than treating it specially.
+    pub fn modified_opt(&self) -> Option<u32> {
+        (self.header.modified != 0).then(|| mactime(self.header.modified))
+    }
+
+    /// The Finder flags of the file.
+    ///
+    /// The flags are interpreted according to the version of the file. MacBinary I only stores
+    /// the high byte of the flags, and some of its bits had a different meaning at the time, see
+    /// [FinderFlags](FinderFlags#versions).
+    pub fn finder_flags(&self) -> FinderFlags {
+        let low = match self.version {
+            Version::I => 0,
+            Version::II | Version::III => self.header.finder_flags2,
+        };
+        FinderFlags::for_version(
+            u16::from_be_bytes([self.header.finder_flags, low]),
+            self.version,
+        )
+    }
+
+    /// The extended Finder flags of the file.
+    ///
+    /// These are only stored by MacBinary III, this is empty for other versions. It's also empty
+    /// when the flags are marked invalid, in which case the script of the file name is stored
+    /// instead.
+    pub fn extended_finder_flags(&self) -> ExtendedFinderFlags {
+        let flags = ExtendedFinderFlags::from_bits(u16::from_be_bytes([
+            self.header.script,
+            self.header.extended_finder_flags,
+        ]));
+        if self.version < Version::III || flags.contains(ExtendedFinderFlags::ARE_INVALID) {
+            ExtendedFinderFlags::empty()
+        } else {
+            flags
+        }
+    }
+
+    /// Identify the program that likely encoded this file, using the default
+    /// [encoder::RULES].
+    ///
+    /// The default rules are empty, so this always returns [encoder::Encoder::Unknown] along with
+    /// the observations. Use [Self::encoder_fingerprint_with] to supply rules.
+    ///
+    /// See the [encoder] module for details.
+    pub fn encoder_fingerprint(&self) -> EncoderHint {
+        self.encoder_fingerprint_with(encoder::RULES)
+    }
+
+    /// Identify the program that likely encoded this file, using the supplied rules.
+    pub fn encoder_fingerprint_with(&self, rules: &[EncoderRule]) -> EncoderHint {
+        let observations = Observations {
+            version: self.header.version,
+            min_version: self.header.min_version,
+            has_signature: self.header.signature == MBIN,
+            reserved_zeroed: self.header.reserved.iter().all(|&byte| byte == 0),
+            rsrc_padded: self.rsrc_padding.len()
+                == usize::num_from(padding_len(self.header.rsrc_fork_len)),
+            crc_matches: self.computed_crc == self.header.crc,
+        };
+        EncoderHint::from_rules(observations, rules)
+    }
+
+    /// Returns `true` if the file is protected.
+    ///
+    /// This is the low-order bit of the protected byte, see [Self::protected_byte].
+    pub fn protected(&self) -> bool {
+        self.header.protected & 1 != 0
+    }
+
+    /// The raw protected byte (byte 81 of the header).
+    ///
+    /// Only the low-order bit is defined by the specification but some encoders set other bits.
+    pub fn protected_byte(&self) -> u8 {
+        self.header.protected
+    }
+
+    /// The position of the file's icon in its window, and the ID of the folder containing it.
+    pub fn finder_position(&self) -> FinderPosition {
+        FinderPosition {
+            v: self.header.vpos,
+            h: self.header.hpos,
+            folder_id: self.header.window_or_folder_id,
+        }
+    }
+
+    /// The Finder label of the file.
+    ///
+    /// This is always [Label::None] for MacBinary I files.
+    pub fn label(&self) -> Label {
+        Label::from(self.finder_flags())
+    }
+
+    /// Returns `true` if the file is invisible in the Finder.
+    pub fn is_invisible(&self) -> bool {
+        self.finder_flags().contains(FinderFlags::IS_INVISIBLE)
+    }
+
+    /// Returns `true` if the file is an alias.
+    pub fn is_alias(&self) -> bool {
+        self.finder_flags().contains(FinderFlags::IS_ALIAS)
+    }
+
+    /// Returns `true` if the file has a bundle (`'BNDL'`) resource.
+    pub fn has_bundle(&self) -> bool {
+        self.finder_flags().contains(FinderFlags::HAS_BUNDLE)
+    }
+
+    /// Returns `true` if the file is a stationery pad.
+    pub fn is_stationery(&self) -> bool {
+        self.finder_flags().contains(FinderFlags::IS_STATIONERY)
+    }
+
+    /// Returns `true` if the file has a custom icon.
+    pub fn has_custom_icon(&self) -> bool {
+        self.finder_flags().contains(FinderFlags::HAS_CUSTOM_ICON)
+    }
+
+    /// The Finder metadata of the file encoded in this MacBinary file.
+    pub fn finder_info(&self) -> FinderInfo {
+        self.header.finder_info()
+    }
+
+    /// An owned copy of the metadata in the header of this file.
+    #[cfg(not(feature = "no_std"))]
+    pub fn metadata(&self) -> Metadata {
+        Metadata {
+            version: self.version,
+            filename: self.filename(),
+            file_type: self.file_type(),
+            file_creator: self.file_creator(),
+            finder_flags: self.finder_flags(),
+            created: self.created_unix(),
+            modified: self.modified_unix(),
+            data_fork_len: self.header.data_fork_len,
+            rsrc_fork_len: self.header.rsrc_fork_len,
+            script: self.header.script,
+            protected: self.protected(),
+            label: self.label(),
+            position: self.finder_position(),
+        }
+    }
+
+    /// Summary statistics about this file.
+    ///
+    /// The counts come from the header and the resource map; the fork data itself isn't read.
+    pub fn stats(&self) -> Stats {
+        let rsrc = self.resource_fork().ok().flatten();
+        Stats {
+            version: self.version,
+            data_fork_len: self.header.data_fork_len,
+            rsrc_fork_len: self.header.rsrc_fork_len,
+            data_padding_len: padding_len(self.header.data_fork_len),
+            rsrc_padding_len: padding_len(self.header.rsrc_fork_len),
+            resource_type_count: rsrc.as_ref().map(|rsrc| rsrc.type_count()),
+            resource_count: rsrc.as_ref().map(|rsrc| rsrc.resource_count()),
+            name_list_len: rsrc.as_ref().map(|rsrc| rsrc.name_list_len()),
+            has_comment: self.header.comment_len != 0,
+            has_secondary_header: self.header.secondary_header_len != 0,
+            crc_matches: self.computed_crc == self.header.crc,
+        }
+    }
+
+    /// The total length of this file as encoded, from the start of the header to the end of the
+    /// padding after the Get Info comment.
+    ///
+    /// This is calculated from the lengths in the header, see [encoded_len_from_header]. The
+    /// input may be shorter if the file was parsed with [ParseOptions::allow_truncated] or the
+    /// padding at the end was left off.
+    pub fn encoded_len(&self) -> Result<usize, ParseError> {
+        self.header.encoded_len(self.version)
+    }
+
+    /// Length of the data fork including the padding that follows it.
+    pub fn data_fork_padded_len(&self) -> Result<u32, ParseError> {
+        pad_to_block(self.header.data_fork_len)
+    }
+
+    /// Length of the resource fork including the padding that follows it.
+    pub fn resource_fork_padded_len(&self) -> Result<u32, ParseError> {
+        pad_to_block(self.header.rsrc_fork_len)
+    }
+
+    /// Returns `true` if the padding following the data and resource forks is all zero.
+    ///
+    /// The specification requires padding to be filled with nulls. Non-zero padding can indicate
+    /// a file that was truncated and then concatenated with other data, or data hidden between
+    /// the forks. See [Self::nonzero_padding] for the location of the first non-zero byte.
+    pub fn padding_is_zeroed(&self) -> bool {
+        self.nonzero_padding().is_none()
+    }
+
+    /// Locate the first non-zero byte in the padding following the data or resource fork.
+    ///
+    /// Returns `None` if all padding bytes are zero.
+    pub fn nonzero_padding(&self) -> Option<NonZeroPadding<'_>> {
+        let regions = self.regions();
+        [regions.data_padding, regions.rsrc_padding]
+            .into_iter()
+            .find_map(|region| {
+                let index = region.data.iter().position(|&byte| byte != 0)?;
+                let preview = &region.data[index..];
+                Some(NonZeroPadding {
+                    offset: region.offset + index,
+                    preview: &preview[..preview.len().min(NonZeroPadding::PREVIEW_LEN)],
+                })
+            })
+    }
+
+    /// The word reserved for computer type and OS ID (bytes 126–127 of the header).
+    ///
+    /// This is zero for files written on a Macintosh but a few encoders stored other values here.
+    /// It is not covered by the header CRC.
+    pub fn os_id(&self) -> u16 {
+        self.header.os_id
+    }
+
+    /// The length of all files when packed files are unpacked (bytes 116–119 of the header).
+    ///
+    /// The specification notes this field was never used but a few archiving tools did set it.
+    /// Returns `None` when it is zero.
+    pub fn unpacked_total_len(&self) -> Option<u32> {
+        Some(self.header.unpacked_total_len).filter(|&len| len != 0)
+    }
+
+    /// Check the file for deviations from the specification.
+    ///
+    /// This includes the checks made by [ResourceFork::validate], with offsets adjusted to be
+    /// from the start of the file.
+    #[cfg(not(feature = "no_std"))]
+    pub fn validate(&self) -> Issues {
+        let mut issues = Issues::default();
+        let regions = self.regions();
+
+        if self.secondary_header_ignored {
+            issues.push(
+                Issue::new(
+                    Severity::Warning,
+                    validate::code::SECONDARY_HEADER_IGNORED,
+                    "secondary header length was ignored",
+                )
+                .at(120),
+            );
+        }
+        for (offset, byte) in [(74, self.header.zero_fill1), (82, self.header.zero_fill2)] {
+            if byte != 0 {
+                issues.push(
+                    Issue::new(
+                        Severity::Warning,
+                        validate::code::NONZERO_ZERO_FILL,
+                        "zero fill byte is not zero",
+                    )
+                    .at(offset),
+                );
+            }
+        }
+        if self.header.os_id != 0 {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    validate::code::NONZERO_OS_ID,
+                    "computer type and OS ID is not zero",
+                )
+                .at(126),
+            );
+        }
+        if self.version == Version::I
+            && (self.finder_flags().legacy_raw() != 0 || self.header.finder_flags2 != 0)
+        {
+            issues.push(
+                Issue::new(
+                    Severity::Warning,
+                    validate::code::LEGACY_FINDER_FLAGS,
+                    "MacBinary I file has Finder flags that the version didn't define, it may be a relabelled MacBinary II or III file",
+                )
+                .at(if self.header.finder_flags2 != 0 { 101 } else { 73 }),
+            );
+        }
+        if self.header.unpacked_total_len != 0 {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    validate::code::UNPACKED_TOTAL_LEN,
+                    "total unpacked length is set",
+                )
+                .at(116),
+            );
+        }
+        for region in [regions.data_padding, regions.rsrc_padding] {
+            if let Some(index) = region.data.iter().position(|&byte| byte != 0) {
+                issues.push(
+                    Issue::new(
+                        Severity::Warning,
+                        validate::code::NONZERO_PADDING,
+                        "fork padding is not zero",
+                    )
+                    .at(region.offset + index),
+                );
+            }
+        }
+        for (complete, region) in [
+            (self.data_fork_complete(), regions.data_fork),
+            (self.resource_fork_complete(), regions.rsrc_fork),
+        ] {
+            if !complete {
+                issues.push(
+                    Issue::new(
+                        Severity::Error,
+                        validate::code::TRUNCATED_FORK,
+                        "fork is shorter than its declared length",
+                    )
+                    .at(region.offset + region.data.len()),
+                );
+            }
+        }
+        // A MacBinary constructed from parts has no padding at all
+        let encoded = !self.data.is_empty();
+        if encoded
+            && self.resource_fork_complete()
+            && regions.rsrc_padding.data.len()
+                != usize::num_from(padding_len(self.header.rsrc_fork_len))
+        {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    validate::code::MISSING_PADDING,
+                    "resource fork padding is incomplete",
+                )
+                .at(regions.rsrc_padding.offset + regions.rsrc_padding.data.len()),
+            );
+        }
+        if !regions.trailing.data.is_empty() {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    validate::code::TRAILING_DATA,
+                    "data follows the end of the file",
+                )
+                .at(regions.trailing.offset),
+            );
+        }
+        match self.resource_fork() {
+            Ok(Some(rsrc)) => issues.append_at(rsrc.validate(), regions.rsrc_fork.offset),
+            Ok(None) => {}
+            Err(_) => issues.push(
+                Issue::new(
+                    Severity::Error,
+                    validate::code::INVALID_RESOURCE_FORK,
+                    "resource fork could not be parsed",
+                )
+                .at(regions.rsrc_fork.offset),
+            ),
+        }
+
+        issues
+    }
+
+    /// The contents of the secondary header, without the padding that follows it.
+    ///
+    /// The secondary header is reserved for future expansion, so its contents have no defined
+    /// meaning. Returns `None` if the secondary header length is zero, or it was ignored (see
+    /// [Self::secondary_header_ignored]).
+    pub fn secondary_header(&self) -> Option<&[u8]> {
+        self.secondary_header
+    }
+
+    /// The CRC stored in the header.
+    ///
+    /// MacBinary I headers don't have a CRC, so this is normally zero for them.
+    pub fn stored_crc(&self) -> u16 {
+        self.header.crc
+    }
+
+    /// The CRC calculated from the first 124 bytes of the header when it was parsed.
+    ///
+    /// Returns zero for a `MacBinary` constructed with [Self::from_parts].
+    pub fn computed_crc(&self) -> u16 {
+        self.computed_crc
+    }
+
+    /// Returns `true` if the secondary header length in the header was ignored.
+    ///
+    /// This can only happen when parsing with
+    /// [ParseOptions::ignore_bad_secondary_header] enabled.
+    pub fn secondary_header_ignored(&self) -> bool {
+        self.secondary_header_ignored
+    }
+
+    /// Returns `true` if the header declares both the data and resource forks to be empty.
+    ///
+    /// Such a file is valid and conveys only the file's metadata, such as its name, type, creator
+    /// and dates. This is based on the declared lengths, so a file parsed with
+    /// [ParseOptions::allow_truncated] whose forks were lost is not metadata-only.
+    pub fn is_metadata_only(&self) -> bool {
+        self.header.data_fork_len == 0 && self.header.rsrc_fork_len == 0
+    }
+
+    /// Data fork data
+    ///
+    /// This is empty if the file has no data fork. If the file was parsed with
+    /// [ParseOptions::allow_truncated] this may be shorter than the declared length, see
+    /// [Self::data_fork_complete].
+    pub fn data_fork(&self) -> &[u8] {
+        self.data_fork
+    }
+
+    /// The length of the data fork, as declared in the header.
+    ///
+    /// This doesn't touch the fork data. For a fully parsed file it equals `data_fork().len()`.
+    /// When parsed with [ParseOptions::allow_truncated] the fork may be shorter than this, see
+    /// [Self::data_fork_complete].
+    pub fn data_fork_declared_len(&self) -> u32 {
+        self.header.data_fork_len
+    }
+
+    #[doc(hidden)]
+    pub fn data_fork_len(&self) -> u32 {
+        self.data_fork_declared_len()
+    }
+
+    /// Returns `true` if all of the data fork is present.
+    ///
+    /// This is always `true` unless the file was parsed with [ParseOptions::allow_truncated]. A
+    /// file with no data fork is complete, so this distinguishes an empty data fork from one that
+    /// has been lost.
+    pub fn data_fork_complete(&self) -> bool {
+        usize::num_from(self.header.data_fork_len) == self.data_fork.len()
+    }
+
+    /// The length of the resource fork, as declared in the header.
+    ///
+    /// This doesn't touch the fork data. For a fully parsed file it equals
+    /// `resource_fork_raw().len()`. When parsed with [ParseOptions::allow_truncated] the fork may
+    /// be shorter than this, see [Self::resource_fork_complete].
+    pub fn resource_fork_declared_len(&self) -> u32 {
+        self.header.rsrc_fork_len
+    }
+
+    #[doc(hidden)]
+    pub fn resource_fork_len(&self) -> u32 {
+        self.resource_fork_declared_len()
+    }
+
+    /// Returns `true` if all of the resource fork is present.
+    ///
+    /// This is always `true` unless the file was parsed with [ParseOptions::allow_truncated].
+    pub fn resource_fork_complete(&self) -> bool {
+        usize::num_from(self.header.rsrc_fork_len) == self.rsrc_fork.len()
+    }
+
+    /// Resource fork data
+    ///
+    /// This is empty if the file has no resource fork. If the file was parsed with
+    /// [ParseOptions::allow_truncated] this may be shorter than the declared length, see
+    /// [Self::resource_fork_complete].
+    pub fn resource_fork_raw(&self) -> &[u8] {
+        self.rsrc_fork
+    }
+
+    /// Parsed resource fork
+    ///
+    /// Note: Not all files have resource fork data. This method will return None if the resource
+    /// fork is empty.
+    pub fn resource_fork(&self) -> Result<Option<ResourceFork<'_>>, ParseError> {
+        if self.rsrc_fork.is_empty() {
+            return Ok(None);
+        }
+
+        ResourceFork::new(self.rsrc_fork).map(Some)
+    }
+
+    /// The version of the file from its `'vers'` resource.
+    ///
+    /// This is the `'vers'` resource with ID 1, or ID 2, the version of the product the file
+    /// belongs to, if there is no ID 1. Returns `Ok(None)` if the file has no resource fork or
+    /// neither resource is present, and an error if the resource fork or the resource can't be
+    /// parsed.
+    pub fn version_resource(&self) -> Result<Option<VersionResource<'_>>, ParseError> {
+        let Some(rsrc) = self.resource_fork()? else {
+            return Ok(None);
+        };
+        let vers = <VersionResource<'_> as TypedResource<'_>>::TYPE;
+        let data = match rsrc.try_resource_data(vers, 1)? {
+            Some(data) => data,
+            None => match rsrc.try_resource_data(vers, 2)? {
+                Some(data) => data,
+                None => return Ok(None),
+            },
+        };
+        VersionResource::parse(data).map(Some)
+    }
+
+    /// Digest of the bytes of a fork, excluding padding.
+    pub fn fork_digest(&self, fork: ForkKind, algo: Algo) -> digest::Digest {
+        let data = match fork {
+            ForkKind::Data => self.data_fork,
+            ForkKind::Resource => self.rsrc_fork,
+        };
+        digest::digest(algo, data)
+    }
+
+    /// Digest of the content of the file: the data fork followed by the resource fork.
+    ///
+    /// Each fork is preceded by its length so the split between them affects the digest. No
+    /// metadata from the header is included, so files that differ only in name, dates, or other
+    /// Finder information have the same content digest.
+    pub fn content_digest(&self, algo: Algo) -> digest::Digest {
+        digest::framed_digest(algo, &[self.data_fork, self.rsrc_fork])
+    }
+}
+
+impl NonZeroPadding<'_> {
+    /// Maximum number of bytes in [Self::preview].
+    pub const PREVIEW_LEN: usize = 16;
+}
+
+impl FinderInfo {
+    /// Encode as the 32-byte Finder info used in extended attributes and AppleDouble files.
+    ///
+    /// This is an `FInfo` record followed by an `FXInfo` record. The dates and protected flag
+    /// are not part of these records, and the fields of `FXInfo` that MacBinary does not carry
+    /// (icon ID, comment ID, put away folder) are zero.
+    pub fn to_finder_info_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0; 32];
+        // FInfo
+        bytes[0..4].copy_from_slice(&self.file_type.0.to_be_bytes());
+        bytes[4..8].copy_from_slice(&self.file_creator.0.to_be_bytes());
+        bytes[8..10].copy_from_slice(&self.finder_flags.to_be_bytes());
+        bytes[10..12].copy_from_slice(&self.vpos.to_be_bytes());
+        bytes[12..14].copy_from_slice(&self.hpos.to_be_bytes());
+        bytes[14..16].copy_from_slice(&self.folder_id.to_be_bytes());
+        // FXInfo: fdIconID and fdUnused (bytes 16-23) are left zeroed
+        bytes[24] = self.script;
+        bytes[25] = self.extended_finder_flags;
+        // fdComment and fdPutAway (bytes 26-31) are left zeroed
+        bytes
+    }
+
+    /// Decode the 32-byte Finder info used in extended attributes and AppleDouble files.
+    ///
+    /// This is the inverse of [Self::to_finder_info_bytes]. The dates and protected flag are not
+    /// part of the Finder info, so they are zero and `false`.
+    pub fn from_finder_info_bytes(bytes: &[u8; 32]) -> FinderInfo {
+        let u16_at = |offset: usize| u16::from_be_bytes([bytes[offset], bytes[offset + 1]]);
+        FinderInfo {
+            file_type: FourCC::new([bytes[0], bytes[1], bytes[2], bytes[3]]),
+            file_creator: FourCC::new([bytes[4], bytes[5], bytes[6], bytes[7]]),
+            finder_flags: u16_at(8),
+            vpos: u16_at(10) as i16,
+            hpos: u16_at(12) as i16,
+            folder_id: u16_at(14) as i16,
+            script: bytes[24],
+            extended_finder_flags: bytes[25],
+            created: 0,
+            modified: 0,
+            protected: false,
+        }
+    }
+}
+
+impl FourCC {
+    /// Construct a `FourCC` from its four bytes.
+    ///
+    /// ```
+    /// use macbinary::FourCC;
+    ///
+    /// assert_eq!(FourCC::new(*b"TEXT").to_string(), "TEXT");
+    /// ```
+    pub const fn new(code: [u8; 4]) -> FourCC {
+        FourCC(u32::from_be_bytes(code))
+    }
+
+    /// Construct a `FourCC` from a slice, checking that it holds four printable characters.
+    ///
+    /// Space is allowed, which is common in codes like `snd ` and `STR `.
+    pub fn try_new(code: &[u8]) -> Result<FourCC, FourCCError> {
+        let bytes: [u8; 4] = code
+            .try_into()
+            .map_err(|_| FourCCError::BadLength(code.len()))?;
+        match bytes.iter().position(|&byte| !is_printable(byte)) {
+            Some(index) => Err(FourCCError::BadByte {
+                index,
+                byte: bytes[index],
+            }),
+            None => Ok(FourCC::new(bytes)),
+        }
+    }
+
+    /// The four bytes of the code.
+    pub const fn as_bytes(&self) -> [u8; 4] {
+        self.0.to_be_bytes()
+    }
+
+    /// Returns `true` if all the bytes of this code are printable characters.
+    ///
+    /// Bytes are interpreted as MacRoman, so high-bit characters such as `©` or the non-breaking
+    /// space (0xCA) are considered printable. When this is `false` the `Display` implementation
+    /// shows the code in hex.
+    ///
+    /// `Display` also uses hex for codes containing 0xCA or 0xF6, which are decoded to the same
+    /// characters as ASCII space and `^`, so that every displayed code can be parsed again.
+    pub fn is_printable(&self) -> bool {
+        self.0.to_be_bytes().iter().all(|&byte| is_printable(byte))
+    }
+}
+
+fn is_printable(byte: u8) -> bool {
+    match macroman_to_char(byte) {
+        Some(ch) => !ch.is_control(),
+        None => false,
+    }
+}
+
+/// Returns `true` if `byte` is printable and parsing the character it's displayed as gives `byte`
+/// back.
+fn is_displayable(byte: u8) -> bool {
+    match macroman_to_char(byte) {
+        Some(ch) => !ch.is_control() && char_to_macroman(ch) == Some(byte),
+        None => false,
+    }
+}
+
+/// Formats the version as "MacBinary I", "MacBinary II", or "MacBinary III".
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Version::I => "MacBinary I",
+            Version::II => "MacBinary II",
+            Version::III => "MacBinary III",
+        };
+        f.pad(name)
+    }
+}
+
+/// Converts a version number (1, 2, or 3) to a `Version`.
+///
+/// Other values return [ParseError::BadVersion].
+impl TryFrom<u8> for Version {
+    type Error = ParseError;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            1 => Ok(Version::I),
+            2 => Ok(Version::II),
+            3 => Ok(Version::III),
+            _ => Err(ParseError::BadVersion),
+        }
+    }
+}
+
+/// Parses anything `Display` produces: four printable MacRoman characters, e.g. `TEXT`, `snd `,
+/// or `©dat`, or the hex form shown for non-printable codes, e.g. `0x54450054`.
+///
+/// A string starting with `0x` that isn't four characters long is always parsed as hex.
+impl TryFrom<&str> for FourCC {
+    type Error = FourCCError;
+
+    fn try_from(code: &str) -> Result<Self, Self::Error> {
+        let char_count = code.chars().count();
+        if let Some(hex) = code.strip_prefix("0x").filter(|_| char_count != 4) {
+            if hex.len() != 8 || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+                return Err(FourCCError::BadHex);
+            }
+            // unwrap safe as there are 8 hex digits
+            return Ok(FourCC(u32::from_str_radix(hex, 16).unwrap()));
+        }
+        if char_count != 4 {
+            return Err(FourCCError::BadLength(char_count));
+        }
+        let mut bytes = [0; 4];
+        for ((index, ch), byte) in code.char_indices().zip(bytes.iter_mut()) {
+            *byte = char_to_macroman(ch).ok_or(FourCCError::NotMacRoman { index, ch })?;
+        }
+        FourCC::try_new(&bytes)
+    }
+}
+
+impl PartialEq<[u8; 4]> for FourCC {
+    fn eq(&self, other: &[u8; 4]) -> bool {
+        self.as_bytes() == *other
+    }
+}
+
+impl PartialEq<FourCC> for [u8; 4] {
+    fn eq(&self, other: &FourCC) -> bool {
+        other == self
+    }
+}
+
+/// Compares the bytes of the string with the code, strings that aren't four bytes long are never
+/// equal.
+impl PartialEq<&str> for FourCC {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl PartialEq<FourCC> for &str {
+    fn eq(&self, other: &FourCC) -> bool {
+        other == self
+    }
+}
+
+/// Serializes the code as a string of its characters when they're all printable ASCII, e.g.
+/// `"snd "`, or in the hex form otherwise, e.g. `"0xa9646174"`.
+///
+/// Unlike `Display`, printable high-bit MacRoman characters are also written in hex so that the
+/// serialized code is always plain ASCII.
+#[cfg(any(feature = "serde", all(target_family = "wasm", feature = "wasm-full")))]
+impl serde::Serialize for FourCC {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self
+            .as_bytes()
+            .iter()
+            .all(|&byte| byte.is_ascii() && is_printable(byte))
+        {
+            serializer.collect_str(self)
+        } else {
+            serializer.collect_str(&format_args!("0x{:08x}", self.0))
+        }
+    }
+}
+
+/// Deserializes a string in one of the forms accepted by `FromStr`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FourCC {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct FourCCVisitor;
+
+        impl serde::de::Visitor<'_> for FourCCVisitor {
+            type Value = FourCC;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a four-character code")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, code: &str) -> Result<FourCC, E> {
+                FourCC::try_from(code).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(FourCCVisitor)
+    }
+}
+
+impl FromStr for FourCC {
+    type Err = FourCCError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        FourCC::try_from(code)
+    }
+}
+
+impl ReadFrom for FourCC {
+    type ReadType = U32Be;
+
+    fn from(value: u32) -> Self {
+        FourCC(value)
+    }
+}
+
+/// Formats the code as characters if it's printable, or hex otherwise. The output can be parsed
+/// with `FromStr` to get the same code back.
+///
+/// The alternate form (`{:#}`) always includes both, e.g. `'snd ' (0x736e6420)`. Non-printable
+/// bytes are escaped in the character part of the alternate form.
+impl Display for FourCC {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let tag = self.0;
+        if f.alternate() {
+            f.write_char('\'')?;
+            for byte in tag.to_be_bytes() {
+                match macroman_to_char(byte) {
+                    Some(ch) if is_printable(byte) => f.write_char(ch)?,
+                    _ => write!(f, "\\x{:02x}", byte)?,
+                }
+            }
+            write!(f, "' (0x{:08x})", tag)
+        } else if tag.to_be_bytes().iter().all(|&byte| is_displayable(byte)) {
+            // Each MacRoman character is at most 3 bytes in UTF-8
+            let mut buf = [0; 4 * 3];
+            let mut len = 0;
+            for ch in tag.to_be_bytes().into_iter().filter_map(macroman_to_char) {
+                len += ch.encode_utf8(&mut buf[len..]).len();
+            }
+            let s = core::str::from_utf8(&buf[..len]).unwrap(); // unwrap safe as built from chars
+            f.pad(s)
+        } else {
+            write!(f, "0x{:08x}", tag)
+        }
+    }
+}
+
+impl fmt::Debug for MacBinary<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let header = &self.header;
+        f.debug_struct("MacBinary")
+            .field("version", &self.version)
+            .field("filename", &DebugMacRoman(header.filename))
+            .field("file_type", &header.file_type)
+            .field("file_creator", &header.file_creator)
+            .field("finder_flags", &self.finder_flags())
+            .field("position", &self.finder_position())
+            .field("protected", &self.protected())
+            .field(
+                "created",
+                &format_args!("{}", DisplayMacDate(header.created)),
+            )
+            .field(
+                "modified",
+                &format_args!("{}", DisplayMacDate(header.modified)),
+            )
+            .field("data_fork_len", &header.data_fork_len)
+            .field("rsrc_fork_len", &header.rsrc_fork_len)
+            .field("comment_len", &header.comment_len)
+            .field("secondary_header_len", &header.secondary_header_len)
+            .field("crc", &format_args!("{:#06x}", header.crc))
+            .finish()
+    }
+}
+
+impl fmt::Debug for FourCC {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}'", self)
+    }
+}
+
+/// Round `len` up to the next multiple of [BLOCK_SIZE].
+///
+/// Returns [ParseError::Overflow] if the padded length can't be represented by the type of
+/// `len`.
+pub fn pad_to_block<T>(len: T) -> Result<T, ParseError>
+where
+    T: Into<u64> + TryFrom<u64>,
+{
+    let len = len.into();
+    len.checked_add(padding_len(len))
+        .and_then(|padded| T::try_from(padded).ok())
+        .ok_or(ParseError::Overflow)
+}
+
+/// Number of bytes needed to pad `len` to a multiple of [BLOCK_SIZE]
+fn padding_len<T>(len: T) -> T
+where
+    T: Into<u64> + TryFrom<u64>,
+{
+    const BLOCK: u64 = BLOCK_SIZE as u64;
+    let padding = (BLOCK - len.into() % BLOCK) % BLOCK;
+    // The padding is less than BLOCK_SIZE so it fits in any of the types that convert into u64
+    T::try_from(padding).unwrap_or_else(|_| unreachable!())
+}
+
+/// Seconds from the Mac OS epoch, 1 January 1904, to the UNIX epoch, 1 January 1970
+///
+/// 66 years from 1904 to 1970, 17 leap years, 86400 seconds in a day
+const MAC_EPOCH_OFFSET: u32 = 66 * 365 * 86400 + (17 * 86400);
+
+/// Convert Mac OS timestamp to UNIX timestamp
+///
+/// The Mac OS epoch is 1 January 1904, UNIX epoch is 1 Jan 1970.
+fn mactime(timestamp: u32) -> u32 {
+    timestamp.wrapping_sub(MAC_EPOCH_OFFSET)
+}
+
+/// Convert a Mac OS timestamp to a UNIX timestamp without wrapping.
+///
+/// Dates before 1970 are negative.
+fn mactime_i64(timestamp: u32) -> i64 {
+    i64::from(timestamp) - i64::from(MAC_EPOCH_OFFSET)
+}
+
+/// Convert a Mac OS timestamp to a date and time in UTC.
+#[cfg(feature = "time")]
+fn datetime(timestamp: u32) -> time::OffsetDateTime {
+    // All Mac OS timestamps are within the range supported by OffsetDateTime
+    time::OffsetDateTime::from_unix_timestamp(mactime_i64(timestamp))
+        .unwrap_or_else(|_| unreachable!())
+}
+
+/// Convert a UNIX timestamp to a Mac OS timestamp.
+///
+/// Returns `None` if the date can't be represented as a Mac OS timestamp, which covers
+/// 1 January 1904 to 6 February 2040.
+pub fn unix_to_mactime(timestamp: i64) -> Option<u32> {
+    timestamp
+        .checked_add(i64::from(MAC_EPOCH_OFFSET))
+        .and_then(|timestamp| u32::try_from(timestamp).ok())
+}
+
+fn calc_crc(data: &[u8]) -> u16 {
+    CRC.checksum(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{
+        macbinary_with_forks, read_fixture, resource_fork, type_list_offset, update_crc,
+    };
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    #[test]
+    fn test_send_sync() {
+        use crate::resource::types;
+
+        assert_send_sync::<MacBinary<'_>>();
+        assert_send_sync::<HeaderInfo<'_>>();
+        assert_send_sync::<Probe<'_>>();
+        assert_send_sync::<Region<'_>>();
+        assert_send_sync::<Regions<'_>>();
+        assert_send_sync::<NonZeroPadding<'_>>();
+        assert_send_sync::<FinderInfo>();
+        assert_send_sync::<Stats>();
+        assert_send_sync::<Metadata>();
+        assert_send_sync::<OwnedMacBinary>();
+        assert_send_sync::<OwnedResourceFork>();
+        assert_send_sync::<SanitizeOptions>();
+        assert_send_sync::<SanitizedFilename>();
+        assert_send_sync::<HeaderCrc>();
+        assert_send_sync::<ParseOptions>();
+        assert_send_sync::<Version>();
+        assert_send_sync::<FourCC>();
+        assert_send_sync::<ForkKind>();
+        assert_send_sync::<DisplayMacDate>();
+        assert_send_sync::<DecodePolicy>();
+        assert_send_sync::<FinderFlags>();
+        assert_send_sync::<ExtendedFinderFlags>();
+        assert_send_sync::<Label>();
+        assert_send_sync::<FinderPosition>();
+        assert_send_sync::<ParseError>();
+        assert_send_sync::<ExtractError>();
+        assert_send_sync::<FourCCError>();
+        assert_send_sync::<MacRomanError>();
+        assert_send_sync::<digest::Algo>();
+        assert_send_sync::<digest::Digest>();
+        assert_send_sync::<encoder::Encoder>();
+        assert_send_sync::<encoder::Observations>();
+        assert_send_sync::<encoder::EncoderRule>();
+        assert_send_sync::<encoder::EncoderHint>();
+        assert_send_sync::<validate::Issue>();
+        assert_send_sync::<validate::Issues>();
+        assert_send_sync::<validate::Severity>();
+        assert_send_sync::<snapshot::Snapshot>();
+        assert_send_sync::<snapshot::ResourceSnapshot>();
+        assert_send_sync::<snapshot::Difference>();
+        assert_send_sync::<ResourceFork<'_>>();
+        assert_send_sync::<ResourceForkHeader>();
+        assert_send_sync::<resource::Resource<'_>>();
+        assert_send_sync::<resource::ResourceTypes<'_, '_>>();
+        assert_send_sync::<resource::Resources<'_, '_>>();
+        assert_send_sync::<resource::TryResources<'_, '_>>();
+        assert_send_sync::<resource::TryIter<'_, '_>>();
+        assert_send_sync::<resource::ResourceIndex<'_, '_>>();
+        assert_send_sync::<resource::ResourcesWithId<'_, '_>>();
+        assert_send_sync::<resource::ResourceNames<'_, '_>>();
+        assert_send_sync::<resource::DeRez<'_, '_>>();
+        assert_send_sync::<resource::FindResources<'_, '_, fn(FourCC, i16, Option<&[u8]>) -> bool>>(
+        );
+        assert_send_sync::<resource::SortedResources<'_, '_>>();
+        assert_send_sync::<resource::TypeListItem>();
+        assert_send_sync::<resource::ResourceAttributes>();
+        assert_send_sync::<resource::MapAttributes>();
+        assert_send_sync::<resource::CompressionInfo>();
+        assert_send_sync::<ForkStats>();
+        assert_send_sync::<TypeStats>();
+        assert_send_sync::<ResourceSize>();
+        assert_send_sync::<types::Appl<'_>>();
+        assert_send_sync::<types::ApplEntries<'_>>();
+        assert_send_sync::<types::Str<'_>>();
+        assert_send_sync::<types::StringList<'_>>();
+        assert_send_sync::<types::StringListIter<'_>>();
+        assert_send_sync::<types::ApplEntry<'_>>();
+        assert_send_sync::<types::BalloonHelp>();
+        assert_send_sync::<types::DecoderInfo>();
+        assert_send_sync::<types::Drvr<'_>>();
+        assert_send_sync::<types::Fcmt<'_>>();
+        assert_send_sync::<types::HelpString>();
+        assert_send_sync::<types::Mctb<'_>>();
+        assert_send_sync::<types::MctbEntry>();
+        assert_send_sync::<types::MenuColorTarget>();
+        assert_send_sync::<types::OutputKind>();
+        assert_send_sync::<types::Point>();
+        assert_send_sync::<types::Rect>();
+        assert_send_sync::<types::RgbColor>();
+        assert_send_sync::<types::DevelopmentStage>();
+        assert_send_sync::<types::VersionResource<'_>>();
+    }
+
+    #[test]
+    fn test_parse_concurrently() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let parsed = parse(&data).unwrap();
+                        // Share the file parsed on the main thread too
+                        assert_eq!(parsed.filename(), file.filename());
+                        let rsrc = parsed.resource_fork().unwrap().unwrap();
+                        (parsed.metadata(), rsrc.resource_count())
+                    })
+                })
+                .collect();
+            for thread in threads {
+                let (metadata, resource_count) = thread.join().unwrap();
+                assert_eq!(metadata, file.metadata());
+                assert_eq!(resource_count, 2);
+            }
+        });
+    }
+
+    #[test]
+    fn test_pad_to_block() {
+        assert_eq!(pad_to_block(0_u16), Ok(0));
+        assert_eq!(pad_to_block(3_u16), Ok(128));
+        assert_eq!(pad_to_block(128_u16), Ok(128));
+        assert_eq!(pad_to_block(129_u16), Ok(256));
+
+        assert_eq!(pad_to_block(0_u32), Ok(0));
+        assert_eq!(pad_to_block(3_u32), Ok(128));
+        assert_eq!(pad_to_block(128_u32), Ok(128));
+        assert_eq!(pad_to_block(129_u32), Ok(256));
+    }
+
+    #[test]
+    fn test_pad_to_block_overflow() {
+        assert_eq!(pad_to_block(u16::MAX - 3), Err(ParseError::Overflow));
+        assert_eq!(pad_to_block(u32::MAX - 3), Err(ParseError::Overflow));
+        assert_eq!(pad_to_block(u64::MAX - 3), Err(ParseError::Overflow));
+        assert_eq!(pad_to_block(u32::MAX - 127), Ok(u32::MAX - 127));
+    }
+
+    fn check_text_file(file: &MacBinary, version: Version) {
+        assert_eq!(file.version(), version);
+        assert_eq!(file.filename(), "Text File");
+        assert_eq!(file.file_type(), FourCC(u32::from_be_bytes(*b"TEXT")));
+        assert_eq!(file.file_creator(), FourCC(u32::from_be_bytes(*b"R*ch"))); // BBEdit
+        assert_eq!(file.data_fork(), b"This is a test file.\r");
+        assert_eq!(file.resource_fork_raw().len(), 1454);
+    }
+
+    #[test]
+    fn test_macbinary_1() {
+        let data = read_fixture("tests/Text File I.Bin");
+        let file = parse(&data).unwrap();
+
+        check_text_file(&file, Version::I);
+    }
+
+    #[test]
+    fn test_macbinary_2() {
+        let data = read_fixture("tests/Text File II.bin");
+        let file = parse(&data).unwrap();
+
+        check_text_file(&file, Version::II);
+    }
+
+    #[test]
+    fn test_macbinary_3() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+
+        check_text_file(&file, Version::III);
+    }
+
+    #[test]
+    fn test_no_resource_fork() {
+        let data = read_fixture("tests/No resource fork.txt.bin");
+        let file = parse(&data).unwrap();
+
+        assert_eq!(file.version(), Version::III);
+        assert!(file.resource_fork().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_dates() {
+        let data = read_fixture("tests/Date Test.bin");
+        let file = parse(&data).unwrap();
+
+        assert_eq!(file.version(), Version::III);
+        assert_eq!(file.filename(), "Date Test");
+        assert_eq!(file.file_type(), FourCC(u32::from_be_bytes(*b"TEXT")));
+        assert_eq!(file.file_creator(), FourCC(u32::from_be_bytes(*b"MPS "))); // MPW Shell
+        assert_eq!(file.data_fork(), b"Sunday, 26 March 2023 10:00:52 AM\r");
+        assert_eq!(file.created(), 1679824852);
+        assert_eq!(file.modified(), 1679824852);
+        assert_eq!(file.created_opt(), Some(1679824852));
+        assert_eq!(file.modified_opt(), Some(1679824852));
+    }
+
+    #[test]
+    fn test_unix_dates() {
+        let mut data = read_fixture("tests/Date Test.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.created_unix(), 1679824852);
+        assert_eq!(file.modified_unix(), 1679824852);
+
+        // 0 is 1 January 1904, 0x70000000 is 1963
+        data[91..95].copy_from_slice(&0_u32.to_be_bytes());
+        data[95..99].copy_from_slice(&0x7000_0000_u32.to_be_bytes());
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert_eq!(file.created_unix(), -2082844800);
+        assert_eq!(file.modified_unix(), -203796608);
+
+        // Last representable date, 6 February 2040
+        data[95..99].copy_from_slice(&u32::MAX.to_be_bytes());
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert_eq!(file.modified_unix(), 2212122495);
+    }
+
+    #[test]
+    #[cfg(feature = "time")]
+    fn test_datetime() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        let iso8601 = file
+            .modified_datetime()
+            .format(&time::format_description::well_known::Rfc3339)
+            .unwrap();
+        assert_eq!(iso8601, "2023-03-22T15:53:12Z");
+        assert_eq!(file.created_datetime(), file.modified_datetime());
+        assert_eq!(datetime(0).year(), 1904);
+        assert_eq!(datetime(u32::MAX).year(), 2040);
+    }
+
+    #[test]
+    fn test_unix_to_mactime() {
+        assert_eq!(unix_to_mactime(0), Some(2082844800));
+        assert_eq!(unix_to_mactime(-2082844800), Some(0));
+        assert_eq!(unix_to_mactime(-2082844801), None);
+        assert_eq!(unix_to_mactime(2212122495), Some(u32::MAX));
+        assert_eq!(unix_to_mactime(2212122496), None);
+        assert_eq!(unix_to_mactime(i64::MAX), None);
+        assert_eq!(unix_to_mactime(1679824852), Some(3762669652));
+    }
+
+    #[test]
+    fn test_unset_dates() {
+        let mut data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.created_opt(), Some(file.created()));
+        assert_eq!(file.modified_opt(), Some(file.modified()));
+
+        data[91..99].fill(0);
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert_eq!(file.created_opt(), None);
+        assert_eq!(file.modified_opt(), None);
+    }
+
+    #[test]
+    fn test_bad_secondary_header_len() {
+        let mut data = read_fixture("tests/Text File.bin");
+        data[120..122].copy_from_slice(&0x7F00_u16.to_be_bytes());
+        update_crc(&mut data);
+
+        assert_eq!(parse(&data).err(), Some(ParseError::BadEof));
+
+        let options = ParseOptions::new().ignore_bad_secondary_header(true);
+        let file = parse_with_options(&data, options).unwrap();
+        assert!(file.secondary_header_ignored());
+        check_text_file(&file, Version::III);
+    }
+
+    #[test]
+    fn test_secondary_header_not_ignored() {
+        let data = read_fixture("tests/Text File.bin");
+        let options = ParseOptions::new().ignore_bad_secondary_header(true);
+        let file = parse_with_options(&data, options).unwrap();
+        assert!(!file.secondary_header_ignored());
+    }
+
+    #[test]
+    fn test_secondary_header() {
+        let data = read_fixture("tests/Secondary header.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.secondary_header(), Some(&b"SECONDARY!"[..]));
+        assert_eq!(file.regions().secondary_header.data.len(), 128);
+        check_text_file(&file, Version::III);
+
+        // Declared secondary header runs past the end of the file
+        let mut truncated = data[..200].to_vec();
+        truncated[83..91].fill(0);
+        update_crc(&mut truncated);
+        assert_eq!(parse(&truncated).err(), Some(ParseError::BadEof));
+
+        let data = read_fixture("tests/Text File.bin");
+        assert_eq!(parse(&data).unwrap().secondary_header(), None);
+    }
+
+    #[test]
+    fn test_writer_versions() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.writer_version(), 129);
+        assert_eq!(file.min_reader_version(), 129);
+        assert_eq!(file.requires_version(), Some(Version::II));
+
+        let data = read_fixture("tests/Text File I.Bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.writer_version(), 0);
+        assert_eq!(file.min_reader_version(), 0);
+        assert_eq!(file.requires_version(), Some(Version::I));
+
+        let mut data = read_fixture("tests/Text File.bin");
+        data[123] = 131;
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert_eq!(file.min_reader_version(), 131);
+        assert_eq!(file.requires_version(), None);
+    }
+
+    #[test]
+    fn test_finder_info() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        let info = file.finder_info();
+
+        assert_eq!(info.file_type, FourCC(u32::from_be_bytes(*b"TEXT")));
+        assert_eq!(info.file_creator, FourCC(u32::from_be_bytes(*b"R*ch")));
+        assert_eq!(info.finder_flags, 0x0100);
+        assert_eq!((info.vpos, info.hpos), (156, 960));
+        assert_eq!(info.folder_id, 0);
+        assert_eq!(info.script, 0x80);
+        assert_eq!(info.extended_finder_flags, 0);
+        assert_eq!(info.created, 0xE040D4E8);
+        assert_eq!(info.modified, 0xE040D4E8);
+        assert!(!info.protected);
+    }
+
+    #[test]
+    fn test_metadata() {
+        let metadata = {
+            let data = read_fixture("tests/Text File.bin");
+            let file = parse(&data).unwrap();
+            file.metadata()
+        };
+
+        assert_eq!(
+            metadata,
+            Metadata {
+                version: Version::III,
+                filename: String::from("Text File"),
+                file_type: FourCC(u32::from_be_bytes(*b"TEXT")),
+                file_creator: FourCC(u32::from_be_bytes(*b"R*ch")),
+                finder_flags: FinderFlags::HAS_BEEN_INITED,
+                created: 1679500392,
+                modified: 1679500392,
+                data_fork_len: 21,
+                rsrc_fork_len: 1454,
+                script: 0x80,
+                protected: false,
+                label: Label::None,
+                position: FinderPosition {
+                    v: 156,
+                    h: 960,
+                    folder_id: 0
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        let debug = format!("{:?}", file);
+        for fragment in [
+            "MacBinary { version: III, ",
+            "filename: \"Text File\", ",
+            "file_type: 'TEXT', file_creator: 'R*ch', ",
+            "finder_flags: FinderFlags(HAS_BEEN_INITED), ",
+            "created: 2023-03-22 15:53:12, modified: 2023-03-22 15:53:12, ",
+            "data_fork_len: 21, rsrc_fork_len: 1454, ",
+            "crc: 0x839d",
+        ] {
+            assert!(
+                debug.contains(fragment),
+                "{} not found in {}",
+                fragment,
+                debug
+            );
+        }
+    }
+
+    #[test]
+    fn test_finder_info_bytes() {
+        let info = FinderInfo {
+            file_type: FourCC(u32::from_be_bytes(*b"APPL")),
+            file_creator: FourCC(u32::from_be_bytes(*b"ttxt")),
+            finder_flags: 0x2101,
+            vpos: -1,
+            hpos: 0x0203,
+            folder_id: -3,
+            script: 0x81,
+            extended_finder_flags: 0x04,
+            created: 1,
+            modified: 2,
+            protected: true,
+        };
+        assert_eq!(
+            info.to_finder_info_bytes(),
+            [
+                b'A', b'P', b'P', b'L', // fdType
+                b't', b't', b'x', b't', // fdCreator
+                0x21, 0x01, // fdFlags
+                0xFF, 0xFF, // fdLocation.v
+                0x02, 0x03, // fdLocation.h
+                0xFF, 0xFD, // fdFldr
+                0x00, 0x00, // fdIconID
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // fdUnused
+                0x81, // fdScript
+                0x04, // fdXFlags
+                0x00, 0x00, // fdComment
+                0x00, 0x00, 0x00, 0x00, // fdPutAway
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fourcc_try_new() {
+        assert_eq!(
+            FourCC::try_new(b"snd "),
+            Ok(FourCC(u32::from_be_bytes(*b"snd ")))
+        );
+        assert_eq!(FourCC::try_new(b"snd"), Err(FourCCError::BadLength(3)));
+        assert_eq!(FourCC::try_new(b"TEXTS"), Err(FourCCError::BadLength(5)));
+        assert_eq!(
+            FourCC::try_new(b"TE\0T"),
+            Err(FourCCError::BadByte { index: 2, byte: 0 })
+        );
+    }
+
+    #[test]
+    fn test_fourcc_from_str() {
+        for code in ["TEXT", "R*ch", "snd "] {
+            let fourcc = code.parse::<FourCC>().unwrap();
+            assert_eq!(&fourcc.as_bytes(), code.as_bytes());
+            assert_eq!(fourcc.to_string(), code);
+        }
+        assert_eq!(FourCC::try_from("STR#"), Ok(FourCC::new(*b"STR#")));
+
+        assert_eq!("\u{a9}dat".parse(), Ok(FourCC::new(*b"\xa9dat")));
+        assert_eq!("0x0000ABCD".parse(), Ok(FourCC(0xABCD)));
+        // Four characters, so not the hex form
+        assert_eq!("0x12".parse(), Ok(FourCC::new(*b"0x12")));
+        assert_eq!("0x\u{a9}d".parse(), Ok(FourCC::new(*b"0x\xa9d")));
+
+        assert_eq!("snd".parse::<FourCC>(), Err(FourCCError::BadLength(3)));
+        assert_eq!("TEXTS".parse::<FourCC>(), Err(FourCCError::BadLength(5)));
+        assert_eq!("\u{a9}da".parse::<FourCC>(), Err(FourCCError::BadLength(3)));
+        for hex in ["0x", "0x123", "0x0000ABCG", "0x+000ABCD", "0x0000ABCDE"] {
+            assert_eq!(hex.parse::<FourCC>(), Err(FourCCError::BadHex), "{}", hex);
+        }
+        assert_eq!(
+            "\u{263a}dat".parse::<FourCC>(),
+            Err(FourCCError::NotMacRoman {
+                index: 0,
+                ch: '\u{263a}'
+            })
+        );
+        assert_eq!(
+            "TE\tT".parse::<FourCC>(),
+            Err(FourCCError::BadByte {
+                index: 2,
+                byte: b'\t'
+            })
+        );
+    }
+
+    #[test]
+    fn test_fourcc_display_round_trip() {
+        let codes = [
+            *b"TEXT",
+            *b"\xa9dat", // printable high-bit character
+            *b"snd\xca", // non-breaking space, decoded as a space
+            *b"\xf6abc", // circumflex, decoded as ^
+            *b"TE\0T",   // control character
+            *b"\x7fDEL", // DEL is a control character too
+            *b"\x80\x9f\xd9\xff",
+            *b"0x12",
+            [0; 4],
+            [0xFF; 4],
+        ];
+        for code in codes {
+            let fourcc = FourCC::new(code);
+            assert_eq!(fourcc.to_string().parse(), Ok(fourcc), "{:#}", fourcc);
+        }
+        for byte in 0..=255 {
+            let fourcc = FourCC::new([byte, b'a', b'b', b'c']);
+            assert_eq!(fourcc.to_string().parse(), Ok(fourcc), "{:#}", fourcc);
+        }
+    }
+
+    #[test]
+    fn test_fourcc_eq() {
+        let icn = FourCC::new(*b"ICN#");
+        assert!(icn == *b"ICN#");
+        assert!(*b"ICN#" == icn);
+        assert!(icn != *b"ICON");
+        assert!(*b"ICON" != icn);
+
+        assert!(icn == "ICN#");
+        assert!("ICN#" == icn);
+        assert!(icn != "ICON");
+        assert!("ICON" != icn);
+        assert!(icn != "ICN");
+        assert!(icn != "ICN##");
+        assert!("" != icn);
+        // Strings are compared by their UTF-8 bytes
+        assert!(FourCC::new(*b"\xa9dat") != "\u{a9}dat");
+    }
+
+    #[test]
+    fn test_fourcc_ord() {
+        use std::collections::BTreeMap;
+
+        let mut counts = BTreeMap::new();
+        for code in [*b"STR#", *b"ICN#", *b"STR ", *b"ICN#", *b"snd "] {
+            *counts.entry(FourCC::new(code)).or_insert(0) += 1;
+        }
+        let counts: Vec<_> = counts
+            .into_iter()
+            .map(|(code, count)| (code.to_string(), count))
+            .collect();
+        assert_eq!(
+            counts,
+            [
+                (String::from("ICN#"), 2),
+                (String::from("STR "), 1),
+                (String::from("STR#"), 1),
+                (String::from("snd "), 1)
+            ]
+        );
+        assert!(FourCC::new(*b"AAAA") < FourCC::new(*b"AAAB"));
+        assert!(FourCC::new(*b"\xa9dat") > FourCC::new(*b"zzzz"));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        fn round_trip<T>(value: T, expected_json: &str)
+        where
+            T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + fmt::Debug,
+        {
+            let json = serde_json::to_string(&value).unwrap();
+            assert_eq!(json, expected_json);
+            assert_eq!(serde_json::from_str::<T>(&json).unwrap(), value);
+        }
+
+        round_trip(FourCC::new(*b"TEXT"), r#""TEXT""#);
+        round_trip(FourCC::new(*b"snd "), r#""snd ""#);
+        round_trip(FourCC::new(*b"TE\0T"), r#""0x54450054""#);
+        round_trip(FourCC::new(*b"\xa9dat"), r#""0xa9646174""#);
+        assert!(serde_json::from_str::<FourCC>(r#""snd""#).is_err());
+        assert_eq!(
+            serde_json::from_str::<FourCC>(r#""\u00e9dat""#).unwrap(),
+            FourCC::new(*b"\x8edat")
+        );
+        assert!(serde_json::from_str::<FourCC>(r#""\u263adat""#).is_err());
+        assert!(serde_json::from_str::<FourCC>("1413830740").is_err());
+
+        round_trip(Version::I, r#""I""#);
+        round_trip(Version::II, r#""II""#);
+        round_trip(Version::III, r#""III""#);
+
+        let data = read_fixture("tests/Text File.bin");
+        let metadata = parse(&data).unwrap().metadata();
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert!(json.contains(r#""file_type":"TEXT","file_creator":"R*ch""#));
+        assert_eq!(serde_json::from_str::<Metadata>(&json).unwrap(), metadata);
+        let stats = parse(&data).unwrap().stats();
+        let json = serde_json::to_string(&stats).unwrap();
+        assert!(json.starts_with(r#"{"version":"III","data_fork_len":"#));
+        assert_eq!(serde_json::from_str::<Stats>(&json).unwrap(), stats);
+
+        round_trip(Label::Red, r#""Red""#);
+        round_trip(
+            FinderPosition {
+                v: -1,
+                h: 2,
+                folder_id: -3,
+            },
+            r#"{"v":-1,"h":2,"folder_id":-3}"#,
+        );
+        let flags = FinderFlags::for_version(0xA10E, Version::I);
+        assert_eq!(
+            serde_json::from_str::<FinderFlags>(&serde_json::to_string(&flags).unwrap()).unwrap(),
+            flags
+        );
+        let flags = ExtendedFinderFlags::from_bits(0x8000);
+        assert_eq!(
+            serde_json::from_str::<ExtendedFinderFlags>(&serde_json::to_string(&flags).unwrap())
+                .unwrap(),
+            flags
+        );
+    }
+
+    #[test]
+    fn test_version_conversions() {
+        for (number, version, name, byte) in [
+            (1, Version::I, "MacBinary I", 0),
+            (2, Version::II, "MacBinary II", 129),
+            (3, Version::III, "MacBinary III", 130),
+        ] {
+            assert_eq!(Version::try_from(number), Ok(version));
+            assert_eq!(version.as_u8(), number);
+            assert_eq!(version.to_string(), name);
+            assert_eq!(version.header_version_byte(), byte);
+        }
+        assert_eq!(Version::try_from(0), Err(ParseError::BadVersion));
+        assert_eq!(Version::try_from(4), Err(ParseError::BadVersion));
+        assert_eq!(Version::try_from(130), Err(ParseError::BadVersion));
+    }
+
+    #[test]
+    fn test_fourcc_is_printable() {
+        assert!(FourCC(u32::from_be_bytes(*b"snd ")).is_printable());
+        assert!(!FourCC(u32::from_be_bytes(*b"TE\0T")).is_printable());
+        assert!(!FourCC(u32::from_be_bytes(*b"TE\x7fT")).is_printable());
+        assert!(FourCC(u32::from_be_bytes(*b"\xa9dat")).is_printable());
+        assert!(FourCC(u32::from_be_bytes(*b"TE\xcaT")).is_printable());
+    }
+
+    #[test]
+    fn test_fourcc_display() {
+        assert_eq!(FourCC(u32::from_be_bytes(*b"snd ")).to_string(), "snd ");
+        assert_eq!(
+            FourCC(u32::from_be_bytes(*b"TE\0T")).to_string(),
+            "0x54450054"
+        );
+        // High-bit MacRoman characters
+        assert_eq!(FourCC(u32::from_be_bytes(*b"\xa9dat")).to_string(), "©dat");
+        // Non-breaking space decodes as a space, so it's shown in hex to tell it apart from `TE T`
+        assert_eq!(
+            FourCC(u32::from_be_bytes(*b"TE\xcaT")).to_string(),
+            "0x5445ca54"
+        );
+        assert_eq!(
+            format!("{:>6}", FourCC(u32::from_be_bytes(*b"\xa9dat"))),
+            "  ©dat"
+        );
+    }
+
+    #[test]
+    fn test_fourcc_display_alternate() {
+        assert_eq!(
+            format!("{:#}", FourCC(u32::from_be_bytes(*b"snd "))),
+            "'snd ' (0x736e6420)"
+        );
+        assert_eq!(
+            format!("{:#}", FourCC(u32::from_be_bytes(*b"\xa9dat"))),
+            "'©dat' (0xa9646174)"
+        );
+        assert_eq!(
+            format!("{:#}", FourCC(u32::from_be_bytes(*b"TE\0T"))),
+            "'TE\\x00T' (0x54450054)"
+        );
+    }
+
+    #[test]
+    fn test_stats() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        let stats = file.stats();
+
+        assert_eq!(stats.version, Version::III);
+        assert_eq!(stats.data_fork_len, 21);
+        assert_eq!(stats.rsrc_fork_len, 1454);
+        // 128 byte header + padded data fork + resource fork + padding == file length
+        assert_eq!(stats.data_padding_len, 128 - 21);
+        assert_eq!(
+            128 + 128 + 1454 + stats.rsrc_padding_len as usize,
+            data.len()
+        );
+        assert_eq!(stats.resource_type_count, Some(2));
+        assert_eq!(stats.resource_count, Some(2));
+        // The fixture's resources are unnamed so the name list is empty
+        assert_eq!(stats.name_list_len, Some(0));
+        assert!(!stats.has_comment);
+        assert!(!stats.has_secondary_header);
+        assert!(stats.crc_matches);
+
+        let data = read_fixture("tests/No resource fork.txt.bin");
+        let stats = parse(&data).unwrap().stats();
+        assert_eq!(stats.rsrc_fork_len, 0);
+        assert_eq!(stats.rsrc_padding_len, 0);
+        assert_eq!(stats.resource_type_count, None);
+        assert_eq!(stats.resource_count, None);
+    }
+
+    #[test]
+    fn test_extract_data_fork() {
+        let data = read_fixture("tests/Text File.bin");
+        let mut out = Vec::new();
+        assert_eq!(extract_data_fork(&data, &mut out).unwrap(), 21);
+        assert_eq!(out, b"This is a test file.\r");
+    }
+
+    #[test]
+    fn test_extract_resource() {
+        let data = read_fixture("tests/Text File.bin");
+        let mpsr = FourCC(u32::from_be_bytes(*b"MPSR"));
+        let mut out = Vec::new();
+        assert_eq!(extract_resource(&data, mpsr, 1005, &mut out).unwrap(), 72);
+        assert_eq!(&out[..8], b"\x00\x09Monaco");
+
+        let mut out = Vec::new();
+        assert!(matches!(
+            extract_resource(&data, mpsr, 1, &mut out),
+            Err(ExtractError::NotFound)
+        ));
+        assert!(out.is_empty());
+
+        let data = read_fixture("tests/No resource fork.txt.bin");
+        assert!(matches!(
+            extract_resource(&data, mpsr, 1005, &mut out),
+            Err(ExtractError::NotFound)
+        ));
+
+        assert!(matches!(
+            extract_resource(&[0; 10], mpsr, 1005, &mut out),
+            Err(ExtractError::Parse(ParseError::BadVersion))
+        ));
+    }
+
+    #[test]
+    fn test_fork_lens() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.data_fork_len(), 21);
+        assert_eq!(
+            usize::num_from(file.data_fork_len()),
+            file.data_fork().len()
+        );
+        assert_eq!(file.resource_fork_len(), 1454);
+        assert_eq!(
+            usize::num_from(file.resource_fork_len()),
+            file.resource_fork_raw().len()
+        );
+    }
+
+    #[test]
+    fn test_encoded_len() {
+        for path in [
+            "tests/Text File.bin",
+            "tests/Text File I.Bin",
+            "tests/Secondary header.bin",
+            "tests/Date Test.bin",
+        ] {
+            let data = read_fixture(path);
+            let file = parse(&data).unwrap();
+            assert_eq!(file.encoded_len(), Ok(data.len()), "{}", path);
+            assert_eq!(encoded_len_from_header(&data[..128]), Ok(data.len()));
+        }
+
+        // The comment is included with its padding
+        let mut data = read_fixture("tests/Text File.bin");
+        data[99..][..2].copy_from_slice(&200_u16.to_be_bytes());
+        update_crc(&mut data);
+        assert_eq!(encoded_len_from_header(&data[..128]), Ok(1792 + 256));
+
+        assert_eq!(
+            encoded_len_from_header(&[0xFF; 128]),
+            Err(ParseError::BadVersion)
+        );
+    }
+
+    #[test]
+    fn test_encoded_len_overflow() {
+        let mut data = read_fixture("tests/Text File.bin");
+        data[83..][..4].copy_from_slice(&u32::MAX.to_be_bytes());
+        update_crc(&mut data);
+        assert_eq!(
+            encoded_len_from_header(&data[..128]),
+            Err(ParseError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_from_finder_info_bytes() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        let info = FinderInfo {
+            vpos: -20,
+            created: 0,
+            modified: 0,
+            protected: false,
+            ..file.finder_info()
+        };
+        let bytes = info.to_finder_info_bytes();
+        assert_eq!(FinderInfo::from_finder_info_bytes(&bytes), info);
+    }
+
+    #[test]
+    fn test_detect_resource_fork() {
+        let data = read_fixture("tests/Text File.rsrc");
+        assert!(detect_resource_fork(&data));
+        assert!(detect_resource_fork(&read_fixture(
+            "tests/Empty resource fork.rsrc"
+        )));
+        assert!(!detect_resource_fork(&data[..1000]));
+        assert!(!detect_resource_fork(&read_fixture("tests/Text File.bin")));
+        assert!(!detect_resource_fork(&read_fixture(
+            "tests/Secondary header.bin"
+        )));
+        assert!(!detect_resource_fork(&[]));
+        assert!(!detect_resource_fork(&[0xA5; 64]));
+
+        // A zeroed copy of the header in the map is accepted, but not a different one
+        let mut data = data;
+        data[1384..][..16].fill(0);
+        assert!(detect_resource_fork(&data));
+        data[1384] = 1;
+        assert!(!detect_resource_fork(&data));
+    }
+
+    #[test]
+    fn test_parse_resource_fork() {
+        let data = read_fixture("tests/Text File.rsrc");
+        let rsrc = parse_resource_fork(&data).unwrap();
+        assert_eq!(rsrc.resource_count(), 2);
+        let mpsr = rsrc.get_resource(FourCC::new(*b"MPSR"), 1005);
+        assert_eq!(mpsr.unwrap().data().len(), 72);
+        assert!(parse_resource_fork(&data[..8]).is_err());
+    }
+
+    #[test]
+    fn test_detect_complete() {
+        let data = read_fixture("tests/Text File.bin");
+        assert_eq!(detect_complete(&data), Some(Version::III));
+        // The padding after the resource fork is optional
+        assert_eq!(detect_complete(&data[..256 + 1454]), Some(Version::III));
+
+        let truncated = &data[..256 + 1354];
+        assert_eq!(detect(truncated), Some(Version::III));
+        assert_eq!(detect_complete(truncated), None);
+        let err = parse(truncated).err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::Truncated {
+                expected: 1710,
+                actual: 1610
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "data appears truncated: expected at least 1710 bytes, found 1610"
+        );
+
+        // The secondary header counts towards the length
+        let data = read_fixture("tests/Secondary header.bin");
+        assert_eq!(detect_complete(&data), Some(Version::III));
+        assert_eq!(detect_complete(&data[..384 + 1000]), None);
+
+        let data = read_fixture("tests/Text File I.Bin");
+        assert_eq!(detect_complete(&data), Some(Version::I));
+        assert_eq!(detect_complete(&data[..200]), None);
+    }
+
+    #[test]
+    fn test_truncated_forks() {
+        let lenient = ParseOptions::new().allow_truncated(true);
+        let data = macbinary_with_forks(b"data", b"rsrc");
+
+        // Both forks declared but missing
+        assert!(matches!(
+            parse(&data[..128]),
+            Err(ParseError::Truncated { .. })
+        ));
+        let file = parse_with_options(&data[..128], lenient).unwrap();
+        assert_eq!(file.data_fork(), b"");
+        assert_eq!(file.data_fork_declared_len(), 4);
+        assert!(!file.data_fork_complete());
+        assert_eq!(file.resource_fork_raw(), b"");
+        assert_eq!(file.resource_fork_declared_len(), 4);
+        assert!(!file.resource_fork_complete());
+        let issues = file.validate();
+        let truncated: Vec<_> = issues
+            .with_code(validate::code::TRUNCATED_FORK)
+            .map(|issue| issue.offset)
+            .collect();
+        assert_eq!(truncated, [Some(128), Some(128)]);
+        assert_eq!(issues.with_code(validate::code::MISSING_PADDING).count(), 0);
+
+        // Resource fork declared but missing, data fork partly present
+        let file = parse_with_options(&data[..128 + 2], lenient).unwrap();
+        assert_eq!(file.data_fork(), b"da");
+        assert!(!file.data_fork_complete());
+        assert!(!file.resource_fork_complete());
+
+        // Only the resource fork is missing
+        let file = parse_with_options(&data[..256], lenient).unwrap();
+        assert!(file.data_fork_complete());
+        assert!(!file.resource_fork_complete());
+        assert_eq!(file.resource_fork_declared_len(), 4);
+        assert_eq!(file.resource_fork_raw(), b"");
+
+        // Complete files and empty forks are complete
+        let file = parse_with_options(&data, lenient).unwrap();
+        assert!(file.data_fork_complete() && file.resource_fork_complete());
+        let empty = macbinary_with_forks(b"", b"");
+        let file = parse(&empty).unwrap();
+        assert_eq!(file.data_fork_declared_len(), 0);
+        assert!(file.data_fork_complete() && file.resource_fork_complete());
+
+        // Extraction refuses truncated files unless allowed
+        let mut out = Vec::new();
+        assert!(extract_data_fork(&data[..128 + 2], &mut out).is_err());
+        assert!(out.is_empty());
+        assert_eq!(
+            extract_data_fork_with_options(&data[..128 + 2], lenient, &mut out).unwrap(),
+            2
+        );
+        assert_eq!(out, b"da");
+    }
+
+    #[test]
+    fn test_version_limits() {
+        assert_eq!(Version::I.max_fork_len(), 0x007F_FFFF);
+        assert_eq!(Version::II.max_fork_len(), u32::MAX);
+        assert_eq!(Version::III.max_fork_len(), u32::MAX);
+        assert_eq!(Version::I.max_filename_len(), 63);
+        assert_eq!(Version::II.max_filename_len(), 31);
+        assert_eq!(Version::III.max_filename_len(), 31);
+    }
+
+    #[test]
+    fn test_filename_bytes() {
+        let data = read_fixture("tests/Text File.bin");
+        let name = {
+            let file = parse(&data).unwrap();
+            file.filename_bytes()
+        };
+        assert_eq!(name, b"Text File");
+
+        // Append ä in MacRoman
+        let mut data = data;
+        data[1] = 10;
+        data[11] = 0x8A;
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert_eq!(file.filename_bytes(), b"Text File\x8A");
+        assert_eq!(file.filename(), "Text File\u{e4}");
+    }
+
+    #[test]
+    fn test_filename_with_policy() {
+        // Replace the space with a byte that isn't in MacRoman
+        let mut data = read_fixture("tests/Text File.bin");
+        data[6] = 0xAD;
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert_eq!(file.filename(), "Text\u{FFFD}File");
+        assert_eq!(
+            file.filename_with_policy(DecodePolicy::default()).unwrap(),
+            file.filename()
+        );
+        assert_eq!(
+            file.filename_with_policy(DecodePolicy::Replace('_'))
+                .unwrap(),
+            "Text_File"
+        );
+        assert_eq!(
+            file.filename_with_policy(DecodePolicy::Skip).unwrap(),
+            "TextFile"
+        );
+        assert_eq!(
+            file.filename_with_policy(DecodePolicy::Error),
+            Err(MacRomanError {
+                position: 4,
+                byte: 0xAD
+            })
+        );
+    }
+
+    /// Parse the Text File fixture with its name replaced by `name`
+    fn with_filename(name: &[u8]) -> Vec<u8> {
+        let mut data = read_fixture("tests/Text File.bin");
+        data[1] = name.len() as u8;
+        data[2..][..63].fill(0);
+        data[2..][..name.len()].copy_from_slice(name);
+        update_crc(&mut data);
+        data
+    }
 
-        ResourceFork::new(self.rsrc_fork).map(Some)
+    #[test]
+    fn test_filename_sanitized() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(
+            file.filename_sanitized(SanitizeOptions::new()),
+            SanitizedFilename {
+                name: String::from("Text File"),
+                changed: false
+            }
+        );
+
+        let data = with_filename(b"Q1/Q2 Report");
+        let file = parse(&data).unwrap();
+        assert_eq!(
+            file.filename_sanitized(SanitizeOptions::new()),
+            SanitizedFilename {
+                name: String::from("Q1:Q2 Report"),
+                changed: true
+            }
+        );
+
+        let data = with_filename(b"Icon\r\0");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.filename_sanitized(SanitizeOptions::new()).name, "Icon");
+        let sanitized = file.filename_sanitized(SanitizeOptions::new().control_replacement('_'));
+        assert_eq!(sanitized.name, "Icon__");
+        assert!(sanitized.changed);
     }
-}
 
-impl ReadFrom for FourCC {
-    type ReadType = U32Be;
+    #[test]
+    fn test_filename_sanitized_truncate() {
+        // 31 × ä, each two bytes in UTF-8
+        let data = with_filename(&[0x8A; 31]);
+        let file = parse(&data).unwrap();
+        let sanitized = file.filename_sanitized(SanitizeOptions::new().max_len(25));
+        assert_eq!(sanitized.name, "\u{e4}".repeat(12));
+        assert!(sanitized.changed);
 
-    fn from(value: u32) -> Self {
-        FourCC(value)
+        let sanitized = file.filename_sanitized(SanitizeOptions::new().max_len(62));
+        assert_eq!(sanitized.name.len(), 62);
+        assert!(!sanitized.changed);
     }
-}
 
-impl Display for FourCC {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let tag = self.0;
-        let bytes = tag.to_be_bytes();
-        if bytes.iter().all(|c| c.is_ascii() && !c.is_ascii_control()) {
-            let s = core::str::from_utf8(&bytes).unwrap(); // unwrap safe due to above check
-            s.fmt(f)
-        } else {
-            write!(f, "0x{:08x}", tag)
+    // detect accepts MacBinary I file names up to 63 bytes, parse must too
+    #[test]
+    fn test_macbinary_1_long_filename() {
+        let mut data = read_fixture("tests/Text File I.Bin");
+        data[1] = 40;
+        assert_eq!(detect(&data), Some(Version::I));
+        let file = parse(&data).unwrap();
+        assert_eq!(file.filename_bytes().len(), 40);
+        assert!(file.filename_bytes().starts_with(b"Text File\0"));
+
+        // but not for later versions
+        let mut data = read_fixture("tests/Text File.bin");
+        data[1] = 40;
+        update_crc(&mut data);
+        assert_eq!(detect(&data), Some(Version::III));
+        assert_eq!(parse(&data).err(), Some(ParseError::BadValue));
+    }
+
+    #[test]
+    fn test_strict_zero_fill() {
+        let mut data = read_fixture("tests/Text File.bin");
+        data[82] = 1;
+        update_crc(&mut data);
+        assert!(parse(&data).is_ok());
+        assert_eq!(
+            parse_with_options(&data, ParseOptions::new().strict(true)).err(),
+            Some(ParseError::BadValue)
+        );
+
+        let data = read_fixture("tests/Text File.bin");
+        assert!(parse_with_options(&data, ParseOptions::new().strict(true)).is_ok());
+    }
+
+    #[test]
+    fn test_header_crc_chunks() {
+        let data = read_fixture("tests/Text File.bin");
+        let header: &[u8; 128] = data[..128].try_into().unwrap();
+        let expected = u16::from_be_bytes([header[124], header[125]]);
+
+        for chunk_size in [1, 7, 128] {
+            let mut crc = HeaderCrc::new();
+            for chunk in header.chunks(chunk_size) {
+                crc.update(chunk);
+            }
+            assert!(crc.is_complete());
+            assert_eq!(crc.finalize(), expected, "chunk size {}", chunk_size);
         }
     }
-}
 
-impl fmt::Debug for FourCC {
-    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "'{}'", self)
+    #[test]
+    fn test_verify_header_crc() {
+        let mut data = read_fixture("tests/Text File.bin");
+        let header: &mut [u8; 128] = (&mut data[..128]).try_into().unwrap();
+        assert_eq!(verify_header_crc(header), Ok(()));
+
+        header[2] = b't';
+        assert_eq!(
+            verify_header_crc(header),
+            Err(ParseError::CrcMismatch {
+                expected: 0x839d,
+                actual: 0x6835
+            })
+        );
     }
-}
 
-fn next_u16_multiple_of_128(value: u16) -> Result<u16, ParseError> {
-    let rem = value % 128;
-    if rem == 0 {
-        Ok(value)
-    } else {
-        value.checked_add(128 - rem).ok_or(ParseError::Overflow)
+    #[test]
+    fn test_crc() {
+        let mut data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.stored_crc(), 0x839d);
+        assert_eq!(file.computed_crc(), 0x839d);
+
+        data[2] = b't';
+        let err = parse(&data).err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::CrcMismatch {
+                expected: 0x839d,
+                actual: 0x6835
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "CRC mismatch: expected 0x839d, calculated 0x6835"
+        );
     }
-}
 
-fn next_u32_multiple_of_128(value: u32) -> Result<u32, ParseError> {
-    let rem = value % 128;
-    if rem == 0 {
-        Ok(value)
-    } else {
-        value.checked_add(128 - rem).ok_or(ParseError::Overflow)
+    #[test]
+    fn test_padded_fork_lengths() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.data_fork_padded_len(), Ok(128));
+        assert_eq!(file.resource_fork_padded_len(), Ok(1536));
+
+        for (len, padded) in [(0, 0), (128, 128), (256, 256), (127, 128), (129, 256)] {
+            let fork = vec![1; len];
+            let data = macbinary_with_forks(&fork, &fork);
+            let file = parse(&data).unwrap();
+            assert_eq!(file.data_fork_padded_len(), Ok(padded));
+            assert_eq!(file.resource_fork_padded_len(), Ok(padded));
+        }
     }
-}
 
-/// Convert Mac OS timestamp to UNIX timestamp
-///
-/// The Mac OS epoch is 1 January 1904, UNIX epoch is 1 Jan 1970.
-fn mactime(timestamp: u32) -> u32 {
-    // 66 years from 1904 to 1970, 17 leap years, 86400 seconds in a day
-    const OFFSET: u32 = 66 * 365 * 86400 + (17 * 86400);
-    timestamp.wrapping_sub(OFFSET)
-}
+    #[test]
+    fn test_padding_is_zeroed() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert!(file.padding_is_zeroed());
+        assert_eq!(file.nonzero_padding(), None);
 
-fn calc_crc(data: &[u8]) -> u16 {
-    let crc: Crc<u16> = Crc::<u16>::new(&CRC_16_XMODEM);
-    crc.checksum(data)
-}
+        // Hide a string in the data fork padding
+        let mut data = macbinary_with_forks(b"data", b"rsrc");
+        data[128 + 4 + 10..][..20].copy_from_slice(b"hidden between forks");
+        let file = parse(&data).unwrap();
+        assert!(!file.padding_is_zeroed());
+        assert_eq!(
+            file.nonzero_padding(),
+            Some(NonZeroPadding {
+                offset: 142,
+                preview: b"hidden between f",
+            })
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::test::read_fixture;
+        // Non-zero resource fork padding, truncated by the end of the file
+        let mut data = macbinary_with_forks(b"data", b"rsrc");
+        data.truncate(128 + 128 + 4 + 2);
+        data[128 + 128 + 4 + 1] = 0xFF;
+        let file = parse(&data).unwrap();
+        assert_eq!(
+            file.nonzero_padding(),
+            Some(NonZeroPadding {
+                offset: 261,
+                preview: &[0xFF],
+            })
+        );
+    }
+
+    #[test]
+    fn test_os_id() {
+        let data = read_fixture("tests/Text File.bin");
+        assert_eq!(parse(&data).unwrap().os_id(), 0);
+
+        let mut data = macbinary_with_forks(b"data", b"rsrc");
+        data[126..128].copy_from_slice(&0xDEADu16.to_be_bytes());
+        let file = parse(&data).unwrap();
+        assert_eq!(file.os_id(), 0xDEAD);
+    }
+
+    fn assert_regions_cover(file: &MacBinary<'_>, len: usize) {
+        let regions = file.regions();
+        let mut offset = 0;
+        for region in [
+            regions.header,
+            regions.secondary_header,
+            regions.data_fork,
+            regions.data_padding,
+            regions.rsrc_fork,
+            regions.rsrc_padding,
+            regions.comment,
+            regions.trailing,
+        ] {
+            assert_eq!(region.offset, offset);
+            offset += region.data.len();
+        }
+        assert_eq!(offset, len);
+    }
 
     #[test]
-    fn test_next_multiple() {
-        assert_eq!(next_u16_multiple_of_128(0), Ok(0));
-        assert_eq!(next_u16_multiple_of_128(3), Ok(128));
-        assert_eq!(next_u16_multiple_of_128(128), Ok(128));
-        assert_eq!(next_u16_multiple_of_128(129), Ok(256));
+    fn test_regions() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_regions_cover(&file, data.len());
+        let regions = file.regions();
+        assert_eq!(regions.header.data, &data[..128]);
+        assert_eq!(regions.data_fork.offset, 128);
+        assert_eq!(regions.data_fork.data, file.data_fork());
+        assert_eq!(regions.data_padding.data.len(), 128 - 21);
+        assert_eq!(regions.rsrc_fork.offset, 256);
+        assert_eq!(regions.rsrc_fork.data, file.resource_fork_raw());
+
+        // No resource fork
+        let data = macbinary_with_forks(b"data", b"");
+        let file = parse(&data).unwrap();
+        assert_regions_cover(&file, data.len());
+        assert!(file.regions().rsrc_fork.data.is_empty());
+        assert!(file.regions().trailing.data.is_empty());
+
+        // Unpadded tail
+        let mut data = macbinary_with_forks(b"data", b"rsrc");
+        data.truncate(128 + 128 + 4);
+        let file = parse(&data).unwrap();
+        assert_regions_cover(&file, data.len());
+        assert!(file.regions().rsrc_padding.data.is_empty());
 
-        assert_eq!(next_u32_multiple_of_128(0), Ok(0));
-        assert_eq!(next_u32_multiple_of_128(3), Ok(128));
-        assert_eq!(next_u32_multiple_of_128(128), Ok(128));
-        assert_eq!(next_u32_multiple_of_128(129), Ok(256));
+        // Comment and trailing data
+        let mut data = macbinary_with_forks(b"data", b"rsrc");
+        data[99..101].copy_from_slice(&7u16.to_be_bytes());
+        update_crc(&mut data);
+        data.extend_from_slice(b"comment");
+        data.extend_from_slice(b"trailer");
+        let file = parse(&data).unwrap();
+        assert_regions_cover(&file, data.len());
+        let regions = file.regions();
+        assert_eq!(regions.comment.offset, 384);
+        assert_eq!(regions.comment.data, b"comment");
+        assert_eq!(regions.trailing.data, b"trailer");
     }
 
     #[test]
-    fn test_next_multiple_overflow() {
+    fn test_validate() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        let issues = file.validate();
+        let codes: Vec<_> = issues.iter().map(|issue| issue.code).collect();
+        // The resource fork was written by the Resource Manager
         assert_eq!(
-            next_u16_multiple_of_128(u16::MAX - 3),
-            Err(ParseError::Overflow)
+            codes,
+            [
+                validate::code::NONZERO_RESERVED_HEADER,
+                validate::code::NONZERO_MAP_RESERVED,
+            ]
         );
+        let map_reserved = issues
+            .with_code(validate::code::NONZERO_MAP_RESERVED)
+            .next()
+            .unwrap();
+        assert_eq!(map_reserved.offset, Some(256 + 1384 + 16));
+        assert_eq!(issues.max_severity(), Some(validate::Severity::Info));
+
+        let mut data = macbinary_with_forks(b"data", b"");
+        data[74] = 1;
+        data[126..128].copy_from_slice(&0xDEADu16.to_be_bytes());
+        update_crc(&mut data);
+        data[128 + 10] = b'!';
+        data.extend_from_slice(b"trailer");
+        let file = parse(&data).unwrap();
+        let issues = file.validate();
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.severity, issue.code, issue.offset))
+            .collect();
         assert_eq!(
-            next_u32_multiple_of_128(u32::MAX - 3),
-            Err(ParseError::Overflow)
+            found,
+            [
+                (
+                    Severity::Warning,
+                    validate::code::NONZERO_ZERO_FILL,
+                    Some(74)
+                ),
+                (Severity::Info, validate::code::NONZERO_OS_ID, Some(126)),
+                (
+                    Severity::Warning,
+                    validate::code::NONZERO_PADDING,
+                    Some(138)
+                ),
+                (Severity::Info, validate::code::TRAILING_DATA, Some(256)),
+            ]
         );
+        assert!(!issues.has_errors());
+
+        // Resource fork padding left off
+        let mut data = macbinary_with_forks(b"", b"rsrc");
+        data.truncate(128 + 4);
+        let file = parse(&data).unwrap();
+        let issue = file
+            .validate()
+            .with_code(validate::code::MISSING_PADDING)
+            .next()
+            .cloned()
+            .unwrap();
+        assert_eq!(issue.offset, Some(132));
+
+        // Unparseable resource fork
+        let data = macbinary_with_forks(b"", b"not a resource fork");
+        let file = parse(&data).unwrap();
+        let issue = file
+            .validate()
+            .with_code(validate::code::INVALID_RESOURCE_FORK)
+            .next()
+            .cloned()
+            .unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.offset, Some(128));
     }
 
-    fn check_text_file(file: &MacBinary, version: Version) {
-        assert_eq!(file.version(), version);
+    #[test]
+    fn test_version_resource() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.version_resource(), Ok(None));
+        let info = HeaderInfo {
+            version: Version::III,
+            filename: b"Application",
+            finder_info: file.finder_info(),
+        };
+
+        let vers = b"\x01\x20\x80\x00\x00\x00\x031.2\x0bVersion 1.2";
+        let product = b"\x02\x00\x80\x00\x00\x00\x032.0\x00";
+        let rsrc = resource_fork(&[(*b"vers", 2, None, product), (*b"vers", 1, None, vers)]);
+        let file = MacBinary::from_parts(info, b"", &rsrc).unwrap();
+        let version = file.version_resource().unwrap().unwrap();
+        assert_eq!((version.major, version.minor, version.bug_fix), (1, 2, 0));
+        assert_eq!(version.long_version(), "Version 1.2");
+
+        // The product version is used when there's no file version
+        let rsrc = resource_fork(&[(*b"vers", 2, None, product)]);
+        let file = MacBinary::from_parts(info, b"", &rsrc).unwrap();
+        let version = file.version_resource().unwrap().unwrap();
+        assert_eq!(version.short_version(), "2.0");
+
+        let rsrc = resource_fork(&[(*b"vers", 1, None, &vers[..10])]);
+        let file = MacBinary::from_parts(info, b"", &rsrc).unwrap();
+        assert_eq!(file.version_resource(), Err(ParseError::BadEof));
+
+        // Unreadable data is an error rather than falling back to the product version
+        let mut rsrc = resource_fork(&[(*b"vers", 1, None, vers), (*b"vers", 2, None, product)]);
+        let reference = type_list_offset(&rsrc) + 10;
+        rsrc[reference + 5..][..3].copy_from_slice(&[0x00, 0xFF, 0xFF]);
+        let file = MacBinary::from_parts(info, b"", &rsrc).unwrap();
+        assert_eq!(file.version_resource(), Err(ParseError::BadEof));
+
+        // As is a reference list outside the map
+        let mut rsrc = resource_fork(&[(*b"vers", 1, None, vers)]);
+        let type_list = type_list_offset(&rsrc);
+        rsrc[type_list + 8..][..2].copy_from_slice(&0xFFF0_u16.to_be_bytes());
+        let file = MacBinary::from_parts(info, b"", &rsrc).unwrap();
+        assert_eq!(file.version_resource(), Err(ParseError::BadOffset));
+
+        let file = MacBinary::from_parts(info, b"", &[]).unwrap();
+        assert_eq!(file.version_resource(), Ok(None));
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let data = read_fixture("tests/Text File.bin");
+        let parsed = parse(&data).unwrap();
+        let info = HeaderInfo {
+            version: Version::III,
+            filename: parsed.filename_bytes(),
+            finder_info: parsed.finder_info(),
+        };
+        let file =
+            MacBinary::from_parts(info, parsed.data_fork(), parsed.resource_fork_raw()).unwrap();
+
+        assert_eq!(file.version(), parsed.version());
+        assert_eq!(file.filename(), parsed.filename());
+        assert_eq!(file.file_type(), parsed.file_type());
+        assert_eq!(file.file_creator(), parsed.file_creator());
+        assert_eq!(file.created(), parsed.created());
+        assert_eq!(file.modified(), parsed.modified());
+        assert_eq!(file.finder_info(), parsed.finder_info());
+        assert_eq!(file.data_fork(), parsed.data_fork());
+        assert_eq!(file.resource_fork_raw(), parsed.resource_fork_raw());
+        assert_eq!(file.data_fork_padded_len(), parsed.data_fork_padded_len());
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let mpsr = rsrc
+            .get_resource(FourCC(u32::from_be_bytes(*b"MPSR")), 1005)
+            .unwrap();
+        assert_eq!(mpsr.data().len(), 72);
+        assert_eq!(file.stats().resource_count, Some(2));
+        assert!(file.regions().header.data.is_empty());
+        assert_eq!(file.validate().len(), parsed.validate().len());
+
+        // Long filenames are only allowed in MacBinary I
+        let long_name = [b'a'; 40];
+        let info = HeaderInfo {
+            version: Version::II,
+            filename: &long_name,
+            finder_info: parsed.finder_info(),
+        };
+        assert!(matches!(
+            MacBinary::from_parts(info, &[], &[]),
+            Err(ParseError::BadValue)
+        ));
+        let info = HeaderInfo {
+            version: Version::I,
+            ..info
+        };
+        assert!(MacBinary::from_parts(info, &[], &[]).is_ok());
+    }
+
+    #[test]
+    fn test_absurd_fork_len() {
+        let mut data = macbinary_with_forks(b"data", b"rsrc");
+        data[87..91].copy_from_slice(&u32::MAX.to_be_bytes());
+        update_crc(&mut data);
+        assert_eq!(
+            parse(&data).err(),
+            Some(ParseError::Truncated {
+                expected: 256 + 0xFFFF_FFFF,
+                actual: 384
+            })
+        );
+
+        let data = macbinary_with_forks(b"data", b"rsrc");
+        assert_eq!(
+            parse(&data[..128 + 2]).err(),
+            Some(ParseError::Truncated {
+                expected: 260,
+                actual: 130
+            })
+        );
+    }
+
+    // A download cut off part way through the resource fork
+    #[test]
+    fn test_truncated_in_resource_fork() {
+        let data = read_fixture("tests/Text File.bin");
+        assert_eq!(
+            parse(&data[..1000]).err(),
+            Some(ParseError::Truncated {
+                expected: 1710,
+                actual: 1000
+            })
+        );
+    }
+
+    // Declared lengths beyond 2 GiB don't fit in isize on 32-bit targets, such as wasm32
+    #[test]
+    fn test_huge_declared_fork_len() {
+        for len in [0x8000_0000_u32, 0xFFFF_FF80, u32::MAX] {
+            for field in [83, 87] {
+                let mut data = macbinary_with_forks(b"data", b"rsrc");
+                data[field..field + 4].copy_from_slice(&len.to_be_bytes());
+                update_crc(&mut data);
+                assert!(matches!(
+                    parse(&data).err(),
+                    Some(ParseError::Truncated { actual: 384, .. })
+                ));
+            }
+        }
+        assert_eq!(pad_to_block(0xFFFF_FF80_u32), Ok(0xFFFF_FF80));
+        assert_eq!(pad_to_block(0xFFFF_FF81_u32), Err(ParseError::Overflow));
+    }
+
+    // The 8 MiB limit on forks only applies to MacBinary I
+    #[test]
+    fn test_large_data_fork() {
+        // Only the header is needed to check the lengths, so the fork itself is left out
+        const LEN: u32 = 100 * 1024 * 1024 + 1;
+        let mut data = macbinary_with_forks(b"", b"");
+        data[83..87].copy_from_slice(&LEN.to_be_bytes());
+        update_crc(&mut data);
+        assert_eq!(
+            parse(&data).err(),
+            Some(ParseError::Truncated {
+                expected: 128 + u64::from(LEN) + 127,
+                actual: 128
+            })
+        );
+        assert_eq!(
+            encoded_len_from_header(&data),
+            Ok(128 + usize::num_from(LEN) + 127)
+        );
+        let lenient = ParseOptions::new().allow_truncated(true);
+        let file = parse_with_options(&data, lenient).unwrap();
+        assert_eq!(file.version(), Version::III);
+        assert_eq!(file.data_fork_declared_len(), LEN);
+        assert_eq!(file.data_fork_padded_len(), Ok(LEN + 127));
+
+        // Detection heuristics for MacBinary II don't apply the limit either
+        data[102..106].copy_from_slice(&[0; 4]);
+        update_crc(&mut data);
+        assert_eq!(detect(&data), Some(Version::II));
+        let file = parse_with_options(&data, lenient).unwrap();
+        assert_eq!(file.data_fork_declared_len(), LEN);
+    }
+
+    #[test]
+    fn test_unpacked_total_len() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.unpacked_total_len(), None);
+
+        let mut data = macbinary_with_forks(b"data", b"rsrc");
+        data[116..120].copy_from_slice(&123_456u32.to_be_bytes());
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert_eq!(file.unpacked_total_len(), Some(123_456));
+        let issues = file.validate();
+        let issue = issues
+            .with_code(validate::code::UNPACKED_TOTAL_LEN)
+            .next()
+            .unwrap();
+        assert_eq!(issue.severity, Severity::Info);
+        assert_eq!(issue.offset, Some(116));
+    }
+
+    #[test]
+    fn test_metadata_only() {
+        let data = macbinary_with_forks(b"", b"");
+        assert_eq!(data.len(), 128);
+        let file = parse(&data).unwrap();
+        assert!(file.is_metadata_only());
         assert_eq!(file.filename(), "Text File");
         assert_eq!(file.file_type(), FourCC(u32::from_be_bytes(*b"TEXT")));
-        assert_eq!(file.file_creator(), FourCC(u32::from_be_bytes(*b"R*ch"))); // BBEdit
-        assert_eq!(file.data_fork(), b"This is a test file.\r");
-        assert_eq!(file.resource_fork_raw().len(), 1454);
+        assert!(file.data_fork().is_empty());
+        assert!(file.resource_fork_raw().is_empty());
+        assert!(matches!(file.resource_fork(), Ok(None)));
+        assert!(file.validate().is_empty());
+        let stats = file.stats();
+        assert_eq!(stats.resource_count, None);
+        assert_eq!(stats.data_padding_len, 0);
+
+        let data = read_fixture("tests/Text File.bin");
+        assert!(!parse(&data).unwrap().is_metadata_only());
+
+        // Truncated forks are an error rather than metadata-only
+        let data = macbinary_with_forks(b"data", b"");
+        assert!(matches!(
+            parse(&data[..128]),
+            Err(ParseError::Truncated { .. })
+        ));
+        // and aren't metadata-only when truncation is allowed either
+        let lenient = ParseOptions::new().allow_truncated(true);
+        let file = parse_with_options(&data[..128], lenient).unwrap();
+        assert!(file.data_fork().is_empty() && file.resource_fork_raw().is_empty());
+        assert!(!file.is_metadata_only());
     }
 
     #[test]
-    fn test_macbinary_1() {
-        let data = read_fixture("tests/Text File I.Bin");
+    fn test_finder_flags() {
+        let data = read_fixture("tests/Text File.bin");
         let file = parse(&data).unwrap();
+        let flags = file.finder_flags();
+        assert_eq!(flags.bits(), 0x0100);
+        assert!(flags.contains(FinderFlags::HAS_BEEN_INITED));
+        assert!(!flags.contains(FinderFlags::IS_INVISIBLE));
+        assert!(!flags.contains(FinderFlags::IS_STATIONERY));
+        assert_eq!(flags.bits(), file.finder_info().finder_flags);
 
-        check_text_file(&file, Version::I);
+        let mut data = macbinary_with_forks(b"", b"");
+        data[73] = 0x48; // isInvisible, isStationery
+        data[101] = 0x0A; // color 5
+        update_crc(&mut data);
+        let flags = parse(&data).unwrap().finder_flags();
+        assert!(flags.contains(FinderFlags::IS_INVISIBLE | FinderFlags::IS_STATIONERY));
+        assert_eq!(flags.color(), 5);
     }
 
     #[test]
-    fn test_macbinary_2() {
-        let data = read_fixture("tests/Text File II.bin");
+    fn test_fork_digest() {
+        let data = read_fixture("tests/Text File.bin");
         let file = parse(&data).unwrap();
+        assert_eq!(
+            file.fork_digest(ForkKind::Data, Algo::Crc32),
+            digest::Digest::Crc32(0x44e72dfd)
+        );
+        assert_eq!(
+            file.fork_digest(ForkKind::Resource, Algo::Crc32),
+            digest::Digest::Crc32(0xcea9de3c)
+        );
+        assert_eq!(
+            file.content_digest(Algo::Crc32),
+            digest::Digest::Crc32(0x9ed19e44)
+        );
 
-        check_text_file(&file, Version::II);
+        // Same forks under a different name
+        let mut renamed = data.clone();
+        renamed[2] = b'N';
+        update_crc(&mut renamed);
+        let renamed = parse(&renamed).unwrap();
+        assert_eq!(
+            renamed.content_digest(Algo::Crc32),
+            file.content_digest(Algo::Crc32)
+        );
     }
 
     #[test]
-    fn test_macbinary_3() {
+    fn test_finder_flag_predicates() {
+        let mut data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(file.version(), Version::III);
+        assert!(!file.is_invisible());
+        assert!(!file.is_alias());
+        assert!(!file.has_bundle());
+        assert!(!file.is_stationery());
+        assert!(!file.has_custom_icon());
+
+        data[73] = 0x64; // isInvisible, hasBundle, hasCustomIcon
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert!(file.is_invisible());
+        assert!(!file.is_alias());
+        assert!(file.has_bundle());
+        assert!(!file.is_stationery());
+        assert!(file.has_custom_icon());
+    }
+
+    #[test]
+    fn test_probe() {
         let data = read_fixture("tests/Text File.bin");
         let file = parse(&data).unwrap();
 
-        check_text_file(&file, Version::III);
+        // Only the header and the resource fork header
+        let probed = probe(&data[..256 + 16]).unwrap();
+        assert_eq!(probed.info.version, Version::III);
+        assert_eq!(probed.info.filename, file.filename_bytes());
+        assert_eq!(probed.info.finder_info, file.finder_info());
+        assert_eq!(probed.data_fork_len, 21);
+        assert_eq!(probed.rsrc_fork_len, 1454);
+        assert_eq!(
+            probed.rsrc_header,
+            Some(ResourceForkHeader {
+                data_offset: 256,
+                map_offset: 1384,
+                data_len: 1128,
+                map_len: 70,
+            })
+        );
+        assert!(parse(&data[..256 + 16]).is_err());
+
+        // The resource fork header is incomplete
+        assert_eq!(probe(&data[..256 + 15]).unwrap().rsrc_header, None);
+        assert_eq!(probe(&data[..128]).unwrap().rsrc_header, None);
+
+        // The header is still checked
+        let mut bad_crc = data[..128].to_vec();
+        bad_crc[124] ^= 0xFF;
+        assert_eq!(
+            probe(&bad_crc).err(),
+            Some(ParseError::CrcMismatch {
+                expected: 0x7c9d,
+                actual: 0x839d
+            })
+        );
+        assert_eq!(probe(&data[..127]).err(), Some(ParseError::BadVersion));
     }
 
     #[test]
-    fn test_no_resource_fork() {
-        let data = read_fixture("tests/No resource fork.txt.bin");
+    fn test_extended_finder_flags() {
+        let mut data = read_fixture("tests/Text File.bin");
+        // The script of the file name is set, rather than extended flags
+        assert_eq!(data[106], 0x80);
+        assert!(parse(&data).unwrap().extended_finder_flags().is_empty());
+
+        data[106] = 0x01; // hasCustomBadge
+        data[107] = 0x84; // objectIsBusy, hasRoutingInfo
+        update_crc(&mut data);
+        let flags = parse(&data).unwrap().extended_finder_flags();
+        assert_eq!(flags.bits(), 0x0184);
+        assert!(flags.contains(ExtendedFinderFlags::HAS_ROUTING_INFO));
+        assert!(flags.contains(ExtendedFinderFlags::HAS_CUSTOM_BADGE));
+
+        // Not present in MacBinary II
+        data[102..106].copy_from_slice(&[0; 4]);
+        update_crc(&mut data);
         let file = parse(&data).unwrap();
+        assert_eq!(file.version(), Version::II);
+        assert!(file.extended_finder_flags().is_empty());
+    }
 
-        assert_eq!(file.version(), Version::III);
-        assert!(file.resource_fork().unwrap().is_none());
+    #[test]
+    fn test_encoder_fingerprint() {
+        use crate::encoder::{Encoder, EncoderRule};
+
+        let rules = [
+            EncoderRule {
+                reserved_zeroed: Some(false),
+                ..EncoderRule::new(Encoder::Other("reserved"))
+            },
+            EncoderRule {
+                version: Some(130),
+                rsrc_padded: Some(false),
+                ..EncoderRule::new(Encoder::Other("unpadded"))
+            },
+        ];
+
+        // The fixture has the mBIN signature but a MacBinary II version number
+        let mut data = read_fixture("tests/Text File.bin");
+        let hint = parse(&data).unwrap().encoder_fingerprint();
+        assert_eq!(hint.observations.version, 129);
+        assert_eq!(hint.observations.min_version, 129);
+        assert!(hint.observations.has_signature);
+        assert!(hint.observations.reserved_zeroed);
+        assert!(hint.observations.rsrc_padded);
+        assert!(hint.observations.crc_matches);
+        assert_eq!(hint.encoder, Encoder::Unknown);
+        let hint = parse(&data).unwrap().encoder_fingerprint_with(&rules);
+        assert_eq!(hint.encoder, Encoder::Unknown);
+
+        // MacBinary III without the final padding
+        data[122] = 130;
+        update_crc(&mut data);
+        let unpadded = &data[..256 + 1454];
+        let hint = parse(unpadded).unwrap().encoder_fingerprint_with(&rules);
+        assert!(!hint.observations.rsrc_padded);
+        assert_eq!(hint.encoder, Encoder::Other("unpadded"));
+        // There are no default rules
+        let hint = parse(unpadded).unwrap().encoder_fingerprint();
+        assert_eq!(hint.encoder, Encoder::Unknown);
+
+        // MacBinary II with non-zero unused bytes
+        let mut data = read_fixture("tests/Text File II.bin");
+        let hint = parse(&data).unwrap().encoder_fingerprint();
+        assert!(!hint.observations.has_signature);
+        data[110] = 0xAA;
+        update_crc(&mut data);
+        let hint = parse(&data).unwrap().encoder_fingerprint_with(&rules);
+        assert!(!hint.observations.reserved_zeroed);
+        assert_eq!(hint.encoder, Encoder::Other("reserved"));
+
+        // MacBinary I has no version bytes
+        let data = read_fixture("tests/Text File I.Bin");
+        let hint = parse(&data).unwrap().encoder_fingerprint_with(&rules);
+        assert_eq!(hint.encoder, Encoder::Unknown);
     }
 
     #[test]
-    fn test_dates() {
-        let data = read_fixture("tests/Date Test.bin");
+    fn test_protected() {
+        let mut data = read_fixture("tests/Text File.bin");
         let file = parse(&data).unwrap();
+        assert!(!file.protected());
+        assert_eq!(file.protected_byte(), 0);
 
-        assert_eq!(file.version(), Version::III);
-        assert_eq!(file.filename(), "Date Test");
-        assert_eq!(file.file_type(), FourCC(u32::from_be_bytes(*b"TEXT")));
-        assert_eq!(file.file_creator(), FourCC(u32::from_be_bytes(*b"MPS "))); // MPW Shell
-        assert_eq!(file.data_fork(), b"Sunday, 26 March 2023 10:00:52 AM\r");
-        assert_eq!(file.created(), 1679824852);
-        assert_eq!(file.modified(), 1679824852);
+        data[81] = 0x03;
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert!(file.protected());
+        assert_eq!(file.protected_byte(), 3);
+        assert!(file.finder_info().protected);
+
+        // Only the low-order bit is the protected flag
+        data[81] = 0x02;
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        assert!(!file.protected());
+        assert_eq!(file.protected_byte(), 2);
+        assert!(!file.finder_info().protected);
+    }
+
+    #[test]
+    fn test_finder_position() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = parse(&data).unwrap();
+        assert_eq!(
+            file.finder_position(),
+            FinderPosition {
+                v: 156,
+                h: 960,
+                folder_id: 0
+            }
+        );
+
+        // Never positioned, in the trash
+        let mut data = data;
+        data[75..81].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFD]);
+        update_crc(&mut data);
+        let file = parse(&data).unwrap();
+        let position = file.finder_position();
+        assert_eq!(position.v, -1);
+        assert_eq!(position.h, -1);
+        assert_eq!(position.folder_id, -3);
+        assert!(!position.is_positioned());
+        assert_eq!(position.folder_id, file.finder_info().folder_id);
+    }
+
+    #[test]
+    fn test_label() {
+        let labels = [
+            Label::None,
+            Label::Gray,
+            Label::Green,
+            Label::Purple,
+            Label::Blue,
+            Label::Yellow,
+            Label::Red,
+            Label::Orange,
+        ];
+        let mut data = macbinary_with_forks(b"", b"");
+        for (bits, label) in (0..).zip(labels) {
+            data[101] = 0x01 | bits << 1; // isOnDesk is ignored
+            update_crc(&mut data);
+            let file = parse(&data).unwrap();
+            assert_eq!(file.label(), label);
+            assert_eq!(file.label().as_bits(), bits);
+        }
+
+        let data = read_fixture("tests/Text File.bin");
+        assert_eq!(parse(&data).unwrap().label(), Label::None);
+    }
+
+    #[test]
+    fn test_finder_flag_predicates_macbinary1() {
+        let mut data = read_fixture("tests/Text File I.Bin");
+        data[73] = 0x60; // isInvisible, hasBundle
+        let file = parse(&data).unwrap();
+        assert_eq!(file.version(), Version::I);
+        assert!(file.is_invisible());
+        assert!(file.has_bundle());
+        assert!(!file.is_alias());
+        assert_eq!(file.finder_flags().bits(), 0x6000);
+        assert_eq!(
+            file.validate()
+                .with_code(validate::code::LEGACY_FINDER_FLAGS)
+                .count(),
+            0
+        );
+
+        // isAlias and isStationery didn't exist yet
+        data[73] = 0x88;
+        let file = parse(&data).unwrap();
+        assert!(!file.is_alias());
+        assert!(!file.is_stationery());
+        assert_eq!(file.finder_flags().bits(), 0);
+        assert_eq!(file.finder_flags().legacy_raw(), 0x8800);
+        let issues = file.validate();
+        let issue = issues
+            .with_code(validate::code::LEGACY_FINDER_FLAGS)
+            .next()
+            .unwrap();
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(issue.offset, Some(73));
+
+        // The low byte isn't stored in MacBinary I
+        let mut finder_info = file.finder_info();
+        finder_info.finder_flags = 0x41C1;
+        let info = HeaderInfo {
+            version: Version::I,
+            filename: file.filename_bytes(),
+            finder_info,
+        };
+        let file = MacBinary::from_parts(info, b"", b"").unwrap();
+        assert_eq!(file.finder_flags().bits(), 0x4100);
+        assert!(!file.finder_flags().contains(FinderFlags::HAS_NO_INITS));
+        assert_eq!(
+            file.validate()
+                .with_code(validate::code::LEGACY_FINDER_FLAGS)
+                .next()
+                .unwrap()
+                .offset,
+            Some(101)
+        );
     }
 }
@@ -0,0 +1,205 @@
+//! Classic Mac metadata of files on macOS.
+//!
+//! macOS still keeps resource forks and Finder info for files on HFS+ and APFS volumes, exposed
+//! as the `com.apple.ResourceFork` and `com.apple.FinderInfo` extended attributes. This module
+//! reads them into the same types used for MacBinary files. It requires the `macos` feature and
+//! is only available when building for macOS.
+
+use std::ffi::CString;
+use std::fs;
+use std::io;
+use std::os::raw::{c_char, c_int, c_void};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{unix_to_mactime, FinderInfo, OwnedResourceFork, ResourceFork};
+
+/// Name of the extended attribute holding the Finder info
+const FINDER_INFO_XATTR: &[u8] = b"com.apple.FinderInfo\0";
+
+/// Error returned by `getxattr` when the attribute does not exist
+const ENOATTR: i32 = 93;
+
+extern "C" {
+    fn getxattr(
+        path: *const c_char,
+        name: *const c_char,
+        value: *mut c_void,
+        size: usize,
+        position: u32,
+        options: c_int,
+    ) -> isize;
+}
+
+impl ResourceFork<'_> {
+    /// Read the resource fork of a file on disk.
+    ///
+    /// The fork is read through the file's `..namedfork/rsrc` path. Returns `Ok(None)` if the
+    /// file has no resource fork, and an error with kind [io::ErrorKind::InvalidData] if it can't
+    /// be parsed.
+    pub fn from_path(path: &Path) -> io::Result<Option<OwnedResourceFork>> {
+        // Check the file itself exists, so that a missing fork can be told apart from a missing file
+        fs::metadata(path)?;
+        let data = match fs::read(path.join("..namedfork/rsrc")) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        if data.is_empty() {
+            return Ok(None);
+        }
+        OwnedResourceFork::new(data)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// Read the Finder info of a file on disk.
+///
+/// The type, creator, flags, and icon position come from the `com.apple.FinderInfo` extended
+/// attribute, and the dates from the file's metadata. Returns `Ok(None)` if the file has no
+/// Finder info.
+pub fn read_finder_info(path: &Path) -> io::Result<Option<FinderInfo>> {
+    let metadata = fs::metadata(path)?;
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    let mut bytes = [0u8; 32];
+    // SAFETY: both strings are nul terminated and the buffer is valid for `bytes.len()` bytes
+    let len = unsafe {
+        getxattr(
+            c_path.as_ptr(),
+            FINDER_INFO_XATTR.as_ptr().cast(),
+            bytes.as_mut_ptr().cast(),
+            bytes.len(),
+            0,
+            0,
+        )
+    };
+    if len < 0 {
+        let err = io::Error::last_os_error();
+        return match err.raw_os_error() {
+            Some(ENOATTR) => Ok(None),
+            _ => Err(err),
+        };
+    }
+    if len as usize != bytes.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Finder info is not 32 bytes",
+        ));
+    }
+
+    let mut info = FinderInfo::from_finder_info_bytes(&bytes);
+    info.created = metadata.created().ok().and_then(mac_timestamp).unwrap_or(0);
+    info.modified = metadata
+        .modified()
+        .ok()
+        .and_then(mac_timestamp)
+        .unwrap_or(0);
+    Ok(Some(info))
+}
+
+/// Convert a system time to a Mac OS timestamp
+///
+/// Returns `None` if the time is outside the range of Mac OS timestamps.
+fn mac_timestamp(time: SystemTime) -> Option<u32> {
+    let secs = match time.duration_since(UNIX_EPOCH) {
+        Ok(duration) => i64::try_from(duration.as_secs()).ok()?,
+        Err(err) => i64::try_from(err.duration().as_secs())
+            .ok()?
+            .checked_neg()?,
+    };
+    unix_to_mactime(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::read_fixture;
+    use crate::FourCC;
+    use std::path::PathBuf;
+
+    extern "C" {
+        fn setxattr(
+            path: *const c_char,
+            name: *const c_char,
+            value: *const c_void,
+            size: usize,
+            position: u32,
+            options: c_int,
+        ) -> c_int;
+    }
+
+    fn temp_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("macbinary-{}-{name}", std::process::id()));
+        fs::write(&path, b"data fork").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_from_path() {
+        let path = temp_file("rsrc");
+        assert!(ResourceFork::from_path(&path).unwrap().is_none());
+
+        let data = read_fixture("tests/Text File.rsrc");
+        fs::write(path.join("..namedfork/rsrc"), &data).unwrap();
+        let owned = ResourceFork::from_path(&path).unwrap().unwrap();
+        assert_eq!(owned.data(), &data[..]);
+        assert_eq!(owned.as_resource_fork().resource_count(), 2);
+
+        fs::write(path.join("..namedfork/rsrc"), b"not a resource fork").unwrap();
+        let err = ResourceFork::from_path(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        fs::remove_file(&path).unwrap();
+
+        assert!(ResourceFork::from_path(&path).is_err());
+    }
+
+    #[test]
+    fn test_mac_timestamp() {
+        use std::time::Duration;
+
+        assert_eq!(mac_timestamp(UNIX_EPOCH), Some(2082844800));
+        let before = UNIX_EPOCH - Duration::from_secs(2082844800);
+        assert_eq!(mac_timestamp(before), Some(0));
+        assert_eq!(mac_timestamp(before - Duration::from_secs(1)), None);
+        // Mac OS timestamps end on 6 February 2040
+        let after = UNIX_EPOCH + Duration::from_secs(2212122496);
+        assert_eq!(
+            mac_timestamp(after - Duration::from_secs(1)),
+            Some(u32::MAX)
+        );
+        assert_eq!(mac_timestamp(after), None);
+    }
+
+    #[test]
+    fn test_read_finder_info() {
+        let path = temp_file("finder-info");
+        assert_eq!(read_finder_info(&path).unwrap(), None);
+
+        let mut bytes = [0; 32];
+        bytes[0..8].copy_from_slice(b"TEXTR*ch");
+        bytes[10..12].copy_from_slice(&(-20_i16).to_be_bytes());
+        let c_path = CString::new(path.as_os_str().as_bytes()).unwrap();
+        // SAFETY: both strings are nul terminated and the buffer is valid for `bytes.len()` bytes
+        let res = unsafe {
+            setxattr(
+                c_path.as_ptr(),
+                FINDER_INFO_XATTR.as_ptr().cast(),
+                bytes.as_ptr().cast(),
+                bytes.len(),
+                0,
+                0,
+            )
+        };
+        assert_eq!(res, 0);
+
+        let info = read_finder_info(&path).unwrap().unwrap();
+        assert_eq!(info.file_type, FourCC::new(*b"TEXT"));
+        assert_eq!(info.file_creator, FourCC::new(*b"R*ch"));
+        assert_eq!(info.vpos, -20);
+        assert_ne!(info.modified, 0);
+        fs::remove_file(&path).unwrap();
+    }
+}
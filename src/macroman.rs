@@ -1,6 +1,13 @@
+use core::fmt::{self, Write as _};
+#[cfg(not(feature = "no_std"))]
+use std::borrow::Cow;
+
 #[cfg(feature = "no_std")]
 use heapless::String;
 
+#[cfg(not(feature = "no_std"))]
+use crate::error::MacRomanError;
+
 #[cfg(feature = "no_std")]
 pub trait FromMacRoman {
     fn try_from_macroman(data: &[u8]) -> Option<Self>
@@ -137,6 +144,16 @@ pub fn macroman_to_char(macroman: u8) -> Option<char> {
     }
 }
 
+/// Converts a Unicode `char` to its Mac OS Roman character.
+///
+/// Returns `None` if the character is not part of the Mac OS Roman character set.
+pub fn char_to_macroman(ch: char) -> Option<u8> {
+    if ch.is_ascii() {
+        return Some(ch as u8);
+    }
+    (128..=255).find(|&macroman| macroman_to_char(macroman) == Some(ch))
+}
+
 #[cfg(not(feature = "no_std"))]
 impl FromMacRoman for String {
     fn from_macroman(data: &[u8]) -> Self {
@@ -146,6 +163,79 @@ impl FromMacRoman for String {
     }
 }
 
+/// Decode MacRoman data, borrowing it when it's plain ASCII.
+///
+/// ASCII is the same in MacRoman and UTF-8 so it doesn't need converting.
+#[cfg(not(feature = "no_std"))]
+pub fn decode_macroman(data: &[u8]) -> Cow<'_, str> {
+    match core::str::from_utf8(data) {
+        Ok(s) if data.is_ascii() => Cow::Borrowed(s),
+        _ => Cow::Owned(String::from_macroman(data)),
+    }
+}
+
+/// Formats MacRoman text as a quoted string for `Debug` output, without allocating.
+///
+/// Bytes that aren't part of the character set are shown as U+FFFD REPLACEMENT CHARACTER.
+pub(crate) struct DebugMacRoman<'a>(pub &'a [u8]);
+
+impl fmt::Debug for DebugMacRoman<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_char('"')?;
+        for &byte in self.0 {
+            let c = macroman_to_char(byte).unwrap_or('\u{FFFD}');
+            write!(f, "{}", c.escape_debug())?;
+        }
+        f.write_char('"')
+    }
+}
+
+/// How to handle bytes that aren't part of the MacRoman character set when decoding text.
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DecodePolicy {
+    /// Replace the byte with the supplied character
+    Replace(char),
+    /// Leave the byte out
+    Skip,
+    /// Fail with a [MacRomanError]
+    Error,
+}
+
+#[cfg(not(feature = "no_std"))]
+impl Default for DecodePolicy {
+    /// Replace with U+FFFD REPLACEMENT CHARACTER, as the methods without a policy do.
+    fn default() -> Self {
+        DecodePolicy::Replace('\u{FFFD}')
+    }
+}
+
+/// Decode MacRoman data, handling undecodable bytes according to `policy`.
+///
+/// The data is borrowed rather than copied when it's plain ASCII, which is the same in MacRoman
+/// and UTF-8.
+#[cfg(not(feature = "no_std"))]
+pub fn decode_with_policy(
+    data: &[u8],
+    policy: DecodePolicy,
+) -> Result<Cow<'_, str>, MacRomanError> {
+    if let Ok(s) = core::str::from_utf8(data) {
+        if data.is_ascii() {
+            return Ok(Cow::Borrowed(s));
+        }
+    }
+    let mut decoded = String::with_capacity(data.len());
+    for (position, &byte) in data.iter().enumerate() {
+        match (macroman_to_char(byte), policy) {
+            (Some(c), _) => decoded.push(c),
+            (None, DecodePolicy::Replace(replacement)) => decoded.push(replacement),
+            (None, DecodePolicy::Skip) => {}
+            (None, DecodePolicy::Error) => return Err(MacRomanError { position, byte }),
+        }
+    }
+    Ok(Cow::Owned(decoded))
+}
+
 #[cfg(feature = "no_std")]
 impl<const N: usize> FromMacRoman for String<N> {
     fn try_from_macroman(bytes: &[u8]) -> Option<String<N>> {
@@ -157,3 +247,64 @@ impl<const N: usize> FromMacRoman for String<N> {
         Some(name)
     }
 }
+
+#[cfg(all(test, not(feature = "no_std")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_with_policy() {
+        // 0xAD is not in the character set, 0x8A is ä
+        let data = b"Caf\x8A\xAD!";
+        assert_eq!(
+            decode_with_policy(data, DecodePolicy::default()).unwrap(),
+            "Caf\u{e4}\u{FFFD}!"
+        );
+        assert_eq!(
+            decode_with_policy(data, DecodePolicy::default()).unwrap(),
+            String::from_macroman(data)
+        );
+        assert_eq!(
+            decode_with_policy(data, DecodePolicy::Replace('_')).unwrap(),
+            "Caf\u{e4}_!"
+        );
+        assert_eq!(
+            decode_with_policy(data, DecodePolicy::Skip).unwrap(),
+            "Caf\u{e4}!"
+        );
+        assert_eq!(
+            decode_with_policy(data, DecodePolicy::Error),
+            Err(MacRomanError {
+                position: 4,
+                byte: 0xAD
+            })
+        );
+
+        assert!(matches!(
+            decode_with_policy(b"plain", DecodePolicy::Error),
+            Ok(Cow::Borrowed("plain"))
+        ));
+    }
+
+    #[test]
+    fn test_char_to_macroman() {
+        for macroman in 0..=255 {
+            if let Some(ch) = macroman_to_char(macroman) {
+                assert_eq!(char_to_macroman(ch).and_then(macroman_to_char), Some(ch));
+            }
+        }
+        assert_eq!(char_to_macroman('\u{e9}'), Some(0x8E));
+        // 0xCA is decoded as a space
+        assert_eq!(char_to_macroman(' '), Some(0x20));
+        assert_eq!(char_to_macroman('\u{FFFD}'), None);
+    }
+
+    #[test]
+    fn test_debug_macroman() {
+        assert_eq!(
+            format!("{:?}", DebugMacRoman(b"\"Caf\x8E\"\xAD\r")),
+            // The same as the Debug output of the decoded str
+            format!("{:?}", "\"Caf\u{e9}\"\u{FFFD}\r")
+        );
+    }
+}
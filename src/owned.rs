@@ -0,0 +1,225 @@
+//! Parsed files that own their data.
+//!
+//! [MacBinary] and [ResourceFork] borrow the data they were parsed from, which avoids copying but
+//! ties them to the lifetime of the input. The owned versions here hold a copy of the data so
+//! they can be kept, e.g. in a cache, after the input has been dropped. The borrowed versions are
+//! obtained from them with [OwnedMacBinary::as_macbinary] and
+//! [OwnedResourceFork::as_resource_fork], which re-read the headers but don't copy anything.
+
+use core::fmt;
+
+use crate::binary::read::ReadScope;
+use crate::{FinderInfo, HeaderInfo, MacBinary, ParseError, ParseOptions, ResourceFork, Version};
+
+/// A parsed MacBinary file that owns its data.
+///
+/// Created with [MacBinary::into_owned] or [OwnedMacBinary::parse].
+#[derive(Clone)]
+pub struct OwnedMacBinary {
+    source: Source,
+}
+
+#[derive(Clone)]
+enum Source {
+    /// A file parsed from its encoded data
+    Parsed {
+        data: Vec<u8>,
+        version: Version,
+        options: ParseOptions,
+    },
+    /// A file constructed with [MacBinary::from_parts]
+    Parts {
+        version: Version,
+        filename: Vec<u8>,
+        finder_info: FinderInfo,
+        data_fork: Vec<u8>,
+        rsrc_fork: Vec<u8>,
+    },
+}
+
+/// A parsed resource fork that owns its data.
+///
+/// Created with [ResourceFork::to_owned] or [OwnedResourceFork::new].
+#[derive(Clone)]
+pub struct OwnedResourceFork {
+    data: Vec<u8>,
+}
+
+impl OwnedMacBinary {
+    /// Parse a MacBinary encoded file, taking ownership of the data.
+    pub fn parse(data: Vec<u8>) -> Result<OwnedMacBinary, ParseError> {
+        Self::parse_with_options(data, ParseOptions::default())
+    }
+
+    /// Parse a MacBinary encoded file using the supplied options, taking ownership of the data.
+    pub fn parse_with_options(
+        data: Vec<u8>,
+        options: ParseOptions,
+    ) -> Result<OwnedMacBinary, ParseError> {
+        let version = crate::parse_with_options(&data, options)?.version();
+        Ok(OwnedMacBinary {
+            source: Source::Parsed {
+                data,
+                version,
+                options,
+            },
+        })
+    }
+
+    pub(crate) fn from_macbinary(file: &MacBinary<'_>) -> OwnedMacBinary {
+        let source = if file.data.is_empty() {
+            Source::Parts {
+                version: file.version,
+                filename: file.filename_bytes().to_vec(),
+                finder_info: file.finder_info(),
+                data_fork: file.data_fork.to_vec(),
+                rsrc_fork: file.rsrc_fork.to_vec(),
+            }
+        } else {
+            Source::Parsed {
+                data: file.data.to_vec(),
+                version: file.version,
+                options: file.options,
+            }
+        };
+        OwnedMacBinary { source }
+    }
+
+    /// Borrow the file as a [MacBinary].
+    pub fn as_macbinary(&self) -> MacBinary<'_> {
+        let file = match &self.source {
+            Source::Parsed {
+                data,
+                version,
+                options,
+            } => ReadScope::new(data).read_dep::<MacBinary<'_>>((*version, *options)),
+            Source::Parts {
+                version,
+                filename,
+                finder_info,
+                data_fork,
+                rsrc_fork,
+            } => MacBinary::from_parts(
+                HeaderInfo {
+                    version: *version,
+                    filename,
+                    finder_info: *finder_info,
+                },
+                data_fork,
+                rsrc_fork,
+            ),
+        };
+        // The data is unchanged since it was parsed successfully when this value was created
+        file.expect("owned MacBinary data is valid")
+    }
+}
+
+impl OwnedResourceFork {
+    /// Parse a resource fork, taking ownership of the data.
+    pub fn new(data: Vec<u8>) -> Result<OwnedResourceFork, ParseError> {
+        ResourceFork::new(&data)?;
+        Ok(OwnedResourceFork { data })
+    }
+
+    /// Borrow the resource fork as a [ResourceFork].
+    pub fn as_resource_fork(&self) -> ResourceFork<'_> {
+        // The data is unchanged since it was parsed successfully when this value was created
+        ResourceFork::new(&self.data).expect("owned resource fork data is valid")
+    }
+
+    /// The raw data of the resource fork.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl fmt::Debug for OwnedMacBinary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_macbinary(), f)
+    }
+}
+
+impl fmt::Debug for OwnedResourceFork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.as_resource_fork(), f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::read_fixture;
+    use crate::FourCC;
+
+    #[test]
+    fn test_into_owned() {
+        let data = read_fixture("tests/Text File.bin");
+        let owned = crate::parse(&data).unwrap().into_owned();
+        drop(data);
+
+        let file = owned.as_macbinary();
+        assert_eq!(file.filename(), "Text File");
+        assert_eq!(file.data_fork().len(), 21);
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let bbst = rsrc.get_resource(FourCC::new(*b"BBST"), 128).unwrap();
+        assert_eq!(bbst.data().len(), 1048);
+    }
+
+    #[test]
+    fn test_into_owned_with_options() {
+        let data = read_fixture("tests/Text File.bin");
+        // Truncate the resource fork, which can only be read with allow_truncated
+        let truncated = data[..1000].to_vec();
+        let options = ParseOptions::new().allow_truncated(true);
+        assert!(OwnedMacBinary::parse(truncated.clone()).is_err());
+        let owned = OwnedMacBinary::parse_with_options(truncated, options).unwrap();
+        assert!(!owned.as_macbinary().resource_fork_complete());
+
+        let file = crate::parse_with_options(&data[..1000], options).unwrap();
+        let owned = file.into_owned();
+        assert!(!owned.as_macbinary().resource_fork_complete());
+    }
+
+    #[test]
+    fn test_from_parts_into_owned() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let info = HeaderInfo {
+            version: Version::II,
+            filename: b"Copy",
+            finder_info: file.finder_info(),
+        };
+        let data_fork = b"data".to_vec();
+        let owned = MacBinary::from_parts(info, &data_fork, file.resource_fork_raw())
+            .unwrap()
+            .into_owned();
+        drop(data_fork);
+        drop(data);
+
+        let file = owned.as_macbinary();
+        assert_eq!(file.version(), Version::II);
+        assert_eq!(file.filename(), "Copy");
+        assert_eq!(file.data_fork(), b"data");
+        assert_eq!(file.resource_fork_len(), 1454);
+    }
+
+    #[test]
+    fn test_resource_fork_to_owned() {
+        let data = read_fixture("tests/Text File.bin");
+        let owned = {
+            let file = crate::parse(&data).unwrap();
+            file.resource_fork().unwrap().unwrap().to_owned()
+        };
+        drop(data);
+
+        let rsrc = owned.as_resource_fork();
+        assert_eq!(rsrc.resource_count(), 2);
+        let mpsr = rsrc.get_resource(FourCC::new(*b"MPSR"), 1005).unwrap();
+        assert_eq!(mpsr.data().len(), 72);
+        assert_eq!(owned.data().len(), 1454);
+
+        assert!(OwnedResourceFork::new(vec![0; 8]).is_err());
+        let owned = OwnedResourceFork::new(owned.data().to_vec()).unwrap();
+        assert_eq!(owned.as_resource_fork().resource_count(), 2);
+    }
+}
@@ -0,0 +1,21 @@
+//! Convenient re-exports of the commonly used types and functions.
+//!
+//! ```
+//! use macbinary::prelude::*;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let data = std::fs::read("tests/Text File.bin")?;
+//! if detect(&data) == Some(Version::III) {
+//!     let file: MacBinary = parse(&data)?;
+//!     let rsrc: Option<ResourceFork> = file.resource_fork()?;
+//!     let rsrc = rsrc.expect("missing resource fork");
+//!     let bbst: Option<Resource> = rsrc.get_resource(FourCC::new(*b"BBST"), 128);
+//!     assert_eq!(bbst.map(|resource| resource.data().len()), Some(1048));
+//! }
+//! # let _: Option<ParseError> = None;
+//! # Ok(())
+//! # }
+//! ```
+
+pub use crate::resource::{Resource, ResourceFork};
+pub use crate::{detect, parse, FourCC, MacBinary, ParseError, Version};
@@ -5,30 +5,95 @@
 //! [Inside Macintosh: More Macintosh Toolbox](https://archive.org/details/inside-macintosh-1992-1994/1993-more_macintosh_toolbox/)
 //! Resource File Format 1-121 (pp. 151)
 
+mod attributes;
+mod compression;
+mod derez;
+#[cfg(not(feature = "no_std"))]
+mod index;
+pub mod types;
+
 // Re: compressed resources: <http://preserve.mactech.com/articles/mactech/Vol.09/09.01/ResCompression/index.html>
 
+use core::fmt;
+use core::ops::Range;
+#[cfg(not(feature = "no_std"))]
+use std::borrow::Cow;
+#[cfg(not(feature = "no_std"))]
+use std::collections::HashSet;
+
 #[cfg(feature = "no_std")]
 use heapless::String;
 
 use crate::binary::read::{
-    CheckIndex, ReadArray, ReadBinary, ReadBinaryDep, ReadCtxt, ReadFrom, ReadScope,
+    CheckIndex, ReadArray, ReadArrayIter, ReadBinary, ReadBinaryDep, ReadCtxt, ReadFrom, ReadScope,
 };
 use crate::binary::{I16Be, NumFrom, U16Be, U24Be, U32Be, U8};
+#[cfg(not(feature = "no_std"))]
+use crate::error::MacRomanError;
 use crate::error::ParseError;
-use crate::macroman::FromMacRoman;
+#[cfg(not(feature = "no_std"))]
+use crate::macroman::{decode_macroman, decode_with_policy, DecodePolicy};
+use crate::macroman::{DebugMacRoman, FromMacRoman};
+#[cfg(not(feature = "no_std"))]
+use crate::validate::{code, Issue, Issues, Severity};
 use crate::FourCC;
+#[cfg(not(feature = "no_std"))]
+use crate::OwnedResourceFork;
+use types::{Str, StringList, TypedResource};
+
+pub use attributes::{MapAttributes, ResourceAttributes};
+pub use compression::CompressionInfo;
+pub use derez::DeRez;
+#[cfg(not(feature = "no_std"))]
+pub use index::ResourceIndex;
+
+/// The 16-byte header at the start of a resource fork.
+///
+/// Offsets are from the start of the resource fork.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ResourceForkHeader {
+    /// Offset of the resource data
+    pub data_offset: u32,
+    /// Offset of the resource map
+    pub map_offset: u32,
+    /// Length of the resource data
+    pub data_len: u32,
+    /// Length of the resource map
+    pub map_len: u32,
+}
 
 /// A parsed resource fork.
 pub struct ResourceFork<'a> {
+    /// The complete resource fork
+    #[cfg_attr(feature = "no_std", allow(unused))]
+    data: &'a [u8],
+    /// Offset of the resource data from the start of the fork
+    data_offset: u32,
+    /// Offset of the resource map from the start of the fork
+    #[cfg_attr(feature = "no_std", allow(unused))]
+    map_offset: u32,
+    /// Length of the resource map
+    #[cfg_attr(feature = "no_std", allow(unused))]
+    map_len: u32,
+    reserved_header: &'a [u8],
     rsrc_data: &'a [u8],
     map: ResourceMap<'a>,
 }
 
 #[allow(unused)]
 struct ResourceMap<'a> {
+    reserved: &'a [u8],
     attributes: u16,
+    /// Offset of the type list from the start of the map
+    #[cfg_attr(feature = "no_std", allow(unused))]
+    type_list_offset: u16,
     type_list: TypeList<'a>,
+    /// Offset of the name list from the start of the map
+    #[cfg_attr(feature = "no_std", allow(unused))]
+    name_list_offset: u16,
     name_list_scope: ReadScope<'a>,
+    /// Whether the name list offset lies within the resource map
+    name_list_valid: bool,
 }
 
 struct TypeList<'a> {
@@ -37,7 +102,12 @@ struct TypeList<'a> {
 }
 
 /// An entry in the resource type list of a resource fork.
-#[derive(Copy, Clone)]
+///
+/// Items are obtained from [ResourceFork::resource_types]. [Self::resource_type] and
+/// [Self::count] are the stable public surface. An item also records where its resources are in
+/// the resource map, so it should only be passed back to the fork it came from, and items for the
+/// same type from different forks may compare unequal.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 pub struct TypeListItem {
     /// Resource type
     rsrc_type: FourCC,
@@ -60,12 +130,72 @@ struct ReferenceListItem {
     data_offset: u32, // actually only 3 bytes
 }
 
+/// Statistics about the contents of a resource fork.
+///
+/// Created with [ResourceFork::stats].
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ForkStats {
+    /// Statistics for each resource type, in type list order
+    pub types: Vec<TypeStats>,
+    /// Total number of resources
+    pub resource_count: usize,
+    /// Number of resources that have a name
+    pub named_count: usize,
+    /// Number of resources that are compressed, see [Resource::is_compressed]
+    pub compressed_count: usize,
+    /// Total length of the data of all resources, excluding their length words
+    pub total_len: u64,
+    /// The resource with the most data, `None` if there are no readable resources
+    pub largest: Option<ResourceSize>,
+    /// Length of the resource map, which holds the type, reference, and name lists
+    pub map_len: u32,
+    /// Bytes of the resource data area not used by any resource or its length word
+    ///
+    /// Resources that share data are counted more than once, so this may underestimate the
+    /// unused space in unusual forks.
+    pub gap_len: u64,
+}
+
+/// Statistics for one resource type, part of [ForkStats].
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeStats {
+    /// Resource type
+    pub rsrc_type: FourCC,
+    /// Number of resources of this type
+    pub count: usize,
+    /// Total length of the data of the resources of this type
+    pub total_len: u64,
+}
+
+/// The size of a resource, identified by type and ID.
+#[cfg(not(feature = "no_std"))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceSize {
+    /// Resource type
+    pub rsrc_type: FourCC,
+    /// Resource ID
+    pub id: i16,
+    /// Length of the resource data
+    pub len: u32,
+}
+
+/// Number of bytes of resource data shown in the `Debug` output of a [Resource]
+const DEBUG_DATA_LEN: usize = 16;
+
 /// An individual resource from a resource fork.
 #[allow(unused)]
 pub struct Resource<'a> {
+    rsrc_type: FourCC,
     id: i16,
     name: Option<&'a [u8]>,
     attributes: u8,
+    /// Offset of the data from the start of the resource fork, after its length
+    data_offset: usize,
     data: &'a [u8],
 }
 
@@ -80,22 +210,94 @@ pub struct ResourceTypes<'a, 'rsrc> {
 /// An iterator over the resources of a given type.
 ///
 /// Typically created with [ResourceFork::resources].
+///
+/// This iterator is lossy: it ends at the first resource that can't be read, so a corrupt entry
+/// looks the same as the end of the list. Use [TryResources] to find out about such entries.
 pub struct Resources<'a, 'rsrc> {
     fork: &'a ResourceFork<'rsrc>,
     item: TypeListItem,
     rsrc_index: u16,
 }
 
+/// An iterator over the resources of a given type that reports resources that can't be read.
+///
+/// Created with [ResourceFork::try_resources].
+pub struct TryResources<'a, 'rsrc> {
+    fork: &'a ResourceFork<'rsrc>,
+    item: TypeListItem,
+    rsrc_index: u16,
+    /// Set when the reference list couldn't be read, after the error has been returned
+    failed: bool,
+}
+
+/// An iterator over the resources with a given type and id.
+///
+/// Created with [ResourceFork::get_resources].
+pub struct ResourcesWithId<'a, 'rsrc> {
+    fork: &'a ResourceFork<'rsrc>,
+    rsrc_type: FourCC,
+    rsrc_id: i16,
+    /// The remaining references of the type, `None` if the type isn't present
+    references: Option<ReadArrayIter<'rsrc, ReferenceListItem>>,
+}
+
+/// An iterator over the resources of a given type in ascending order of id.
+///
+/// Created with [ResourceFork::resources_sorted].
+#[cfg(not(feature = "no_std"))]
+pub struct SortedResources<'a, 'rsrc> {
+    fork: &'a ResourceFork<'rsrc>,
+    rsrc_type: FourCC,
+    references: std::vec::IntoIter<ReferenceListItem>,
+}
+
+/// An iterator over the names of the resources in a resource fork.
+///
+/// Created with [ResourceFork::resource_names].
+#[cfg(not(feature = "no_std"))]
+pub struct ResourceNames<'a, 'rsrc> {
+    fork: &'a ResourceFork<'rsrc>,
+    types: ResourceTypes<'a, 'rsrc>,
+    /// The type and remaining references of the type currently being iterated
+    references: Option<(FourCC, ReadArrayIter<'rsrc, ReferenceListItem>)>,
+}
+
+/// An iterator over the resources in a resource fork that match a predicate.
+///
+/// Created with [ResourceFork::find_resources].
+pub struct FindResources<'a, 'rsrc, F> {
+    fork: &'a ResourceFork<'rsrc>,
+    types: ResourceTypes<'a, 'rsrc>,
+    /// The type and remaining references of the type currently being iterated
+    references: Option<(FourCC, ReadArrayIter<'rsrc, ReferenceListItem>)>,
+    predicate: F,
+}
+
+/// An iterator over all the resources in a resource fork that reports resources that can't be
+/// read.
+///
+/// Created with [ResourceFork::try_iter].
+pub struct TryIter<'a, 'rsrc> {
+    types: ResourceTypes<'a, 'rsrc>,
+    resources: Option<TryResources<'a, 'rsrc>>,
+}
+
 impl<'a> ResourceFork<'a> {
     // FIXME: Make this a ReadBinary impl
     /// Parse resource fork data
     pub fn new(data: &[u8]) -> Result<ResourceFork<'_>, ParseError> {
         let scope = ReadScope::new(data);
         let mut ctxt = scope.ctxt();
-        let data_offset = ctxt.read_u32be()?;
-        let map_offset = ctxt.read_u32be()?;
-        let data_len = ctxt.read_u32be()?;
-        let map_len = ctxt.read_u32be()?;
+        let ResourceForkHeader {
+            data_offset,
+            map_offset,
+            data_len,
+            map_len,
+        } = ctxt.read::<ResourceForkHeader>()?;
+
+        // Lengths that are larger than the whole fork are corrupt
+        ctxt.check(usize::num_from(data_len) <= data.len())?;
+        ctxt.check(usize::num_from(map_len) <= data.len())?;
 
         let rsrc_data =
             scope.offset_length(usize::num_from(data_offset), usize::num_from(data_len))?;
@@ -103,7 +305,15 @@ impl<'a> ResourceFork<'a> {
             scope.offset_length(usize::num_from(map_offset), usize::num_from(map_len))?;
         let rsrc_map = map_data.read::<ResourceMap>()?;
 
+        // The 16-byte header is followed by 112 bytes reserved for system use
+        let reserved_header = ctxt.read_slice(112.min(data.len() - 16))?;
+
         Ok(ResourceFork {
+            data,
+            data_offset,
+            map_offset,
+            map_len,
+            reserved_header,
             rsrc_data: rsrc_data.data(),
             map: rsrc_map,
         })
@@ -120,43 +330,723 @@ impl<'a> ResourceFork<'a> {
     /// Create an iterator over the resources of the supplied type in the resource fork.
     ///
     /// [`TypeListItem`] instance is obtained through [`Self::resource_types`].
-    pub fn resources<'b>(&'b self, item: TypeListItem) -> Resources<'_, 'a> {
+    pub fn resources(&self, item: TypeListItem) -> Resources<'_, 'a> {
         Resources {
             fork: self,
             item,
             rsrc_index: 0,
         }
     }
+
+    /// Create an iterator over the resources of type `rsrc_type` in the resource fork.
+    ///
+    /// This is the same as finding the type with [Self::resource_types] and passing it to
+    /// [Self::resources]. The iterator is empty if there are no resources of the type.
+    pub fn resources_of_type(&self, rsrc_type: FourCC) -> Resources<'_, 'a> {
+        let item = self
+            .map
+            .type_list
+            .list
+            .iter()
+            .find(|item| item.rsrc_type == rsrc_type)
+            // An empty reference list at the start of the type list yields nothing
+            .unwrap_or(TypeListItem {
+                rsrc_type,
+                num_rsrc: 0,
+                reference_list_offset: 0,
+            });
+        self.resources(item)
+    }
+
+    /// Create an iterator over the resources of the supplied type that yields an error for each
+    /// resource that can't be read.
+    ///
+    /// Unlike [Self::resources], iteration continues with the next resource after an error. If
+    /// the reference list for the type can't be read a single error is returned.
+    pub fn try_resources(&self, item: TypeListItem) -> TryResources<'_, 'a> {
+        TryResources {
+            fork: self,
+            item,
+            rsrc_index: 0,
+            failed: false,
+        }
+    }
+
+    /// Create an iterator over the resources of all types in the resource fork, in the same way
+    /// as [Self::try_resources].
+    pub fn try_iter(&self) -> TryIter<'_, 'a> {
+        TryIter {
+            types: self.resource_types(),
+            resources: None,
+        }
+    }
+
+    /// Create an iterator over the resources of the supplied type in ascending order of id.
+    ///
+    /// Resource maps don't have to store resources in order, so [Self::resources] may return
+    /// them in any order. Resources with the same id are returned in map order. Resources that
+    /// can't be read are skipped.
+    #[cfg(not(feature = "no_std"))]
+    pub fn resources_sorted(&self, item: TypeListItem) -> SortedResources<'_, 'a> {
+        let mut references: Vec<_> = item
+            .reference_list(self.map.type_list.scope)
+            .ok()
+            .map(|reference_list| reference_list.list.iter().collect())
+            .unwrap_or_default();
+        references.sort_by_key(|reference| reference.id);
+        SortedResources {
+            fork: self,
+            rsrc_type: item.rsrc_type,
+            references: references.into_iter(),
+        }
+    }
+
+    /// Create an iterator over all the resources with the supplied type and id, in map order.
+    ///
+    /// A resource fork should only have one resource with a given type and id, but corrupt forks
+    /// or those edited with buggy tools can contain duplicates. [Self::get_resource] returns the
+    /// first of these. Resources that can't be read are skipped.
+    pub fn get_resources(&self, rsrc_type: FourCC, rsrc_id: i16) -> ResourcesWithId<'_, 'a> {
+        let references = self
+            .map
+            .type_list
+            .list
+            .iter()
+            .find(|item| item.rsrc_type == rsrc_type)
+            .and_then(|item| item.reference_list(self.map.type_list.scope).ok())
+            .map(|reference_list| reference_list.list.iter());
+        ResourcesWithId {
+            fork: self,
+            rsrc_type,
+            rsrc_id,
+            references,
+        }
+    }
+
+    /// Create an iterator over the type, id, and name of each resource that has a name.
+    ///
+    /// Only the resource map is read, the resource data is not touched, which makes this much
+    /// cheaper than [Self::try_iter] for listing the contents of a fork. Names are decoded from
+    /// MacRoman. Resources without a name, or whose name can't be read, are skipped.
+    #[cfg(not(feature = "no_std"))]
+    pub fn resource_names(&self) -> ResourceNames<'_, 'a> {
+        ResourceNames {
+            fork: self,
+            types: self.resource_types(),
+            references: None,
+        }
+    }
+
+    /// Create an iterator over the resources for which `predicate` returns `true`.
+    ///
+    /// The predicate is called with the type, id, and raw name bytes of each resource in map
+    /// order. Only the resource map is read to call it, the data of a resource is only read
+    /// when it matches. Resources that can't be read are skipped.
+    ///
+    /// ```
+    /// # fn owner_resources(rsrc: &macbinary::ResourceFork<'_>) {
+    /// let owners = rsrc.find_resources(|_, _, name| {
+    ///     matches!(name, Some(name) if name.starts_with(b"Owner"))
+    /// });
+    /// # }
+    /// ```
+    pub fn find_resources<F>(&self, predicate: F) -> FindResources<'_, 'a, F>
+    where
+        F: FnMut(FourCC, i16, Option<&[u8]>) -> bool,
+    {
+        FindResources {
+            fork: self,
+            types: self.resource_types(),
+            references: None,
+            predicate,
+        }
+    }
+
+    /// The data of the resource with the supplied type and id.
+    ///
+    /// Like [Self::try_get_resource] but the data borrows from the fork data rather than `self`.
+    pub(crate) fn try_resource_data(
+        &self,
+        rsrc_type: FourCC,
+        rsrc_id: i16,
+    ) -> Result<Option<&'a [u8]>, ParseError> {
+        match self.find_reference(rsrc_type, rsrc_id)? {
+            Some(item) => self.read_resource_data(item.data_offset).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn read_resource_data(&self, offset: u32) -> Result<&'a [u8], ParseError> {
+        let mut ctxt = ReadScope::new(self.rsrc_data)
+            .offset(usize::num_from(offset))
+            .ctxt();
+        let len = usize::num_from(ctxt.read_u32be()?);
+        // The resource data area is bounded by the fork header, so a length that runs past it
+        // is corrupt
+        ctxt.check(len <= ctxt.scope().data().len())?;
+        Ok(ctxt.read_slice(len)?)
+    }
+
+    /// A textual listing of the resources in the fork in the style of DeRez.
+    ///
+    /// The listing is produced by the `Display` implementation of the returned value.
+    pub fn derez(&self) -> DeRez<'_, 'a> {
+        DeRez { fork: self }
+    }
+
+    /// Copy the resource fork into an [OwnedResourceFork] that doesn't borrow the data it was
+    /// parsed from.
+    #[cfg(not(feature = "no_std"))]
+    pub fn to_owned(&self) -> OwnedResourceFork {
+        OwnedResourceFork::new(self.data.to_vec()).expect("resource fork data is valid")
+    }
+
+    /// Build an index of the resources for fast lookups by type and id, or type and name.
+    ///
+    /// Returns an error if the reference list of any type lies outside the resource map.
+    #[cfg(not(feature = "no_std"))]
+    pub fn index(&self) -> Result<ResourceIndex<'_, 'a>, ParseError> {
+        ResourceIndex::new(self)
+    }
 }
 
 impl ResourceFork<'_> {
+    /// The bytes following the resource fork header that are reserved for system use.
+    ///
+    /// These are nominally zero but some tools store data here. This is usually 112 bytes,
+    /// but may be shorter if the resource fork is truncated.
+    pub fn reserved_header(&self) -> &[u8] {
+        self.reserved_header
+    }
+
+    /// The first 22 bytes of the resource map.
+    ///
+    /// These hold a copy of the resource header, a handle to the next resource map, and the file
+    /// reference number. They are filled in by the Resource Manager at runtime, so a fork that
+    /// was written while open in an application will often have non-zero values here.
+    pub fn map_reserved(&self) -> &[u8] {
+        self.map.reserved
+    }
+
+    /// Returns `false` if the offset of the resource name list lies outside the resource map.
+    ///
+    /// When the offset is invalid the resources can still be read, but none of them have names.
+    pub fn name_list_valid(&self) -> bool {
+        self.map.name_list_valid
+    }
+
+    /// The attributes of the resource fork, from the resource map.
+    pub fn map_attributes(&self) -> MapAttributes {
+        MapAttributes::from_bits(self.map.attributes)
+    }
+
+    /// The number of resource types in the fork.
+    ///
+    /// This and the other counts only read the resource map, not the resources.
+    pub fn type_count(&self) -> usize {
+        self.map.type_list.list.len()
+    }
+
+    /// The total number of resources of all types in the fork.
+    pub fn resource_count(&self) -> usize {
+        self.map
+            .type_list
+            .list
+            .iter()
+            .map(|item| usize::from(item.num_rsrc))
+            .sum()
+    }
+
+    /// Returns `true` if the resource fork holds resources of type `rsrc_type`.
+    pub fn has_type(&self, rsrc_type: FourCC) -> bool {
+        self.map
+            .type_list
+            .list
+            .iter()
+            .any(|item| item.rsrc_type == rsrc_type)
+    }
+
+    /// The number of resources of type `rsrc_type`, or `None` if there are none of that type.
+    pub fn count_of(&self, rsrc_type: FourCC) -> Option<u16> {
+        self.map
+            .type_list
+            .list
+            .iter()
+            .find(|item| item.rsrc_type == rsrc_type)
+            .map(|item| item.num_rsrc)
+    }
+
+    pub(crate) fn name_list_len(&self) -> usize {
+        self.map.name_list_scope.data().len()
+    }
+
+    /// Gather statistics about the resources in the fork.
+    ///
+    /// Resource sizes are taken from the length word preceding the data of each resource. The
+    /// data itself isn't read, apart from the header of resources marked as compressed. This is a
+    /// single pass over the resource map. Resources with data outside the resource data area are counted but
+    /// don't contribute to the lengths.
+    #[cfg(not(feature = "no_std"))]
+    pub fn stats(&self) -> ForkStats {
+        let mut stats = ForkStats {
+            types: Vec::with_capacity(self.type_count()),
+            resource_count: 0,
+            named_count: 0,
+            compressed_count: 0,
+            total_len: 0,
+            largest: None,
+            map_len: self.map_len,
+            gap_len: 0,
+        };
+        let mut used = 0;
+        for item in self.map.type_list.list.iter() {
+            let mut type_stats = TypeStats {
+                rsrc_type: item.rsrc_type,
+                count: 0,
+                total_len: 0,
+            };
+            let references = item.reference_list(self.map.type_list.scope).ok();
+            for reference in references.iter().flat_map(|list| list.list.iter()) {
+                type_stats.count += 1;
+                if reference.name_offset.is_some() {
+                    stats.named_count += 1;
+                }
+                let Ok(data) = self.read_resource_data(reference.data_offset) else {
+                    continue;
+                };
+                if ResourceAttributes::from_bits(reference.attributes).is_compressed()
+                    && CompressionInfo::read(data).is_ok()
+                {
+                    stats.compressed_count += 1;
+                }
+                // Length was read from a u32 so this can't truncate
+                let len = data.len() as u32;
+                type_stats.total_len += u64::from(len);
+                used += 4 + u64::from(len);
+                match stats.largest {
+                    Some(largest) if largest.len >= len => {}
+                    _ => {
+                        stats.largest = Some(ResourceSize {
+                            rsrc_type: item.rsrc_type,
+                            id: reference.id,
+                            len,
+                        })
+                    }
+                }
+            }
+            stats.resource_count += type_stats.count;
+            stats.total_len += type_stats.total_len;
+            stats.types.push(type_stats);
+        }
+        stats.gap_len = (self.rsrc_data.len() as u64).saturating_sub(used);
+        stats
+    }
+
+    /// Check the resource fork for problems.
+    ///
+    /// The offsets of the returned issues are from the start of the resource fork.
+    #[cfg(not(feature = "no_std"))]
+    pub fn validate(&self) -> Issues {
+        let mut issues = Issues::default();
+
+        if self.reserved_header.iter().any(|&byte| byte != 0) {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    code::NONZERO_RESERVED_HEADER,
+                    "area reserved for system use is not zero",
+                )
+                .at(16),
+            );
+        }
+        // The first 16 bytes are a copy of the header so only the handle and file reference
+        // number are checked. These are set when the fork is written by the Resource Manager.
+        if self.map.reserved[16..].iter().any(|&byte| byte != 0) {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    code::NONZERO_MAP_RESERVED,
+                    "resource map handle and file reference number are not zero",
+                )
+                .at(usize::num_from(self.map_offset) + 16),
+            );
+        }
+
+        if !self.map.name_list_valid {
+            issues.push(
+                Issue::new(
+                    Severity::Warning,
+                    code::NAME_LIST_OUT_OF_BOUNDS,
+                    "resource name list is outside the resource map, names are unavailable",
+                )
+                .at(usize::num_from(self.map_offset) + 26),
+            );
+        }
+
+        let data_offset = usize::num_from(self.data_offset);
+        let type_list_offset = usize::from(self.map.type_list_offset);
+        // The end of the last structure in the map, to compare with the declared map length
+        let mut map_end = type_list_offset + 2 + 8 * self.map.type_list.list.len();
+        if self.map.name_list_valid {
+            map_end = map_end.max(usize::from(self.map.name_list_offset));
+        }
+        // The type, id, and extent of the data and length of each readable resource, relative to
+        // the start of the data area
+        let mut extents = Vec::new();
+        let mut seen = HashSet::new();
+
+        for item in self.map.type_list.list.iter() {
+            if !item.is_plausible() {
+                issues.push(
+                    Issue::new(
+                        Severity::Warning,
+                        code::IMPLAUSIBLE_TYPE,
+                        "resource type is not printable",
+                    )
+                    .resource(item.rsrc_type, None),
+                );
+            }
+            if item.num_rsrc == 0 {
+                issues.push(
+                    Issue::new(
+                        Severity::Warning,
+                        code::EMPTY_TYPE,
+                        "resource type has no resources",
+                    )
+                    .resource(item.rsrc_type, None),
+                );
+            }
+            let Some(reference_list) = item.reference_list(self.map.type_list.scope).ok() else {
+                issues.push(
+                    Issue::new(
+                        Severity::Error,
+                        code::REFERENCE_LIST_OUT_OF_BOUNDS,
+                        "reference list is outside the resource map",
+                    )
+                    .resource(item.rsrc_type, None),
+                );
+                continue;
+            };
+            map_end = map_end.max(
+                type_list_offset
+                    + usize::from(item.reference_list_offset)
+                    + 12 * reference_list.list.len(),
+            );
+            for reference in reference_list.list.iter() {
+                if !seen.insert((item.rsrc_type, reference.id)) {
+                    issues.push(
+                        Issue::new(
+                            Severity::Warning,
+                            code::DUPLICATE_RESOURCE,
+                            "resource type and id are used by more than one resource",
+                        )
+                        .resource(item.rsrc_type, Some(reference.id)),
+                    );
+                }
+                let data_issue = match self.read_resource_data(reference.data_offset) {
+                    Ok(data) => {
+                        let start = usize::num_from(reference.data_offset);
+                        extents.push((start, start + 4 + data.len(), item.rsrc_type, reference.id));
+                        None
+                    }
+                    Err(ParseError::BadValue) => Some((
+                        code::BAD_RESOURCE_LENGTH,
+                        "resource length runs past the resource data area",
+                    )),
+                    Err(_) => Some((
+                        code::RESOURCE_DATA_OUT_OF_BOUNDS,
+                        "resource data is outside the resource data area",
+                    )),
+                };
+                if let Some((code, message)) = data_issue {
+                    issues.push(
+                        Issue::new(Severity::Error, code, message)
+                            .at(usize::num_from(self.data_offset)
+                                .saturating_add(usize::num_from(reference.data_offset)))
+                            .resource(item.rsrc_type, Some(reference.id)),
+                    );
+                }
+                if let (Some(offset), Some(name)) = (
+                    reference.name_offset,
+                    reference
+                        .name_offset
+                        .and_then(|offset| self.read_name(offset)),
+                ) {
+                    map_end = map_end.max(
+                        usize::from(self.map.name_list_offset)
+                            + usize::from(offset)
+                            + 1
+                            + name.len(),
+                    );
+                }
+                if let Some(&[]) = reference
+                    .name_offset
+                    .and_then(|offset| self.read_name(offset))
+                {
+                    issues.push(
+                        Issue::new(Severity::Info, code::EMPTY_NAME, "resource name is empty")
+                            .resource(item.rsrc_type, Some(reference.id)),
+                    );
+                }
+                // Names outside the name list are reported once above if the whole list is
+                // out of bounds
+                let name_offset = reference.name_offset;
+                if self.map.name_list_valid
+                    && name_offset.is_some()
+                    && name_offset
+                        .and_then(|offset| self.read_name(offset))
+                        .is_none()
+                {
+                    issues.push(
+                        Issue::new(
+                            Severity::Warning,
+                            code::NAME_OUT_OF_BOUNDS,
+                            "resource name is outside the name list",
+                        )
+                        .resource(item.rsrc_type, Some(reference.id)),
+                    );
+                }
+            }
+        }
+
+        // Resource data is normally packed, so a length that runs into the next block of data is
+        // likely to be corrupt. Resources that share the same data aren't reported here.
+        extents.sort_by_key(|&(start, ..)| start);
+        for (i, &(start, end, rsrc_type, id)) in extents.iter().enumerate() {
+            let next = extents[i + 1..]
+                .iter()
+                .map(|&(next, ..)| next)
+                .find(|&next| next > start);
+            if matches!(next, Some(next) if end > next) {
+                issues.push(
+                    Issue::new(
+                        Severity::Warning,
+                        code::RESOURCE_LENGTH_OVERLAPS,
+                        "resource length runs into the data of the next resource",
+                    )
+                    .at(data_offset + start)
+                    .resource(rsrc_type, Some(id)),
+                );
+            }
+        }
+
+        // Resources that share data, and data that isn't used by any resource
+        let mut covered = 0;
+        for &(start, end, rsrc_type, id) in &extents {
+            if start < covered {
+                issues.push(
+                    Issue::new(
+                        Severity::Warning,
+                        code::OVERLAPPING_RESOURCE_DATA,
+                        "resource data overlaps the data of another resource",
+                    )
+                    .at(data_offset + start)
+                    .resource(rsrc_type, Some(id)),
+                );
+            } else if start > covered {
+                issues.push(
+                    Issue::new(
+                        Severity::Info,
+                        code::UNUSED_RESOURCE_DATA,
+                        "resource data area contains bytes not used by any resource",
+                    )
+                    .at(data_offset + covered),
+                );
+            }
+            covered = covered.max(end);
+        }
+        if covered < self.rsrc_data.len() {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    code::UNUSED_RESOURCE_DATA,
+                    "resource data area contains bytes not used by any resource",
+                )
+                .at(data_offset + covered),
+            );
+        }
+
+        // The names can't be found when the name list is out of bounds, which is reported above
+        if self.map.name_list_valid && usize::num_from(self.map_len) > map_end {
+            issues.push(
+                Issue::new(
+                    Severity::Info,
+                    code::MAP_LENGTH_MISMATCH,
+                    "resource map is longer than the lists it contains",
+                )
+                .at(usize::num_from(self.map_offset) + map_end),
+            );
+        }
+
+        issues
+    }
+
+    /// The ids of the resources of the supplied type in ascending order.
+    ///
+    /// Only the resource map is read. The list is empty if there are no resources of the type.
+    #[cfg(not(feature = "no_std"))]
+    pub fn ids_of(&self, rsrc_type: FourCC) -> Vec<i16> {
+        let mut ids: Vec<_> = self
+            .map
+            .type_list
+            .list
+            .iter()
+            .find(|item| item.rsrc_type == rsrc_type)
+            .and_then(|item| item.reference_list(self.map.type_list.scope).ok())
+            .map(|reference_list| {
+                reference_list
+                    .list
+                    .iter()
+                    .map(|reference| reference.id)
+                    .collect()
+            })
+            .unwrap_or_default();
+        ids.sort_unstable();
+        ids
+    }
+
     /// Get the data for the resource with the supplied type and id.
+    ///
+    /// If there is more than one resource with the type and id the first one is returned, as the
+    /// Resource Manager does. Use [Self::get_resources] to get all of them.
+    ///
+    /// Returns `None` both when the resource isn't present and when the resource map is corrupt.
+    /// Use [Self::try_get_resource] to tell the two apart.
     pub fn get_resource(&self, rsrc_type: FourCC, rsrc_id: i16) -> Option<Resource<'_>> {
-        let reference_list = self.map.type_list.find(rsrc_type)?;
-        let item = reference_list.find(rsrc_id)?;
-        self.read_resource(&item)
+        self.try_get_resource(rsrc_type, rsrc_id).ok().flatten()
     }
 
-    fn read_resource(&self, item: &ReferenceListItem) -> Option<Resource<'_>> {
-        let data = self.read_resource_data(item.data_offset)?;
+    /// Get the data for the resource with the supplied type and id, reporting a corrupt map.
+    ///
+    /// Returns `Ok(None)` if there is no resource with the type and id, and an error if the
+    /// reference list or the resource data lies outside the resource fork.
+    pub fn try_get_resource(
+        &self,
+        rsrc_type: FourCC,
+        rsrc_id: i16,
+    ) -> Result<Option<Resource<'_>>, ParseError> {
+        match self.find_reference(rsrc_type, rsrc_id)? {
+            Some(item) => self.try_read_resource(rsrc_type, &item).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the resource with the supplied type and id, cutting short data that runs into the
+    /// data of the next resource.
+    ///
+    /// The data of the resources in a fork is normally packed one after another, so a length
+    /// that runs into the data of the next resource, or past the end of the resource data area,
+    /// is likely to be corrupt. Rather than returning that data, or failing like
+    /// [Self::try_get_resource], the data is cut short at the start of the next resource or the
+    /// end of the data area, and a warning describing this is returned along with the resource.
+    /// The warning is `None` if the data was not cut short.
+    ///
+    /// Errors are returned as for [Self::try_get_resource] when the resource can't be found or
+    /// its data starts outside the resource data area.
+    #[cfg(not(feature = "no_std"))]
+    pub fn try_get_resource_clamped(
+        &self,
+        rsrc_type: FourCC,
+        rsrc_id: i16,
+    ) -> Result<Option<(Resource<'_>, Option<Issue>)>, ParseError> {
+        let Some(item) = self.find_reference(rsrc_type, rsrc_id)? else {
+            return Ok(None);
+        };
+        let start = usize::num_from(item.data_offset);
+        let mut ctxt = ReadScope::new(self.rsrc_data).offset(start).ctxt();
+        let len = ctxt.read_u32be()?;
+        let available = ctxt.scope().data().len();
+        let next = self
+            .map
+            .type_list
+            .list
+            .iter()
+            .filter_map(|item| item.reference_list(self.map.type_list.scope).ok())
+            .flat_map(|reference_list| reference_list.list.iter())
+            .map(|reference| usize::num_from(reference.data_offset))
+            .filter(|&offset| offset > start)
+            .min();
+
+        // In u64 so that a corrupt length can't overflow on 32-bit targets
+        let end = u64::from(item.data_offset) + 4 + u64::from(len);
+        let len = usize::num_from(len);
+        let mut clamped = None;
+        if let Some(next) = next.filter(|&next| end > next as u64) {
+            clamped = Some((
+                next.saturating_sub(start + 4).min(available),
+                code::RESOURCE_LENGTH_OVERLAPS,
+                "resource length runs into the data of the next resource, data was cut short",
+            ));
+        } else if len > available {
+            clamped = Some((
+                available,
+                code::BAD_RESOURCE_LENGTH,
+                "resource length runs past the resource data area, data was cut short",
+            ));
+        }
+        let issue = clamped.map(|(_, code, message)| {
+            Issue::new(Severity::Warning, code, message)
+                .at(usize::num_from(self.data_offset) + start)
+                .resource(rsrc_type, Some(rsrc_id))
+        });
+        let data = ctxt.read_slice(clamped.map_or(len, |(len, ..)| len))?;
+
+        let resource = Resource {
+            rsrc_type,
+            id: item.id,
+            name: item.name_offset.and_then(|offset| self.read_name(offset)),
+            attributes: item.attributes,
+            data_offset: usize::num_from(self.data_offset) + start + 4,
+            data,
+        };
+        Ok(Some((resource, issue)))
+    }
+
+    fn find_reference(
+        &self,
+        rsrc_type: FourCC,
+        rsrc_id: i16,
+    ) -> Result<Option<ReferenceListItem>, ParseError> {
+        let Some(reference_list) = self.map.type_list.find(rsrc_type)? else {
+            return Ok(None);
+        };
+        Ok(reference_list.find(rsrc_id))
+    }
+
+    fn read_resource(&self, rsrc_type: FourCC, item: &ReferenceListItem) -> Option<Resource<'_>> {
+        self.try_read_resource(rsrc_type, item).ok()
+    }
+
+    fn try_read_resource(
+        &self,
+        rsrc_type: FourCC,
+        item: &ReferenceListItem,
+    ) -> Result<Resource<'_>, ParseError> {
         let name = item.name_offset.and_then(|offset| self.read_name(offset));
+        self.read_resource_with_name(rsrc_type, item, name)
+    }
+
+    /// Read a resource whose name has already been read from the name list
+    fn read_resource_with_name<'b>(
+        &'b self,
+        rsrc_type: FourCC,
+        item: &ReferenceListItem,
+        name: Option<&'b [u8]>,
+    ) -> Result<Resource<'b>, ParseError> {
+        let data = self.read_resource_data(item.data_offset)?;
+        // The data follows its 4 byte length
+        let data_offset = usize::num_from(self.data_offset) + usize::num_from(item.data_offset) + 4;
 
-        Some(Resource {
+        Ok(Resource {
+            rsrc_type,
             id: item.id,
             name,
             attributes: item.attributes,
+            data_offset,
             data,
         })
     }
 
-    fn read_resource_data(&self, offset: u32) -> Option<&[u8]> {
-        let mut ctxt = ReadScope::new(self.rsrc_data)
-            .offset(usize::num_from(offset))
-            .ctxt();
-        let len = ctxt.read_u32be().ok()?;
-        ctxt.read_slice(usize::num_from(len)).ok() // FIXME: ok
-    }
-
     fn read_name(&self, offset: u16) -> Option<&[u8]> {
         let mut ctxt = self.map.name_list_scope.offset(usize::from(offset)).ctxt();
         let len = ctxt.read_u8().ok()?;
@@ -164,14 +1054,28 @@ impl ResourceFork<'_> {
     }
 }
 
+impl ReadFrom for ResourceForkHeader {
+    type ReadType = ((U32Be, U32Be), (U32Be, U32Be));
+
+    fn from(((data_offset, map_offset), (data_len, map_len)): ((u32, u32), (u32, u32))) -> Self {
+        ResourceForkHeader {
+            data_offset,
+            map_offset,
+            data_len,
+            map_len,
+        }
+    }
+}
+
 impl ReadBinary for ResourceMap<'_> {
     type HostType<'a> = ResourceMap<'a>;
 
     fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
-        // Skip the first 22 bytes these are all set to 0 and are used by the Resource
+        // The first 22 bytes are reserved for a copy of the resource header, a handle to the
+        // next resource map, and the file reference number. These are used by the Resource
         // Manager for storing data at runtime.
         let scope = ctxt.scope();
-        let _ = ctxt.read_slice(16 + 4 + 2)?;
+        let reserved = ctxt.read_slice(16 + 4 + 2)?;
         let attributes = ctxt.read_u16be()?;
         let rsrc_type_list_offset = ctxt.read_u16be()?;
         let rsrc_name_list_offset = ctxt.read_u16be()?;
@@ -179,12 +1083,19 @@ impl ReadBinary for ResourceMap<'_> {
         let type_list = scope
             .offset(usize::from(rsrc_type_list_offset))
             .read::<TypeList<'_>>()?;
+        // An offset past the end of the map results in an empty name list, so that the
+        // resources can still be read, just without names
+        let name_list_valid = usize::from(rsrc_name_list_offset) <= scope.data().len();
         let name_list_scope = scope.offset(usize::from(rsrc_name_list_offset));
 
         Ok(ResourceMap {
+            reserved,
             attributes,
+            type_list_offset: rsrc_type_list_offset,
             type_list,
+            name_list_offset: rsrc_name_list_offset,
             name_list_scope,
+            name_list_valid,
         })
     }
 }
@@ -194,7 +1105,8 @@ impl ReadBinary for TypeList<'_> {
 
     fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
         let scope = ctxt.scope();
-        // Value is stored minus 1, so add 1 to it after reading
+        // Value is stored minus 1, so add 1 to it after reading. An empty map stores 0xFFFF (-1),
+        // which wraps around to zero types.
         let num_types = ctxt.read_u16be()?.wrapping_add(1);
         let list = ctxt.read_array::<TypeListItem>(usize::from(num_types))?;
 
@@ -203,9 +1115,11 @@ impl ReadBinary for TypeList<'_> {
 }
 
 impl TypeList<'_> {
-    fn find(&self, rsrc_type: FourCC) -> Option<ReferenceList<'_>> {
-        let item = self.list.iter().find(|item| item.rsrc_type == rsrc_type)?;
-        item.reference_list(self.scope)
+    fn find(&self, rsrc_type: FourCC) -> Result<Option<ReferenceList<'_>>, ParseError> {
+        match self.list.iter().find(|item| item.rsrc_type == rsrc_type) {
+            Some(item) => item.reference_list(self.scope).map(Some),
+            None => Ok(None),
+        }
     }
 }
 
@@ -215,7 +1129,8 @@ impl ReadFrom for TypeListItem {
     fn from((rsrc_type, num_rsrc, reference_list_offset): (FourCC, u16, u16)) -> Self {
         TypeListItem {
             rsrc_type,
-            // Value is stored minus 1
+            // Value is stored minus 1. A type always has at least one resource, so a stored
+            // 0xFFFF can only come from a corrupt map. It wraps to zero, which yields no resources.
             num_rsrc: num_rsrc.wrapping_add(1),
             reference_list_offset,
         }
@@ -228,11 +1143,30 @@ impl TypeListItem {
         self.rsrc_type
     }
 
-    fn reference_list<'a>(&self, scope: ReadScope<'a>) -> Option<ReferenceList<'a>> {
+    /// Returns the number of resources of this type.
+    pub fn count(&self) -> u16 {
+        self.num_rsrc
+    }
+
+    /// Returns `true` if the resource type consists of printable MacRoman characters.
+    ///
+    /// Real resource types are almost always printable, so an implausible type in a resource map
+    /// is a sign of corruption.
+    pub fn is_plausible(&self) -> bool {
+        self.rsrc_type.is_printable()
+    }
+
+    /// Read the reference list of this type from the type list `scope`.
+    ///
+    /// Returns [ParseError::BadOffset] if the offset of the list lies outside the type list.
+    fn reference_list<'a>(&self, scope: ReadScope<'a>) -> Result<ReferenceList<'a>, ParseError> {
+        let offset = usize::from(self.reference_list_offset);
+        if offset > scope.data().len() {
+            return Err(ParseError::BadOffset);
+        }
         scope
-            .offset(usize::from(self.reference_list_offset))
+            .offset(offset)
             .read_dep::<ReferenceList<'_>>(self.num_rsrc)
-            .ok() // FIXME: ok?
     }
 }
 
@@ -271,15 +1205,23 @@ impl ReadFrom for ReferenceListItem {
 }
 
 impl Resource<'_> {
+    /// Returns the type of this resource.
+    pub fn resource_type(&self) -> FourCC {
+        self.rsrc_type
+    }
+
     /// Returns the ID of this resource.
     pub fn id(&self) -> i16 {
         self.id
     }
 
     /// The name associated with this resource, if present.
+    ///
+    /// A resource with an empty name returns `Some` with an empty string, while a resource without
+    /// a name returns `None`. See [Self::name_bytes].
     #[cfg(not(feature = "no_std"))]
     pub fn name(&self) -> Option<String> {
-        self.name.map(|name| String::from_macroman(name))
+        self.name.map(String::from_macroman)
     }
 
     /// The name associated with this resource, if present.
@@ -293,7 +1235,31 @@ impl Resource<'_> {
         self.name.and_then(String::try_from_macroman)
     }
 
+    /// The name associated with this resource, if present, decoded with `policy`.
+    ///
+    /// [Self::name] is the same as using [DecodePolicy::default].
+    #[cfg(not(feature = "no_std"))]
+    pub fn name_with_policy(&self, policy: DecodePolicy) -> Result<Option<String>, MacRomanError> {
+        self.name
+            .map(|name| decode_with_policy(name, policy).map(Cow::into_owned))
+            .transpose()
+    }
+
+    /// The name associated with this resource, if present.
+    ///
+    /// Unlike [Self::name] this only allocates when the name contains non-ASCII characters.
+    #[cfg(not(feature = "no_std"))]
+    pub fn name_str(&self) -> Option<Cow<'_, str>> {
+        self.name.map(decode_macroman)
+    }
+
     /// The raw bytes of the resource name.
+    ///
+    /// Returns `None` if the resource has no name, which is indicated by a name offset of -1 in
+    /// the resource map. If the name is present but zero length this returns `Some(&[])`.
+    ///
+    /// `None` is also returned if the name offset points outside the name list. This is reported
+    /// by [ResourceFork::validate].
     pub fn name_bytes(&self) -> Option<&[u8]> {
         self.name
     }
@@ -302,21 +1268,147 @@ impl Resource<'_> {
     pub fn data(&self) -> &[u8] {
         self.data
     }
-}
-
-impl<'a, 'rsrc> Iterator for ResourceTypes<'a, 'rsrc> {
-    type Item = TypeListItem;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // Get the current type list
-        let list = &self.fork.map.type_list.list;
-        let type_list_item = list
-            .check_index(usize::from(self.type_index))
-            .ok()
-            .map(|()| list.get_item(usize::from(self.type_index)))?;
+    /// The location of the data of this resource within the resource fork.
+    ///
+    /// The range is of the data only. It excludes the 4 byte length that precedes the data in
+    /// the fork, which starts at `data_range().start - 4`.
+    pub fn data_range(&self) -> Range<usize> {
+        self.data_offset..self.data_offset + self.data.len()
+    }
 
-        self.type_index += 1;
-        Some(type_list_item)
+    /// The attributes of this resource.
+    pub fn attributes(&self) -> ResourceAttributes {
+        ResourceAttributes::from_bits(self.attributes)
+    }
+
+    /// Returns `true` if the resource data is compressed.
+    ///
+    /// A resource is compressed when it has the [ResourceAttributes::COMPRESSED] attribute and
+    /// its data starts with an extended resource header. The data of a compressed resource is
+    /// returned as stored, not decompressed.
+    pub fn is_compressed(&self) -> bool {
+        self.compression_info().is_some()
+    }
+
+    /// Decode the resource as a string (`'STR '`) resource.
+    ///
+    /// Returns `None` if this isn't a `'STR '` resource.
+    pub fn as_str_resource(&self) -> Option<Result<Str<'_>, ParseError>> {
+        (self.rsrc_type == <Str<'_> as TypedResource<'_>>::TYPE).then(|| Str::parse(self.data))
+    }
+
+    /// Decode the resource as a string list (`'STR#'`) resource.
+    ///
+    /// Returns `None` if this isn't a `'STR#'` resource.
+    pub fn as_string_list(&self) -> Option<Result<StringList<'_>, ParseError>> {
+        (self.rsrc_type == <StringList<'_> as TypedResource<'_>>::TYPE)
+            .then(|| StringList::parse(self.data))
+    }
+
+    /// Details of how the resource is compressed, or `None` if it isn't compressed.
+    pub fn compression_info(&self) -> Option<CompressionInfo> {
+        if !self.attributes().is_compressed() {
+            return None;
+        }
+        CompressionInfo::read(self.data).ok()
+    }
+
+    /// Returns `true` if the resource is loaded when the file is opened.
+    pub fn is_preload(&self) -> bool {
+        self.attributes().is_preload()
+    }
+
+    /// Returns `true` if the resource can't be changed.
+    pub fn is_protected(&self) -> bool {
+        self.attributes().is_protected()
+    }
+
+    #[cfg_attr(feature = "no_std", allow(unused))]
+    pub(crate) fn raw_attributes(&self) -> u8 {
+        self.attributes
+    }
+}
+
+impl fmt::Debug for ResourceFork<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// The resource types in the fork and the number of resources of each type
+        struct TypeCounts<'a, 'rsrc>(&'a ResourceFork<'rsrc>);
+
+        impl fmt::Debug for TypeCounts<'_, '_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_map()
+                    .entries(
+                        self.0
+                            .resource_types()
+                            .map(|item| (item.rsrc_type, item.num_rsrc)),
+                    )
+                    .finish()
+            }
+        }
+
+        f.debug_struct("ResourceFork")
+            .field("data_len", &self.rsrc_data.len())
+            .field("attributes", &self.map_attributes())
+            .field("types", &TypeCounts(self))
+            .finish()
+    }
+}
+
+impl fmt::Debug for TypeListItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeListItem")
+            .field("type", &self.rsrc_type)
+            .field("count", &self.num_rsrc)
+            .finish()
+    }
+}
+
+impl fmt::Debug for Resource<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        /// The first [DEBUG_DATA_LEN] bytes of the data in hex
+        struct HexPrefix<'a>(&'a [u8]);
+
+        impl fmt::Debug for HexPrefix<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("[")?;
+                for (i, byte) in self.0.iter().take(DEBUG_DATA_LEN).enumerate() {
+                    if i > 0 {
+                        f.write_str(" ")?;
+                    }
+                    write!(f, "{:02x}", byte)?;
+                }
+                if self.0.len() > DEBUG_DATA_LEN {
+                    f.write_str(" ..")?;
+                }
+                f.write_str("]")
+            }
+        }
+
+        f.debug_struct("Resource")
+            .field("type", &self.rsrc_type)
+            .field("id", &self.id)
+            .field("name", &self.name.map(DebugMacRoman))
+            .field("attributes", &self.attributes())
+            .field("data_len", &self.data.len())
+            .field("data", &HexPrefix(self.data))
+            .finish()
+    }
+}
+
+impl<'a, 'rsrc> Iterator for ResourceTypes<'a, 'rsrc> {
+    type Item = TypeListItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Get the current type list
+        let list = &self.fork.map.type_list.list;
+        let type_list_item = list
+            .check_index(usize::from(self.type_index))
+            .ok()
+            .map(|()| list.get_item(usize::from(self.type_index)))?;
+
+        self.type_index += 1;
+        Some(type_list_item)
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -335,7 +1427,9 @@ impl<'rsrc, 'a: 'rsrc> Iterator for Resources<'a, 'rsrc> {
             .check_index(usize::from(self.rsrc_index))
             .ok()
             .map(|()| reference_list.list.get_item(usize::from(self.rsrc_index)))?;
-        let resource = self.fork.read_resource(&reference_list_item)?;
+        let resource = self
+            .fork
+            .read_resource(self.item.rsrc_type, &reference_list_item)?;
 
         self.rsrc_index += 1;
         Some(resource)
@@ -353,14 +1447,145 @@ impl<'rsrc, 'a: 'rsrc> Iterator for Resources<'a, 'rsrc> {
 
 impl Resources<'_, '_> {
     fn reference_list(&self) -> Option<ReferenceList<'_>> {
-        self.item.reference_list(self.fork.map.type_list.scope)
+        self.item.reference_list(self.fork.map.type_list.scope).ok()
+    }
+}
+
+impl<'rsrc, 'a: 'rsrc> Iterator for TryResources<'a, 'rsrc> {
+    type Item = Result<Resource<'rsrc>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let reference_list = match self.item.reference_list(self.fork.map.type_list.scope) {
+            Ok(reference_list) => reference_list,
+            Err(err) => {
+                self.failed = true;
+                return Some(Err(err));
+            }
+        };
+        let index = usize::from(self.rsrc_index);
+        reference_list.list.check_index(index).ok()?;
+        let reference_list_item = reference_list.list.get_item(index);
+
+        self.rsrc_index += 1;
+        Some(
+            self.fork
+                .try_read_resource(self.item.rsrc_type, &reference_list_item),
+        )
+    }
+}
+
+impl<'rsrc, 'a: 'rsrc> Iterator for ResourcesWithId<'a, 'rsrc> {
+    type Item = Resource<'rsrc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let references = self.references.as_mut()?;
+        for reference in references.by_ref() {
+            if reference.id == self.rsrc_id {
+                if let Some(resource) = self.fork.read_resource(self.rsrc_type, &reference) {
+                    return Some(resource);
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl<'rsrc, 'a: 'rsrc> Iterator for SortedResources<'a, 'rsrc> {
+    type Item = Resource<'rsrc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let fork = self.fork;
+        let rsrc_type = self.rsrc_type;
+        self.references
+            .by_ref()
+            .find_map(|reference| fork.read_resource(rsrc_type, &reference))
+    }
+}
+
+#[cfg(not(feature = "no_std"))]
+impl Iterator for ResourceNames<'_, '_> {
+    type Item = (FourCC, i16, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((rsrc_type, references)) = &mut self.references {
+                for reference in references.by_ref() {
+                    let name = reference
+                        .name_offset
+                        .and_then(|offset| self.fork.read_name(offset));
+                    if let Some(name) = name {
+                        return Some((*rsrc_type, reference.id, String::from_macroman(name)));
+                    }
+                }
+            }
+            let item = self.types.next()?;
+            // Types with an unreadable reference list have no names
+            self.references = item
+                .reference_list(self.fork.map.type_list.scope)
+                .ok()
+                .map(|reference_list| (item.rsrc_type, reference_list.list.iter()));
+        }
+    }
+}
+
+impl<'rsrc, 'a: 'rsrc, F> Iterator for FindResources<'a, 'rsrc, F>
+where
+    F: FnMut(FourCC, i16, Option<&[u8]>) -> bool,
+{
+    type Item = Resource<'rsrc>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some((rsrc_type, references)) = &mut self.references {
+                for reference in references.by_ref() {
+                    let name = reference
+                        .name_offset
+                        .and_then(|offset| self.fork.read_name(offset));
+                    if !(self.predicate)(*rsrc_type, reference.id, name) {
+                        continue;
+                    }
+                    let resource = self
+                        .fork
+                        .read_resource_with_name(*rsrc_type, &reference, name);
+                    if let Ok(resource) = resource {
+                        return Some(resource);
+                    }
+                }
+            }
+            let item = self.types.next()?;
+            // Types with an unreadable reference list are skipped
+            self.references = item
+                .reference_list(self.fork.map.type_list.scope)
+                .ok()
+                .map(|reference_list| (item.rsrc_type, reference_list.list.iter()));
+        }
+    }
+}
+
+impl<'rsrc, 'a: 'rsrc> Iterator for TryIter<'a, 'rsrc> {
+    type Item = Result<Resource<'rsrc>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(resource) = self.resources.as_mut().and_then(Iterator::next) {
+                return Some(resource);
+            }
+            let item = self.types.next()?;
+            self.resources = Some(self.types.fork.try_resources(item));
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test::read_fixture;
+    use crate::test::{
+        compressed_resource, read_fixture, resource_fork, type_list_offset, TestResource,
+    };
 
     #[test]
     fn test_macbinary_3() {
@@ -371,6 +1596,7 @@ mod tests {
             .get_resource(FourCC(u32::from_be_bytes(*b"BBST")), 128)
             .unwrap();
         assert_eq!(bbst.data().len(), 1048);
+        assert_eq!(bbst.resource_type(), "BBST");
 
         let mpsr = rsrc
             .get_resource(FourCC(u32::from_be_bytes(*b"MPSR")), 1005)
@@ -388,6 +1614,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_data_range() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let fork = file.resource_fork_raw();
+        let rsrc = ResourceFork::new(fork).unwrap();
+
+        // Resource data starts at 256, and the MPSR data is first, after its length
+        let mpsr = rsrc.get_resource(FourCC::new(*b"MPSR"), 1005).unwrap();
+        assert_eq!(mpsr.data_range(), 260..332);
+        assert_eq!(&fork[mpsr.data_range()], mpsr.data());
+        assert_eq!(fork[256..260], 72_u32.to_be_bytes());
+
+        // BBST follows at data offset 76
+        let bbst = rsrc.get_resource(FourCC::new(*b"BBST"), 128).unwrap();
+        assert_eq!(bbst.data_range(), 336..1384);
+        assert_eq!(&fork[bbst.data_range()], bbst.data());
+    }
+
     #[test]
     fn test_iter_types() {
         let data = read_fixture("tests/Text File.bin");
@@ -409,7 +1654,7 @@ mod tests {
         for item in rsrc.resource_types() {
             resources.extend(rsrc.resources(item).map(|resource| {
                 (
-                    item.rsrc_type.to_string(),
+                    resource.resource_type().to_string(),
                     resource.id,
                     resource.name(),
                     resource.data().len(),
@@ -424,4 +1669,918 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_debug() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let debug = format!("{:?}", rsrc);
+        assert!(debug.starts_with("ResourceFork { data_len: "));
+        assert!(debug.contains("types: {'MPSR': 1, 'BBST': 1}"));
+
+        let mpsr = rsrc
+            .get_resource(FourCC(u32::from_be_bytes(*b"MPSR")), 1005)
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", mpsr),
+            "Resource { type: 'MPSR', id: 1005, name: None, attributes: ResourceAttributes(), data_len: 72, \
+             data: [00 09 4d 6f 6e 61 63 6f 00 e0 00 00 00 00 07 10 ..] }"
+        );
+
+        let data = resource_fork(&[(*b"STR ", -16396, Some(b"Caf\x8E"), b"\x01\x02")]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = rsrc
+            .get_resource(FourCC(u32::from_be_bytes(*b"STR ")), -16396)
+            .unwrap();
+        assert_eq!(
+            format!("{:?}", str_),
+            "Resource { type: 'STR ', id: -16396, name: Some(\"Caf\u{e9}\"), attributes: ResourceAttributes(), data_len: 2, \
+             data: [01 02] }"
+        );
+    }
+
+    #[test]
+    fn test_type_list_item() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let items: Vec<_> = rsrc.resource_types().collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].resource_type(), FourCC::new(*b"MPSR"));
+        assert_eq!(items[0].count(), 1);
+        assert_eq!(items[1].resource_type(), FourCC::new(*b"BBST"));
+        assert_eq!(items[1].count(), 1);
+        assert_eq!(
+            format!("{:?}", items[0]),
+            "TypeListItem { type: 'MPSR', count: 1 }"
+        );
+
+        assert_eq!(items[0], rsrc.resource_types().next().unwrap());
+        assert_ne!(items[0], items[1]);
+        let unique: HashSet<_> = rsrc.resource_types().chain(items).collect();
+        assert_eq!(unique.len(), 2);
+    }
+
+    #[test]
+    fn test_counts() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        assert_eq!(rsrc.type_count(), 2);
+        assert_eq!(rsrc.resource_count(), 2);
+        assert_eq!(rsrc.map_attributes(), MapAttributes::empty());
+        assert_eq!(rsrc.count_of(FourCC::new(*b"MPSR")), Some(1));
+        assert_eq!(rsrc.count_of(FourCC::new(*b"BBST")), Some(1));
+        assert_eq!(rsrc.count_of(FourCC::new(*b"ICN#")), None);
+        let counts: Vec<_> = rsrc.resource_types().map(|item| item.count()).collect();
+        assert_eq!(counts, [1, 1]);
+
+        let data = resource_fork(&[
+            (*b"STR ", 128, None, b""),
+            (*b"STR ", 129, None, b""),
+            (*b"ICN#", 128, None, b""),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert_eq!(rsrc.type_count(), 2);
+        assert_eq!(rsrc.resource_count(), 3);
+        assert_eq!(rsrc.count_of(FourCC::new(*b"STR ")), Some(2));
+    }
+
+    #[test]
+    fn test_try_resources() {
+        let mut data = resource_fork(&[
+            (*b"STR ", 128, None, b"one"),
+            (*b"STR ", 129, None, b"two"),
+            (*b"STR ", 130, None, b"three"),
+            (*b"ICN#", 128, None, b"icon"),
+        ]);
+        // Point the data of the second resource past the end of the resource data
+        let reference = rsrc_reference_offset(&data, *b"STR ") + 12;
+        data[reference + 5..][..3].copy_from_slice(&[0x00, 0xFF, 0xFF]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = rsrc.resource_types().next().unwrap();
+        assert_eq!(str_.resource_type(), "STR ");
+
+        let results: Vec<_> = rsrc
+            .try_resources(str_)
+            .map(|result| result.map(|resource| resource.id()))
+            .collect();
+        assert_eq!(results, [Ok(128), Err(ParseError::BadEof), Ok(130)]);
+
+        // The lossy iterator stops at the bad resource
+        assert_eq!(rsrc.resources(str_).count(), 1);
+
+        let results: Vec<_> = rsrc
+            .try_iter()
+            .map(|result| {
+                result.map(|resource| (resource.resource_type(), resource.data().to_vec()))
+            })
+            .collect();
+        assert_eq!(
+            results,
+            [
+                Ok((FourCC::new(*b"STR "), b"one".to_vec())),
+                Err(ParseError::BadEof),
+                Ok((FourCC::new(*b"STR "), b"three".to_vec())),
+                Ok((FourCC::new(*b"ICN#"), b"icon".to_vec())),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_try_resources_bad_reference_list() {
+        let mut data = resource_fork(&[(*b"STR ", 128, None, b"one")]);
+        // Claim more resources than the reference list holds
+        let type_list = type_list_offset(&data);
+        data[type_list + 6..][..2].copy_from_slice(&0x7FFF_u16.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let results: Vec<_> = rsrc.try_iter().map(|result| result.err()).collect();
+        assert_eq!(results, [Some(ParseError::BadEof)]);
+    }
+
+    #[test]
+    fn test_try_get_resource() {
+        let mut data = resource_fork(&[
+            (*b"STR ", 128, None, b"one"),
+            (*b"STR ", 129, None, b"two"),
+            (*b"ICN#", 128, None, b"icon"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = FourCC::new(*b"STR ");
+        assert_eq!(
+            rsrc.try_get_resource(str_, 129).unwrap().unwrap().data(),
+            b"two"
+        );
+        assert!(matches!(rsrc.try_get_resource(str_, 130), Ok(None)));
+        assert!(matches!(
+            rsrc.try_get_resource(FourCC::new(*b"PICT"), 128),
+            Ok(None)
+        ));
+
+        // Point the data of the second resource past the end of the resource data
+        let reference = rsrc_reference_offset(&data, *b"STR ") + 12;
+        data[reference + 5..][..3].copy_from_slice(&[0x00, 0xFF, 0xFF]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert!(matches!(
+            rsrc.try_get_resource(str_, 129),
+            Err(ParseError::BadEof)
+        ));
+        assert!(rsrc.get_resource(str_, 129).is_none());
+    }
+
+    #[test]
+    fn test_try_get_resource_bad_reference_list() {
+        let mut data = resource_fork(&[(*b"STR ", 128, None, b"one")]);
+        // Point the reference list past the end of the map
+        let type_list = type_list_offset(&data);
+        data[type_list + 8..][..2].copy_from_slice(&0xFFF0_u16.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = FourCC::new(*b"STR ");
+        assert!(matches!(
+            rsrc.try_get_resource(str_, 128),
+            Err(ParseError::BadOffset)
+        ));
+        assert!(rsrc.get_resource(str_, 128).is_none());
+        // The lossy iterator skips the type, the fallible one reports it
+        assert_eq!(
+            rsrc.resources(rsrc.resource_types().next().unwrap())
+                .count(),
+            0
+        );
+        let results: Vec<_> = rsrc.try_iter().map(|result| result.err()).collect();
+        assert_eq!(results, [Some(ParseError::BadOffset)]);
+        // Other types are unaffected
+        assert!(matches!(
+            rsrc.try_get_resource(FourCC::new(*b"PICT"), 128),
+            Ok(None)
+        ));
+    }
+
+    #[test]
+    fn test_corrupt_resource_length() {
+        let resources: [TestResource<'_>; 3] = [
+            (*b"STR ", 128, Some(b"first"), b"one"),
+            (*b"STR ", 129, None, b"two"),
+            (*b"ICN#", 128, None, b"three"),
+        ];
+        let str_ = FourCC::new(*b"STR ");
+        let icon = FourCC::new(*b"ICN#");
+        let mut data = resource_fork(&resources);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let (resource, issue) = rsrc.try_get_resource_clamped(str_, 128).unwrap().unwrap();
+        assert_eq!(resource.data(), b"one");
+        assert_eq!(issue, None);
+
+        // The length of the first resource runs into the data of the second
+        data[256..260].copy_from_slice(&9_u32.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let found: Vec<_> = rsrc
+            .validate()
+            .iter()
+            .map(|issue| (issue.code, issue.offset, issue.rsrc_type, issue.rsrc_id))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    code::RESOURCE_LENGTH_OVERLAPS,
+                    Some(256),
+                    Some(str_),
+                    Some(128)
+                ),
+                (
+                    code::OVERLAPPING_RESOURCE_DATA,
+                    Some(263),
+                    Some(str_),
+                    Some(129)
+                ),
+            ]
+        );
+        assert_eq!(
+            rsrc.try_get_resource(str_, 128).unwrap().unwrap().data(),
+            b"one\0\0\0\x03tw"
+        );
+        let (resource, issue) = rsrc.try_get_resource_clamped(str_, 128).unwrap().unwrap();
+        assert_eq!(resource.data(), b"one");
+        assert_eq!(resource.data_range(), 260..263);
+        assert_eq!(resource.name_bytes(), Some(&b"first"[..]));
+        let issue = issue.unwrap();
+        assert_eq!(issue.severity, Severity::Warning);
+        assert_eq!(issue.code, code::RESOURCE_LENGTH_OVERLAPS);
+        assert_eq!(issue.offset, Some(256));
+        assert_eq!((issue.rsrc_type, issue.rsrc_id), (Some(str_), Some(128)));
+        // The resource it runs into is unaffected
+        let (resource, issue) = rsrc.try_get_resource_clamped(str_, 129).unwrap().unwrap();
+        assert_eq!((resource.data(), issue), (&b"two"[..], None));
+
+        // A length that overflows a 32-bit usize is clamped to the next resource too
+        data[256..260].copy_from_slice(&u32::MAX.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let (resource, issue) = rsrc.try_get_resource_clamped(str_, 128).unwrap().unwrap();
+        assert_eq!(resource.data(), b"one");
+        assert_eq!(issue.unwrap().code, code::RESOURCE_LENGTH_OVERLAPS);
+
+        // The length of the last resource runs past the resource data area
+        let mut data = resource_fork(&resources);
+        data[270..274].copy_from_slice(&0xFFFF_u32.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert_eq!(
+            rsrc.try_get_resource(icon, 128).err(),
+            Some(ParseError::BadValue)
+        );
+        let (resource, issue) = rsrc.try_get_resource_clamped(icon, 128).unwrap().unwrap();
+        assert_eq!(resource.data(), b"three");
+        assert_eq!(issue.unwrap().code, code::BAD_RESOURCE_LENGTH);
+
+        assert!(matches!(rsrc.try_get_resource_clamped(icon, 129), Ok(None)));
+    }
+
+    #[test]
+    fn test_find_resources() {
+        let data = resource_fork(&[
+            (*b"STR ", 128, Some(b"Owner name"), b"one"),
+            (*b"STR ", -16396, Some(b"Other"), b"two"),
+            (*b"ICN#", -16455, None, b"icon"),
+            (*b"vers", 1, Some(b"Owner version"), b"vers"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+
+        let owners: Vec<_> = rsrc
+            .find_resources(|_, _, name| matches!(name, Some(name) if name.starts_with(b"Owner")))
+            .map(|resource| (resource.resource_type(), resource.id()))
+            .collect();
+        assert_eq!(
+            owners,
+            [(FourCC::new(*b"STR "), 128), (FourCC::new(*b"vers"), 1)]
+        );
+        let owner = rsrc.find_resources(|_, id, _| id == 128).next().unwrap();
+        assert_eq!(owner.name_bytes(), Some(&b"Owner name"[..]));
+        assert_eq!(owner.data(), b"one");
+
+        let negative: Vec<_> = rsrc
+            .find_resources(|_, id, _| id < 0)
+            .map(|resource| resource.id())
+            .collect();
+        assert_eq!(negative, [-16396, -16455]);
+        assert_eq!(rsrc.find_resources(|_, _, _| false).count(), 0);
+
+        // Only the matching resources are read
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let mut seen = Vec::new();
+        let found: Vec<_> = rsrc
+            .find_resources(|rsrc_type, id, _| {
+                seen.push((rsrc_type, id));
+                (1000..2000).contains(&id)
+            })
+            .map(|resource| resource.id())
+            .collect();
+        assert_eq!(found, [1005]);
+        assert_eq!(seen.len(), 2);
+    }
+
+    #[test]
+    fn test_resource_names() {
+        let big = vec![0; 64 * 1024];
+        let data = resource_fork(&[
+            (*b"snd ", 128, Some(b"Wild Eep"), &big),
+            (*b"snd ", 129, None, &big),
+            (*b"PICT", 128, None, b"pict"),
+            (*b"STR ", -16396, Some(b"Caf\x8E"), b"str"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let names: Vec<_> = rsrc
+            .resource_names()
+            .map(|(rsrc_type, id, name)| (rsrc_type.to_string(), id, name))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                (String::from("snd "), 128, String::from("Wild Eep")),
+                (String::from("STR "), -16396, String::from("Caf\u{e9}")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_get_resources() {
+        let data = resource_fork(&[
+            (*b"STR ", 128, None, b"first"),
+            (*b"STR ", 129, None, b"other"),
+            (*b"STR ", 128, None, b"second"),
+            (*b"ICN#", 128, None, b"icon"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = FourCC::new(*b"STR ");
+        let found: Vec<_> = rsrc
+            .get_resources(str_, 128)
+            .map(|resource| resource.data().to_vec())
+            .collect();
+        assert_eq!(found, [b"first".to_vec(), b"second".to_vec()]);
+        assert_eq!(rsrc.get_resource(str_, 128).unwrap().data(), b"first");
+
+        assert_eq!(rsrc.get_resources(str_, 129).count(), 1);
+        assert_eq!(rsrc.get_resources(str_, 130).count(), 0);
+        assert_eq!(rsrc.get_resources(FourCC::new(*b"PICT"), 128).count(), 0);
+    }
+
+    #[test]
+    fn test_resources_of_type() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let bbst = FourCC::new(*b"BBST");
+        assert!(rsrc.has_type(bbst));
+        let resources: Vec<_> = rsrc.resources_of_type(bbst).collect();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].id(), 128);
+        assert_eq!(resources[0].data().len(), 1048);
+
+        let icn = FourCC::new(*b"ICN#");
+        assert!(!rsrc.has_type(icn));
+        assert_eq!(rsrc.resources_of_type(icn).count(), 0);
+
+        let data = resource_fork(&[
+            (*b"ICN#", 128, None, b"one"),
+            (*b"STR ", 128, None, b"str"),
+            (*b"ICN#", 129, None, b"two"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let ids: Vec<_> = rsrc.resources_of_type(icn).map(|r| r.id()).collect();
+        assert_eq!(ids, [128, 129]);
+    }
+
+    #[test]
+    fn test_resources_sorted() {
+        let data = resource_fork(&[
+            (*b"FONT", 1300, None, b"c"),
+            (*b"FONT", -2, None, b"a"),
+            (*b"FONT", 1290, None, b"b"),
+            (*b"FONT", 1300, None, b"d"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let font = FourCC::new(*b"FONT");
+        let item = rsrc.resource_types().next().unwrap();
+
+        let ids: Vec<_> = rsrc.resources(item).map(|resource| resource.id()).collect();
+        assert_eq!(ids, [1300, -2, 1290, 1300]);
+
+        let sorted: Vec<_> = rsrc
+            .resources_sorted(item)
+            .map(|resource| (resource.id(), resource.data().to_vec()))
+            .collect();
+        assert_eq!(
+            sorted,
+            [
+                (-2, b"a".to_vec()),
+                (1290, b"b".to_vec()),
+                (1300, b"c".to_vec()),
+                (1300, b"d".to_vec()),
+            ]
+        );
+
+        assert_eq!(rsrc.ids_of(font), [-2, 1290, 1300, 1300]);
+        assert!(rsrc.ids_of(FourCC::new(*b"NFNT")).is_empty());
+    }
+
+    #[test]
+    fn test_string_resources() {
+        let data = resource_fork(&[
+            (*b"STR ", 128, None, b"\x07Caf\x8e au"),
+            (*b"STR#", 128, None, b"\x00\x02\x03One\x03Two"),
+            (*b"STR#", 129, None, b"\x00\x03\x03One"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = rsrc.get_resource(FourCC::new(*b"STR "), 128).unwrap();
+        let string = str_.as_str_resource().unwrap().unwrap();
+        assert_eq!(string.string(), "Caf\u{e9} au");
+        assert!(str_.as_string_list().is_none());
+
+        let list = rsrc.get_resource(FourCC::new(*b"STR#"), 128).unwrap();
+        assert_eq!(
+            list.as_string_list().unwrap().unwrap().to_strings(),
+            ["One", "Two"]
+        );
+        assert!(list.as_str_resource().is_none());
+
+        let truncated = rsrc.get_resource(FourCC::new(*b"STR#"), 129).unwrap();
+        assert!(matches!(
+            truncated.as_string_list(),
+            Some(Err(ParseError::BadEof))
+        ));
+    }
+
+    #[test]
+    fn test_compressed() {
+        let compressed = compressed_resource(9, 2, 1000, b"compressed");
+        let mut data = resource_fork(&[
+            (*b"CODE", 1, None, &compressed),
+            (*b"CODE", 2, None, b"not compressed"),
+            (*b"CODE", 3, None, &compressed),
+        ]);
+        // Set the compressed attribute of the first two resources
+        let reference = rsrc_reference_offset(&data, *b"CODE");
+        data[reference + 4] = ResourceAttributes::COMPRESSED.bits();
+        data[reference + 12 + 4] = ResourceAttributes::COMPRESSED.bits();
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let code = FourCC::new(*b"CODE");
+
+        let resource = rsrc.get_resource(code, 1).unwrap();
+        assert!(resource.is_compressed());
+        let info = resource.compression_info().unwrap();
+        assert_eq!(info.dcmp_id, 2);
+        assert_eq!(info.decompressed_len, 1000);
+        assert_eq!(usize::from(info.header_len), compressed.len() - 10);
+
+        // Attribute set without the header
+        let resource = rsrc.get_resource(code, 2).unwrap();
+        assert!(resource.attributes().is_compressed());
+        assert!(!resource.is_compressed());
+        assert_eq!(resource.compression_info(), None);
+
+        // Header without the attribute
+        let resource = rsrc.get_resource(code, 3).unwrap();
+        assert!(!resource.is_compressed());
+        assert_eq!(resource.compression_info(), None);
+
+        assert_eq!(rsrc.stats().compressed_count, 1);
+    }
+
+    #[test]
+    fn test_map_attributes() {
+        let mut data = resource_fork(&[(*b"STR ", 128, None, b"")]);
+        let map_offset = usize::num_from(u32::from_be_bytes(data[4..8].try_into().unwrap()));
+        // Attributes follow the copy of the header, next map handle, and file reference number
+        data[map_offset + 22..][..2].copy_from_slice(&0x0080_u16.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert!(rsrc.map_attributes().is_read_only());
+        assert_eq!(rsrc.map_attributes(), MapAttributes::READ_ONLY);
+    }
+
+    #[test]
+    fn test_empty_resource_fork() {
+        let data = read_fixture("tests/Empty resource fork.rsrc");
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert_eq!(rsrc.resource_types().size_hint(), (0, Some(0)));
+        assert_eq!(rsrc.resource_types().count(), 0);
+        assert!(rsrc
+            .get_resource(FourCC(u32::from_be_bytes(*b"BBST")), 128)
+            .is_none());
+    }
+
+    #[test]
+    fn test_reserved_regions() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        assert_eq!(rsrc.reserved_header(), &file.resource_fork_raw()[16..128]);
+        assert_eq!(&rsrc.map_reserved()[..16], &file.resource_fork_raw()[..16]);
+        assert_eq!(
+            &rsrc.map_reserved()[16..],
+            &[0x06, 0xFA, 0x9B, 0x64, 0x12, 0x00]
+        );
+
+        let data = read_fixture("tests/Empty resource fork.rsrc");
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert!(rsrc.reserved_header().iter().all(|&b| b == 0));
+        assert_eq!(rsrc.reserved_header().len(), 112);
+        assert_eq!(&rsrc.map_reserved()[..16], &data[..16]);
+        assert!(rsrc.map_reserved()[16..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_validate() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let issues = rsrc.validate();
+        let codes: Vec<_> = issues.iter().map(|issue| issue.code).collect();
+        assert_eq!(
+            codes,
+            [code::NONZERO_RESERVED_HEADER, code::NONZERO_MAP_RESERVED]
+        );
+        assert_eq!(issues.iter().nth(1).unwrap().offset, Some(1384 + 16));
+        assert_eq!(issues.max_severity(), Some(Severity::Info));
+
+        let data = read_fixture("tests/Empty resource fork.rsrc");
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert!(rsrc.validate().is_empty());
+
+        // Point the MPSR data offset past the end of the data area
+        let mut data = file.resource_fork_raw().to_vec();
+        let mpsr = rsrc_reference_offset(&data, *b"MPSR");
+        data[mpsr + 5..mpsr + 8].copy_from_slice(&[0x00, 0x10, 0x00]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let issues = rsrc.validate();
+        let issue = issues
+            .with_code(code::RESOURCE_DATA_OUT_OF_BOUNDS)
+            .next()
+            .unwrap();
+        assert_eq!(issue.severity, Severity::Error);
+        assert_eq!(issue.offset, Some(256 + 0x1000));
+        assert_eq!(issue.rsrc_type, Some(FourCC(u32::from_be_bytes(*b"MPSR"))));
+        assert_eq!(issue.rsrc_id, Some(1005));
+        assert!(issues.has_errors());
+    }
+
+    #[test]
+    fn test_validate_structure() {
+        let resources: [TestResource<'_>; 3] = [
+            (*b"STR ", 128, Some(b"first"), b"one"),
+            (*b"STR ", 129, None, b"two"),
+            (*b"ICN#", 128, Some(b"icon"), b"three"),
+        ];
+        let data = resource_fork(&resources);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert!(rsrc.validate().is_empty());
+
+        // Duplicate id
+        let data = resource_fork(&[(*b"STR ", 128, None, b"one"), (*b"STR ", 128, None, b"two")]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let issues = rsrc.validate();
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.code, issue.rsrc_id))
+            .collect();
+        assert_eq!(found, [(code::DUPLICATE_RESOURCE, Some(128))]);
+
+        // Point the second resource at the data of the first, leaving its own data unused
+        let mut data = resource_fork(&resources);
+        let reference = rsrc_reference_offset(&data, *b"STR ") + 12;
+        data[reference + 5..][..3].copy_from_slice(&[0, 0, 0]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let issues = rsrc.validate();
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.code, issue.offset, issue.rsrc_id))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (code::OVERLAPPING_RESOURCE_DATA, Some(256), Some(129)),
+                (code::UNUSED_RESOURCE_DATA, Some(256 + 7), None),
+            ]
+        );
+
+        // Map length that includes bytes after the name list
+        let mut data = resource_fork(&resources);
+        let map_offset = usize::num_from(u32::from_be_bytes(data[4..8].try_into().unwrap()));
+        let map_len = data.len() - map_offset;
+        data.extend_from_slice(&[0; 6]);
+        data[12..16].copy_from_slice(&(map_len as u32 + 6).to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let issues = rsrc.validate();
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.code, issue.offset))
+            .collect();
+        assert_eq!(
+            found,
+            [(code::MAP_LENGTH_MISMATCH, Some(map_offset + map_len))]
+        );
+    }
+
+    #[test]
+    fn test_absurd_resource_len() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let mut data = file.resource_fork_raw().to_vec();
+        // MPSR is the first resource in the data area
+        data[256..260].copy_from_slice(&u32::MAX.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let mpsr = FourCC(u32::from_be_bytes(*b"MPSR"));
+        assert!(rsrc.get_resource(mpsr, 1005).is_none());
+        assert_eq!(rsrc.read_resource_data(0), Err(ParseError::BadValue));
+        let issue = rsrc
+            .validate()
+            .with_code(code::BAD_RESOURCE_LENGTH)
+            .next()
+            .cloned()
+            .unwrap();
+        assert_eq!(issue.offset, Some(256));
+        assert_eq!(issue.rsrc_id, Some(1005));
+
+        // Other resources are unaffected
+        let bbst = FourCC(u32::from_be_bytes(*b"BBST"));
+        assert_eq!(rsrc.get_resource(bbst, 128).unwrap().data().len(), 1048);
+
+        // Data length in the fork header larger than the fork
+        let mut data = file.resource_fork_raw().to_vec();
+        data[8..12].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(matches!(
+            ResourceFork::new(&data),
+            Err(ParseError::BadValue)
+        ));
+    }
+
+    #[test]
+    fn test_name_list_out_of_bounds() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        assert!(rsrc.name_list_valid());
+        assert!(rsrc
+            .validate()
+            .with_code(code::NAME_LIST_OUT_OF_BOUNDS)
+            .next()
+            .is_none());
+
+        let mut data = file.resource_fork_raw().to_vec();
+        data[1384 + 26..1384 + 28].copy_from_slice(&0xFFFF_u16.to_be_bytes());
+        let patched = ResourceFork::new(&data).unwrap();
+        assert!(!patched.name_list_valid());
+        let issues = patched.validate();
+        let found: Vec<_> = issues.with_code(code::NAME_LIST_OUT_OF_BOUNDS).collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].offset, Some(1384 + 26));
+
+        // Resource data is unaffected
+        for item in rsrc.resource_types() {
+            for (a, b) in rsrc.resources(item).zip(patched.resources(item)) {
+                assert_eq!(a.id(), b.id());
+                assert_eq!(a.data(), b.data());
+            }
+        }
+
+        // Named resources lose their names, with no additional issues
+        let mut data = resource_fork(&[(*b"STR ", 128, Some(b"Named"), b"\x05named")]);
+        let map_offset = usize::num_from(u32::from_be_bytes(data[4..8].try_into().unwrap()));
+        data[map_offset + 26..map_offset + 28].copy_from_slice(&0xFFFF_u16.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let resource = rsrc
+            .get_resource(FourCC(u32::from_be_bytes(*b"STR ")), 128)
+            .unwrap();
+        assert_eq!(resource.name_bytes(), None);
+        assert_eq!(resource.data(), b"\x05named");
+        let codes: Vec<_> = rsrc.validate().iter().map(|issue| issue.code).collect();
+        assert_eq!(codes, [code::NAME_LIST_OUT_OF_BOUNDS]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let data = read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let mpsr = FourCC(u32::from_be_bytes(*b"MPSR"));
+        let bbst = FourCC(u32::from_be_bytes(*b"BBST"));
+        assert_eq!(
+            rsrc.stats(),
+            ForkStats {
+                types: vec![
+                    TypeStats {
+                        rsrc_type: mpsr,
+                        count: 1,
+                        total_len: 72
+                    },
+                    TypeStats {
+                        rsrc_type: bbst,
+                        count: 1,
+                        total_len: 1048
+                    },
+                ],
+                resource_count: 2,
+                named_count: 0,
+                compressed_count: 0,
+                total_len: 72 + 1048,
+                largest: Some(ResourceSize {
+                    rsrc_type: bbst,
+                    id: 128,
+                    len: 1048
+                }),
+                map_len: 70,
+                gap_len: 0,
+            }
+        );
+
+        let data = resource_fork(&[
+            (*b"STR ", 128, Some(b"greeting"), b"\x05hello"),
+            (*b"STR ", 129, None, b"\x03bye"),
+        ]);
+        let mut data = data;
+        // Grow the data area to leave 10 unused bytes
+        let data_len = u32::from_be_bytes(data[8..12].try_into().unwrap());
+        data[8..12].copy_from_slice(&(data_len + 10).to_be_bytes());
+        let map_offset = usize::num_from(u32::from_be_bytes(data[4..8].try_into().unwrap()));
+        data.splice(map_offset..map_offset, [0; 10]);
+        data[4..8].copy_from_slice(&(map_offset as u32 + 10).to_be_bytes());
+        let stats = ResourceFork::new(&data).unwrap().stats();
+        assert_eq!(stats.resource_count, 2);
+        assert_eq!(stats.named_count, 1);
+        assert_eq!(stats.total_len, 10);
+        assert_eq!(stats.largest.map(|largest| largest.id), Some(128));
+        assert_eq!(stats.gap_len, 10);
+
+        let data = read_fixture("tests/Empty resource fork.rsrc");
+        let stats = ResourceFork::new(&data).unwrap().stats();
+        assert!(stats.types.is_empty());
+        assert_eq!(stats.largest, None);
+    }
+
+    #[test]
+    fn test_empty_name() {
+        let data = resource_fork(&[
+            (*b"STR ", 128, Some(b""), b"\x05empty"),
+            (*b"STR ", 129, None, b"\x07unnamed"),
+            (*b"STR ", 130, Some(b"Named"), b"\x05named"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = FourCC(u32::from_be_bytes(*b"STR "));
+
+        let empty = rsrc.get_resource(str_, 128).unwrap();
+        assert_eq!(empty.name_bytes(), Some(&b""[..]));
+        assert_eq!(empty.name().as_deref(), Some(""));
+        assert_eq!(empty.name_str().as_deref(), Some(""));
+
+        let unnamed = rsrc.get_resource(str_, 129).unwrap();
+        assert_eq!(unnamed.name_bytes(), None);
+        assert_eq!(unnamed.name(), None);
+        assert_eq!(unnamed.name_str(), None);
+
+        let named = rsrc.get_resource(str_, 130).unwrap();
+        assert_eq!(named.name().as_deref(), Some("Named"));
+
+        let issues = rsrc.validate();
+        let empty_names: Vec<_> = issues
+            .with_code(code::EMPTY_NAME)
+            .map(|issue| issue.rsrc_id)
+            .collect();
+        assert_eq!(empty_names, [Some(128)]);
+        assert_eq!(issues.max_severity(), Some(Severity::Info));
+    }
+
+    #[test]
+    fn test_name_with_policy() {
+        let data = resource_fork(&[
+            (*b"STR ", 128, Some(b"Bad\xADName"), b""),
+            (*b"STR ", 129, None, b""),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let str_ = FourCC(u32::from_be_bytes(*b"STR "));
+        let named = rsrc.get_resource(str_, 128).unwrap();
+        assert_eq!(named.name().as_deref(), Some("Bad\u{FFFD}Name"));
+        assert_eq!(
+            named.name_with_policy(DecodePolicy::Replace('?')),
+            Ok(Some(String::from("Bad?Name")))
+        );
+        assert_eq!(
+            named.name_with_policy(DecodePolicy::Skip),
+            Ok(Some(String::from("BadName")))
+        );
+        assert_eq!(
+            named.name_with_policy(DecodePolicy::Error),
+            Err(MacRomanError {
+                position: 3,
+                byte: 0xAD
+            })
+        );
+
+        let unnamed = rsrc.get_resource(str_, 129).unwrap();
+        assert_eq!(unnamed.name_with_policy(DecodePolicy::Error), Ok(None));
+    }
+
+    #[test]
+    fn test_implausible_type() {
+        let mut data = resource_fork(&[
+            (*b"STR ", 128, None, b"\x05hello"),
+            (*b"TEXT", 128, None, b"text"),
+        ]);
+        // Replace the first type list entry with a zeroed type and a count of 0xFFFF
+        let map_offset = u32::from_be_bytes(data[4..8].try_into().unwrap()) as usize;
+        let item = map_offset + 28 + 2;
+        data[item..item + 6].copy_from_slice(&[0, 0, 0, 0, 0xFF, 0xFF]);
+
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let items: Vec<_> = rsrc.resource_types().collect();
+        assert_eq!(items.len(), 2);
+        assert!(!items[0].is_plausible());
+        assert!(items[1].is_plausible());
+        assert_eq!(rsrc.resources(items[0]).count(), 0);
+
+        let issues = rsrc.validate();
+        let found: Vec<_> = issues
+            .iter()
+            .map(|issue| (issue.code, issue.rsrc_type))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (code::IMPLAUSIBLE_TYPE, Some(FourCC(0))),
+                (code::EMPTY_TYPE, Some(FourCC(0))),
+                // The data of the STR resource is no longer referenced
+                (code::UNUSED_RESOURCE_DATA, None),
+            ]
+        );
+    }
+
+    /// Find the offset of the first reference list entry for `rsrc_type` in a resource fork
+    fn rsrc_reference_offset(fork: &[u8], rsrc_type: [u8; 4]) -> usize {
+        let type_list = type_list_offset(fork);
+        let num_types =
+            usize::from(u16::from_be_bytes([fork[type_list], fork[type_list + 1]]).wrapping_add(1));
+        (0..num_types)
+            .map(|i| type_list + 2 + i * 8)
+            .find(|&item| fork[item..item + 4] == rsrc_type)
+            .map(|item| {
+                type_list + usize::from(u16::from_be_bytes([fork[item + 6], fork[item + 7]]))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn test_attributes() {
+        // ID 128, no name, attributes 0x5C, data offset 0
+        let data = [0x00, 0x80, 0xFF, 0xFF, 0x5C, 0, 0, 0, 0, 0, 0, 0];
+        let item = ReadScope::new(&data).read::<ReferenceListItem>().unwrap();
+        let resource = Resource {
+            rsrc_type: FourCC::new(*b"CODE"),
+            id: item.id,
+            name: None,
+            attributes: item.attributes,
+            data_offset: 0,
+            data: &[],
+        };
+        let attributes = resource.attributes();
+        assert_eq!(attributes.bits(), 0x5C);
+        assert!(attributes.contains(ResourceAttributes::SYS_HEAP | ResourceAttributes::LOCKED));
+        assert!(resource.is_protected());
+        assert!(resource.is_preload());
+        assert!(!resource.is_compressed());
+    }
+
+    #[test]
+    fn test_name_str() {
+        let ascii = Resource {
+            rsrc_type: FourCC::new(*b"STR "),
+            id: 128,
+            name: Some(b"Owner"),
+            attributes: 0,
+            data_offset: 0,
+            data: &[],
+        };
+        assert!(matches!(ascii.name_str(), Some(Cow::Borrowed("Owner"))));
+
+        let accented = Resource {
+            rsrc_type: FourCC::new(*b"STR "),
+            id: 129,
+            name: Some(b"Caf\x8E"),
+            attributes: 0,
+            data_offset: 0,
+            data: &[],
+        };
+        assert!(matches!(accented.name_str(), Some(Cow::Owned(_))));
+        assert_eq!(accented.name_str().as_deref(), Some("Café"));
+        assert_eq!(accented.name().as_deref(), Some("Café"));
+
+        let unnamed = Resource {
+            rsrc_type: FourCC::new(*b"STR "),
+            id: 130,
+            name: None,
+            attributes: 0,
+            data_offset: 0,
+            data: &[],
+        };
+        assert!(unnamed.name_str().is_none());
+    }
 }
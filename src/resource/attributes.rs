@@ -0,0 +1,281 @@
+//! Resource and resource map attribute flags.
+//!
+//! ### Reference:
+//!
+//! [Inside Macintosh: More Macintosh Toolbox](https://archive.org/details/inside-macintosh-1992-1994/1993-more_macintosh_toolbox/)
+//! Resource Attributes 1-37 (pp. 67), Resource Map 1-124 (pp. 154)
+
+use core::fmt;
+use core::ops::BitOr;
+
+/// The attributes of a resource, from its entry in the reference list.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceAttributes(u8);
+
+/// The attributes of a resource fork, from its resource map.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MapAttributes(u16);
+
+impl ResourceAttributes {
+    /// The resource is loaded into the system heap (`resSysHeap`)
+    pub const SYS_HEAP: ResourceAttributes = ResourceAttributes(0x40);
+    /// The resource can be purged from memory (`resPurgeable`)
+    pub const PURGEABLE: ResourceAttributes = ResourceAttributes(0x20);
+    /// The resource can't be moved in memory (`resLocked`)
+    pub const LOCKED: ResourceAttributes = ResourceAttributes(0x10);
+    /// The resource can't be changed (`resProtected`)
+    pub const PROTECTED: ResourceAttributes = ResourceAttributes(0x08);
+    /// The resource is loaded when the file is opened (`resPreload`)
+    pub const PRELOAD: ResourceAttributes = ResourceAttributes(0x04);
+    /// The resource has been changed and not yet written to disk (`resChanged`)
+    pub const CHANGED: ResourceAttributes = ResourceAttributes(0x02);
+    /// The resource data is compressed, used by System 7 resource compression
+    pub const COMPRESSED: ResourceAttributes = ResourceAttributes(0x01);
+
+    const NAMED: [(ResourceAttributes, &'static str); 7] = [
+        (Self::SYS_HEAP, "SYS_HEAP"),
+        (Self::PURGEABLE, "PURGEABLE"),
+        (Self::LOCKED, "LOCKED"),
+        (Self::PROTECTED, "PROTECTED"),
+        (Self::PRELOAD, "PRELOAD"),
+        (Self::CHANGED, "CHANGED"),
+        (Self::COMPRESSED, "COMPRESSED"),
+    ];
+
+    /// Construct from the raw attributes byte.
+    pub const fn from_bits(bits: u8) -> ResourceAttributes {
+        ResourceAttributes(bits)
+    }
+
+    /// No attributes set.
+    pub const fn empty() -> ResourceAttributes {
+        ResourceAttributes(0)
+    }
+
+    /// The raw attributes byte.
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    /// Returns `true` if no attributes are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all the attributes in `other` are set.
+    pub const fn contains(self, other: ResourceAttributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if the resource is loaded into the system heap.
+    pub const fn is_sys_heap(self) -> bool {
+        self.contains(Self::SYS_HEAP)
+    }
+
+    /// Returns `true` if the resource can be purged from memory.
+    pub const fn is_purgeable(self) -> bool {
+        self.contains(Self::PURGEABLE)
+    }
+
+    /// Returns `true` if the resource can't be moved in memory.
+    pub const fn is_locked(self) -> bool {
+        self.contains(Self::LOCKED)
+    }
+
+    /// Returns `true` if the resource can't be changed.
+    pub const fn is_protected(self) -> bool {
+        self.contains(Self::PROTECTED)
+    }
+
+    /// Returns `true` if the resource is loaded when the file is opened.
+    pub const fn is_preload(self) -> bool {
+        self.contains(Self::PRELOAD)
+    }
+
+    /// Returns `true` if the resource has been changed and not written to disk.
+    pub const fn is_changed(self) -> bool {
+        self.contains(Self::CHANGED)
+    }
+
+    /// Returns `true` if the resource data is compressed.
+    pub const fn is_compressed(self) -> bool {
+        self.contains(Self::COMPRESSED)
+    }
+}
+
+impl BitOr for ResourceAttributes {
+    type Output = ResourceAttributes;
+
+    fn bitor(self, rhs: ResourceAttributes) -> ResourceAttributes {
+        ResourceAttributes(self.0 | rhs.0)
+    }
+}
+
+impl MapAttributes {
+    /// The resource fork can't be written to (`mapReadOnly`)
+    pub const READ_ONLY: MapAttributes = MapAttributes(0x0080);
+    /// The resource fork should be compacted when it's updated (`mapCompact`)
+    pub const COMPACT: MapAttributes = MapAttributes(0x0040);
+    /// The resource map has been changed and should be written to disk (`mapChanged`)
+    pub const CHANGED: MapAttributes = MapAttributes(0x0020);
+
+    const NAMED: [(MapAttributes, &'static str); 3] = [
+        (Self::READ_ONLY, "READ_ONLY"),
+        (Self::COMPACT, "COMPACT"),
+        (Self::CHANGED, "CHANGED"),
+    ];
+
+    /// Construct from the raw 16-bit attributes value.
+    pub const fn from_bits(bits: u16) -> MapAttributes {
+        MapAttributes(bits)
+    }
+
+    /// No attributes set.
+    pub const fn empty() -> MapAttributes {
+        MapAttributes(0)
+    }
+
+    /// The raw 16-bit attributes value.
+    pub const fn bits(self) -> u16 {
+        self.0
+    }
+
+    /// Returns `true` if no attributes are set.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns `true` if all the attributes in `other` are set.
+    pub const fn contains(self, other: MapAttributes) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Returns `true` if the resource fork is read-only.
+    pub const fn is_read_only(self) -> bool {
+        self.contains(Self::READ_ONLY)
+    }
+}
+
+impl BitOr for MapAttributes {
+    type Output = MapAttributes;
+
+    fn bitor(self, rhs: MapAttributes) -> MapAttributes {
+        MapAttributes(self.0 | rhs.0)
+    }
+}
+
+/// Lists the names of the set attributes, followed by any other bits in hex, e.g.
+/// `MapAttributes(READ_ONLY)`.
+impl fmt::Debug for MapAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MapAttributes(")?;
+        let mut remaining = self.0;
+        for (flag, name) in Self::NAMED {
+            if self.contains(flag) {
+                if remaining != self.0 {
+                    f.write_str(" | ")?;
+                }
+                f.write_str(name)?;
+                remaining &= !flag.0;
+            }
+        }
+        if remaining != 0 {
+            if remaining != self.0 {
+                f.write_str(" | ")?;
+            }
+            write!(f, "{:#06x}", remaining)?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Lists the names of the set attributes, followed by any other bits in hex, e.g.
+/// `ResourceAttributes(PURGEABLE | PRELOAD)`.
+impl fmt::Debug for ResourceAttributes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ResourceAttributes(")?;
+        let mut remaining = self.0;
+        for (flag, name) in Self::NAMED {
+            if self.contains(flag) {
+                if remaining != self.0 {
+                    f.write_str(" | ")?;
+                }
+                f.write_str(name)?;
+                remaining &= !flag.0;
+            }
+        }
+        if remaining != 0 {
+            if remaining != self.0 {
+                f.write_str(" | ")?;
+            }
+            write!(f, "{:#04x}", remaining)?;
+        }
+        f.write_str(")")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_attributes() {
+        let attributes = ResourceAttributes::from_bits(0x5C);
+        assert!(attributes.is_sys_heap());
+        assert!(attributes.is_locked());
+        assert!(attributes.is_protected());
+        assert!(attributes.is_preload());
+        assert!(!attributes.is_purgeable());
+        assert!(!attributes.is_changed());
+        assert!(!attributes.is_compressed());
+        assert_eq!(
+            attributes,
+            ResourceAttributes::SYS_HEAP
+                | ResourceAttributes::LOCKED
+                | ResourceAttributes::PROTECTED
+                | ResourceAttributes::PRELOAD
+        );
+        assert_eq!(attributes.bits(), 0x5C);
+        assert!(ResourceAttributes::empty().is_empty());
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(
+            format!("{:?}", ResourceAttributes::from_bits(0x5C)),
+            "ResourceAttributes(SYS_HEAP | LOCKED | PROTECTED | PRELOAD)"
+        );
+        assert_eq!(
+            format!("{:?}", ResourceAttributes::from_bits(0x81)),
+            "ResourceAttributes(COMPRESSED | 0x80)"
+        );
+        assert_eq!(
+            format!("{:?}", ResourceAttributes::empty()),
+            "ResourceAttributes()"
+        );
+        assert_eq!(
+            format!("{:?}", MapAttributes::from_bits(0x00A1)),
+            "MapAttributes(READ_ONLY | CHANGED | 0x0001)"
+        );
+    }
+
+    #[test]
+    fn test_map_attributes() {
+        for (bits, flag) in [
+            (0x0080, MapAttributes::READ_ONLY),
+            (0x0040, MapAttributes::COMPACT),
+            (0x0020, MapAttributes::CHANGED),
+        ] {
+            let attributes = MapAttributes::from_bits(bits);
+            assert_eq!(attributes, flag);
+            assert_eq!(attributes.bits(), bits);
+            assert!(attributes.contains(flag));
+            assert_eq!(attributes.is_read_only(), bits == 0x0080);
+        }
+        let all = MapAttributes::READ_ONLY | MapAttributes::COMPACT | MapAttributes::CHANGED;
+        assert_eq!(all.bits(), 0x00E0);
+        assert!(MapAttributes::empty().is_empty());
+    }
+}
@@ -0,0 +1,107 @@
+//! Detection of compressed resources.
+//!
+//! System 7 can store resources compressed. The compressed data starts with an extended resource
+//! header that identifies the `'dcmp'` resource used to decompress it and gives the length of
+//! the decompressed data.
+//!
+//! ### Reference:
+//!
+//! [Resource Compression](http://preserve.mactech.com/articles/mactech/Vol.09/09.01/ResCompression/index.html),
+//! MacTech Volume 9, Issue 1
+
+use crate::binary::read::ReadScope;
+use crate::error::ParseError;
+
+/// Signature at the start of the extended resource header
+const SIGNATURE: u32 = 0xA89F6572;
+
+/// Attribute in the extended resource header indicating the data is compressed
+const COMPRESSED: u8 = 0x01;
+
+/// Header version used by `'dcmp'` 0 (DonnBits)
+const VERSION_8: u8 = 8;
+
+/// Header version used by `'dcmp'` 2 (GreggyBits)
+const VERSION_9: u8 = 9;
+
+/// Details of a compressed resource from its extended resource header.
+///
+/// Returned by [Resource::compression_info](crate::resource::Resource::compression_info).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CompressionInfo {
+    /// Version of the extended resource header, 8 or 9
+    pub header_version: u8,
+    /// Length of the header that precedes the compressed data
+    pub header_len: u16,
+    /// Length of the data once decompressed
+    pub decompressed_len: u32,
+    /// ID of the `'dcmp'` resource that decompresses the data
+    pub dcmp_id: i16,
+}
+
+impl CompressionInfo {
+    /// Read the extended resource header at the start of `data`.
+    pub(crate) fn read(data: &[u8]) -> Result<CompressionInfo, ParseError> {
+        let mut ctxt = ReadScope::new(data).ctxt();
+        let signature = ctxt.read_u32be()?;
+        ctxt.check(signature == SIGNATURE)?;
+        let header_len = ctxt.read_u16be()?;
+        let header_version = ctxt.read_u8()?;
+        let attributes = ctxt.read_u8()?;
+        ctxt.check(attributes & COMPRESSED != 0)?;
+        let decompressed_len = ctxt.read_u32be()?;
+        ctxt.check_version(header_version == VERSION_8 || header_version == VERSION_9)?;
+        if header_version == VERSION_8 {
+            // Working buffer fractional size and expansion buffer size precede the ID
+            let _ = ctxt.read_slice(2)?;
+        }
+        let dcmp_id = ctxt.read_i16be()?;
+        Ok(CompressionInfo {
+            header_version,
+            header_len,
+            decompressed_len,
+            dcmp_id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::compressed_resource;
+
+    #[test]
+    fn test_read() {
+        let data = compressed_resource(VERSION_8, 0, 1234, b"data");
+        assert_eq!(
+            CompressionInfo::read(&data),
+            Ok(CompressionInfo {
+                header_version: 8,
+                header_len: 18,
+                decompressed_len: 1234,
+                dcmp_id: 0,
+            })
+        );
+
+        let data = compressed_resource(VERSION_9, 2, 0x10000, b"data");
+        let info = CompressionInfo::read(&data).unwrap();
+        assert_eq!(info.dcmp_id, 2);
+        assert_eq!(info.decompressed_len, 0x10000);
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let mut data = compressed_resource(VERSION_9, 2, 100, b"");
+        assert!(CompressionInfo::read(&data[..10]).is_err());
+
+        data[6] = 10;
+        assert_eq!(CompressionInfo::read(&data), Err(ParseError::BadVersion));
+
+        data[6] = VERSION_9;
+        data[7] = 0;
+        assert_eq!(CompressionInfo::read(&data), Err(ParseError::BadValue));
+
+        data[0] = 0;
+        assert_eq!(CompressionInfo::read(&data), Err(ParseError::BadValue));
+    }
+}
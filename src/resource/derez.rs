@@ -0,0 +1,181 @@
+//! A textual listing of a resource fork in the style of DeRez.
+//!
+//! DeRez is the resource decompiler from the Macintosh Programmer's Workshop (MPW). Its output is
+//! source for the Rez resource compiler.
+
+use core::fmt::{self, Write};
+
+use super::{Resource, ResourceAttributes, ResourceFork};
+use crate::FourCC;
+
+/// Bytes of resource data on each line
+const BYTES_PER_LINE: usize = 16;
+
+/// Column of the comment holding the text of the data, after the leading tab
+const COMMENT_COLUMN: usize = 54;
+
+/// Attributes that have a Rez keyword
+const KEYWORDS: [(ResourceAttributes, &str); 6] = [
+    (ResourceAttributes::SYS_HEAP, "sysheap"),
+    (ResourceAttributes::PURGEABLE, "purgeable"),
+    (ResourceAttributes::LOCKED, "locked"),
+    (ResourceAttributes::PROTECTED, "protected"),
+    (ResourceAttributes::PRELOAD, "preload"),
+    (ResourceAttributes::CHANGED, "changed"),
+];
+
+/// A listing of the resources in a resource fork like the default output of Apple's DeRez tool.
+///
+/// Each resource is written as a Rez `data` statement with its data in hex, 16 bytes per line,
+/// followed by a comment showing the printable ASCII characters. The listing is written as it is
+/// formatted, without building it in memory first. Resources that can't be read are left out.
+///
+/// Created with [ResourceFork::derez].
+pub struct DeRez<'a, 'rsrc> {
+    pub(super) fork: &'a ResourceFork<'rsrc>,
+}
+
+impl<'rsrc, 'a: 'rsrc> fmt::Display for DeRez<'a, 'rsrc> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for item in self.fork.resource_types() {
+            for resource in self.fork.resources(item) {
+                write_resource(f, &resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn write_resource(f: &mut fmt::Formatter<'_>, resource: &Resource<'_>) -> fmt::Result {
+    f.write_str("data '")?;
+    write_type(f, resource.resource_type())?;
+    write!(f, "' ({}", resource.id())?;
+    if let Some(name) = resource.name_bytes() {
+        f.write_str(", \"")?;
+        for &byte in name {
+            write_char(f, byte, b'"')?;
+        }
+        f.write_char('"')?;
+    }
+    write_attributes(f, resource.attributes())?;
+    f.write_str(") {\n")?;
+    for line in resource.data().chunks(BYTES_PER_LINE) {
+        write_line(f, line)?;
+    }
+    f.write_str("};\n\n")
+}
+
+fn write_type(f: &mut fmt::Formatter<'_>, rsrc_type: FourCC) -> fmt::Result {
+    rsrc_type
+        .as_bytes()
+        .iter()
+        .try_for_each(|&byte| write_char(f, byte, b'\''))
+}
+
+/// Write a byte of a Rez string or type literal delimited by `quote`
+fn write_char(f: &mut fmt::Formatter<'_>, byte: u8, quote: u8) -> fmt::Result {
+    match byte {
+        b'\\' => f.write_str("\\\\"),
+        _ if byte == quote => write!(f, "\\{}", char::from(byte)),
+        0x20..=0x7E => f.write_char(char::from(byte)),
+        _ => write!(f, "\\${byte:02X}"),
+    }
+}
+
+fn write_attributes(f: &mut fmt::Formatter<'_>, attributes: ResourceAttributes) -> fmt::Result {
+    if attributes.is_empty() {
+        return Ok(());
+    }
+    // Rez has no keyword for the compressed attribute or the unused high bit
+    let named = KEYWORDS
+        .iter()
+        .fold(ResourceAttributes::empty(), |acc, &(attr, _)| acc | attr);
+    if attributes.bits() & !named.bits() != 0 {
+        return write!(f, ", ${:02X}", attributes.bits());
+    }
+    for (attr, keyword) in KEYWORDS {
+        if attributes.contains(attr) {
+            write!(f, ", {keyword}")?;
+        }
+    }
+    Ok(())
+}
+
+fn write_line(f: &mut fmt::Formatter<'_>, line: &[u8]) -> fmt::Result {
+    f.write_str("\t$\"")?;
+    for (i, pair) in line.chunks(2).enumerate() {
+        if i > 0 {
+            f.write_char(' ')?;
+        }
+        for byte in pair {
+            write!(f, "{byte:02X}")?;
+        }
+    }
+    f.write_char('"')?;
+    // The hex digits, the spaces between each pair of bytes, and the `$""` around them
+    let width = 2 * line.len() + (line.len() - 1) / 2 + 3;
+    write!(
+        f,
+        "{:pad$}/* ",
+        "",
+        pad = COMMENT_COLUMN.saturating_sub(width)
+    )?;
+    let mut prev = 0;
+    for &byte in line {
+        // Avoid ending the comment early
+        let printable = matches!(byte, 0x20..=0x7E) && !(prev == b'*' && byte == b'/');
+        f.write_char(if printable { char::from(byte) } else { '.' })?;
+        prev = byte;
+    }
+    f.write_str(" */\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{read_fixture, resource_fork};
+
+    #[test]
+    fn test_derez() {
+        let data = read_fixture("tests/Text File.rsrc");
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let expected = String::from_utf8(read_fixture("tests/Text File.r")).unwrap();
+        assert_eq!(rsrc.derez().to_string(), expected);
+    }
+
+    #[test]
+    fn test_derez_escapes() {
+        let data = resource_fork(&[
+            (*b"\xA9'\\x", -1, Some(b"Say \"hi\"\x8E"), b"*/abc"),
+            (*b"STR ", 128, None, b""),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert_eq!(
+            rsrc.derez().to_string(),
+            "data '\\$A9\\'\\\\x' (-1, \"Say \\\"hi\\\"\\$8E\") {\n\
+             \t$\"2A2F 6162 63\"                                       /* *.abc */\n\
+             };\n\
+             \n\
+             data 'STR ' (128) {\n\
+             };\n\
+             \n"
+        );
+    }
+
+    #[test]
+    fn test_write_attributes() {
+        struct Attrs(ResourceAttributes);
+
+        impl fmt::Display for Attrs {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_attributes(f, self.0)
+            }
+        }
+
+        let attrs = ResourceAttributes::PURGEABLE | ResourceAttributes::PRELOAD;
+        assert_eq!(Attrs(attrs).to_string(), ", purgeable, preload");
+        assert_eq!(Attrs(ResourceAttributes::empty()).to_string(), "");
+        let attrs = ResourceAttributes::LOCKED | ResourceAttributes::COMPRESSED;
+        assert_eq!(Attrs(attrs).to_string(), ", $11");
+    }
+}
@@ -0,0 +1,177 @@
+//! An index of a resource fork for repeated lookups.
+
+use std::collections::HashMap;
+use std::string::String;
+
+use crate::error::ParseError;
+use crate::macroman::decode_macroman;
+use crate::FourCC;
+
+use super::{ReferenceListItem, Resource, ResourceFork};
+
+/// An index of the resources in a resource fork by type and id, and by type and name.
+///
+/// [ResourceFork::get_resource] scans the resource map on each call. The index reads the map once
+/// so that each lookup is a hash table lookup, which is worthwhile when looking up many
+/// resources. The resource data is only read when a resource is looked up.
+///
+/// Created with [ResourceFork::index].
+pub struct ResourceIndex<'a, 'rsrc> {
+    fork: &'a ResourceFork<'rsrc>,
+    items: HashMap<(FourCC, i16), ReferenceListItem>,
+    names: HashMap<(FourCC, String), i16>,
+}
+
+impl<'a, 'rsrc> ResourceIndex<'a, 'rsrc> {
+    pub(super) fn new(fork: &'a ResourceFork<'rsrc>) -> Result<Self, ParseError> {
+        let mut items = HashMap::with_capacity(fork.resource_count());
+        let mut names = HashMap::new();
+        for type_item in fork.map.type_list.list.iter() {
+            let reference_list = type_item.reference_list(fork.map.type_list.scope)?;
+            for item in reference_list.list.iter() {
+                // Like the reference list search, the first of any duplicates wins
+                if let Some(name) = item.name_offset.and_then(|offset| fork.read_name(offset)) {
+                    names
+                        .entry((type_item.rsrc_type, decode_macroman(name).into_owned()))
+                        .or_insert(item.id);
+                }
+                items.entry((type_item.rsrc_type, item.id)).or_insert(item);
+            }
+        }
+        Ok(ResourceIndex { fork, items, names })
+    }
+
+    /// The number of distinct resources in the index.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the resource fork holds no resources.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the resource with the supplied type and id.
+    ///
+    /// Returns `Ok(None)` if there is no such resource, and an error if its data lies outside the
+    /// resource fork.
+    pub fn get(&self, rsrc_type: FourCC, rsrc_id: i16) -> Result<Option<Resource<'a>>, ParseError> {
+        match self.items.get(&(rsrc_type, rsrc_id)) {
+            Some(item) => self.fork.try_read_resource(rsrc_type, item).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the resource with the supplied type and name.
+    ///
+    /// Names are decoded from MacRoman and compared exactly. Returns `Ok(None)` if there is no
+    /// such resource, and an error if its data lies outside the resource fork.
+    pub fn get_named(
+        &self,
+        rsrc_type: FourCC,
+        name: &str,
+    ) -> Result<Option<Resource<'a>>, ParseError> {
+        // Avoid allocating a key for the lookup when there are no names at all
+        if self.names.is_empty() {
+            return Ok(None);
+        }
+        match self.names.get(&(rsrc_type, String::from(name))) {
+            Some(&rsrc_id) => self.get(rsrc_type, rsrc_id),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::{resource_fork, type_list_offset};
+
+    #[test]
+    fn test_index() {
+        let data = resource_fork(&[
+            (*b"STR ", 128, Some(b"Greeting"), b"hello"),
+            (*b"STR ", 129, None, b"world"),
+            (*b"ICN#", 128, Some(b"Caf\x8E"), b"icon"),
+        ]);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let index = rsrc.index().unwrap();
+        let str_ = FourCC::new(*b"STR ");
+        let icn = FourCC::new(*b"ICN#");
+        assert_eq!(index.len(), 3);
+
+        let resource = index.get(str_, 129).unwrap().unwrap();
+        assert_eq!(resource.data(), b"world");
+        assert!(index.get(str_, 130).unwrap().is_none());
+        assert!(index.get(FourCC::new(*b"PICT"), 128).unwrap().is_none());
+
+        let resource = index.get_named(icn, "Caf\u{e9}").unwrap().unwrap();
+        assert_eq!(resource.id(), 128);
+        assert_eq!(resource.data(), b"icon");
+        assert!(index.get_named(str_, "Caf\u{e9}").unwrap().is_none());
+        assert!(index.get_named(str_, "greeting").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parity() {
+        let data = crate::test::read_fixture("tests/Text File.bin");
+        let file = crate::parse(&data).unwrap();
+        let rsrc = file.resource_fork().unwrap().unwrap();
+        let index = rsrc.index().unwrap();
+        assert_eq!(index.len(), rsrc.resource_count());
+        for resource in rsrc.try_iter().map(Result::unwrap) {
+            let rsrc_type = resource.resource_type();
+            let indexed = index.get(rsrc_type, resource.id()).unwrap().unwrap();
+            let linear = rsrc.get_resource(rsrc_type, resource.id()).unwrap();
+            assert_eq!(indexed.data(), linear.data());
+            assert_eq!(indexed.name(), linear.name());
+            assert_eq!(indexed.attributes(), linear.attributes());
+        }
+    }
+
+    #[test]
+    fn test_many_resources() {
+        let resources: Vec<_> = (0..1000_i16)
+            .map(|id| {
+                let rsrc_type = if id % 2 == 0 { *b"snd " } else { *b"PICT" };
+                (rsrc_type, id, id.to_be_bytes())
+            })
+            .collect();
+        let names: Vec<_> = (0..1000).map(|id| format!("Resource {id}")).collect();
+        let entries: Vec<_> = resources
+            .iter()
+            .zip(&names)
+            .map(|((rsrc_type, id, data), name)| {
+                (*rsrc_type, *id, Some(name.as_bytes()), &data[..])
+            })
+            .collect();
+        let data = resource_fork(&entries);
+        let rsrc = ResourceFork::new(&data).unwrap();
+        let index = rsrc.index().unwrap();
+        assert_eq!(index.len(), 1000);
+
+        for (rsrc_type, id, data) in &resources {
+            let rsrc_type = FourCC::new(*rsrc_type);
+            let resource = index.get(rsrc_type, *id).unwrap().unwrap();
+            assert_eq!(resource.data(), data);
+            let linear = rsrc.get_resource(rsrc_type, *id).unwrap();
+            assert_eq!(resource.data(), linear.data());
+
+            let resource = index
+                .get_named(rsrc_type, &format!("Resource {id}"))
+                .unwrap()
+                .unwrap();
+            assert_eq!(resource.id(), *id);
+        }
+    }
+
+    #[test]
+    fn test_bad_reference_list() {
+        let mut data = resource_fork(&[(*b"STR ", 128, None, b"one")]);
+        // Point the reference list past the end of the map
+        let type_list = type_list_offset(&data);
+        data[type_list + 8..][..2].copy_from_slice(&0xFFF0_u16.to_be_bytes());
+        let rsrc = ResourceFork::new(&data).unwrap();
+        assert!(matches!(rsrc.index(), Err(ParseError::BadOffset)));
+    }
+}
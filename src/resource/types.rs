@@ -0,0 +1,179 @@
+//! Decoders for the contents of well-known resource types.
+//!
+//! The resource fork parser in the parent module hands out the raw bytes of each resource. The
+//! types in this module interpret those bytes for specific resource types.
+//!
+//! Each decoder implements [TypedResource]. [supported] lists them all and [decode_any] decodes
+//! a resource with the decoder for its type.
+
+#[cfg(not(feature = "no_std"))]
+mod balloon;
+mod code;
+mod color;
+mod desktop;
+mod drvr;
+mod geometry;
+mod mctb;
+mod strings;
+mod vers;
+
+#[cfg(not(feature = "no_std"))]
+pub use balloon::{BalloonHelp, HelpString};
+pub use code::looks_like_68k_code;
+pub use color::RgbColor;
+pub use desktop::{Appl, ApplEntries, ApplEntry, Fcmt};
+pub use drvr::Drvr;
+pub use geometry::{Point, Rect};
+pub use mctb::{Mctb, MctbEntry, MenuColorTarget};
+pub use strings::{Str, StringList, StringListIter};
+pub use vers::{DevelopmentStage, VersionResource};
+
+use crate::error::ParseError;
+use crate::FourCC;
+
+/// A decoder for resources of a specific type.
+pub trait TypedResource<'a>: Sized {
+    /// The resource type this decoder handles.
+    const TYPE: FourCC;
+
+    /// Decode the data of a resource of type [Self::TYPE].
+    fn parse(data: &'a [u8]) -> Result<Self, ParseError>;
+}
+
+/// Information about a resource decoder.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DecoderInfo {
+    /// The resource type that can be decoded
+    pub type_code: FourCC,
+    /// Name of the decoded type
+    pub name: &'static str,
+    /// The kind of data that the decoder produces
+    pub output: OutputKind,
+}
+
+/// The kind of data produced by a decoder.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum OutputKind {
+    /// Text
+    Text,
+    /// An image
+    Image,
+    /// Sound
+    Audio,
+    /// Structured data, such as a list of records
+    Structured,
+}
+
+/// Defines the [TypedResource] impls, the [Decoded] enum, and the decoder registry from one
+/// list so that they can't get out of sync.
+macro_rules! decoders {
+    ($($ty:ident => $code:literal, $output:ident;)*) => {
+        $(
+            impl<'a> TypedResource<'a> for $ty<'a> {
+                const TYPE: FourCC = FourCC::new(*$code);
+
+                fn parse(data: &'a [u8]) -> Result<Self, ParseError> {
+                    $ty::parse(data)
+                }
+            }
+        )*
+
+        /// A decoded resource, returned by [decode_any].
+        #[derive(Debug)]
+        pub enum Decoded<'a> {
+            $(
+                #[doc = concat!("A decoded [", stringify!($ty), "] resource")]
+                $ty($ty<'a>),
+            )*
+        }
+
+        static SUPPORTED: &[DecoderInfo] = &[
+            $(
+                DecoderInfo {
+                    type_code: <$ty<'_> as TypedResource<'_>>::TYPE,
+                    name: stringify!($ty),
+                    output: OutputKind::$output,
+                },
+            )*
+        ];
+
+        /// Decode `data` with the decoder for `rsrc_type`.
+        ///
+        /// Returns `None` if there's no decoder for the type.
+        pub fn decode_any(rsrc_type: FourCC, data: &[u8]) -> Option<Result<Decoded<'_>, ParseError>> {
+            $(
+                if rsrc_type == <$ty<'_> as TypedResource<'_>>::TYPE {
+                    return Some(<$ty<'_> as TypedResource<'_>>::parse(data).map(Decoded::$ty));
+                }
+            )*
+            None
+        }
+    };
+}
+
+decoders! {
+    Appl => b"APPL", Structured;
+    Drvr => b"DRVR", Structured;
+    Fcmt => b"FCMT", Text;
+    Mctb => b"mctb", Structured;
+    Str => b"STR ", Text;
+    StringList => b"STR#", Text;
+    VersionResource => b"vers", Structured;
+}
+
+/// The resource types that can be decoded by this module.
+pub fn supported() -> &'static [DecoderInfo] {
+    SUPPORTED
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn supports<'a, T: TypedResource<'a>>() -> bool {
+        supported().iter().any(|info| info.type_code == T::TYPE)
+    }
+
+    #[test]
+    fn test_supported() {
+        assert!(supports::<Appl<'_>>());
+        assert!(supports::<Drvr<'_>>());
+        assert!(supports::<Fcmt<'_>>());
+        assert!(supports::<Mctb<'_>>());
+        assert!(supports::<Str<'_>>());
+        assert!(supports::<StringList<'_>>());
+        assert!(supports::<VersionResource<'_>>());
+        assert_eq!(supported().len(), 7);
+
+        let codes: Vec<_> = supported()
+            .iter()
+            .map(|info| info.type_code.to_string())
+            .collect();
+        assert_eq!(
+            codes,
+            ["APPL", "DRVR", "FCMT", "mctb", "STR ", "STR#", "vers"]
+        );
+    }
+
+    #[test]
+    fn test_decode_any() {
+        let fcmt = FourCC(u32::from_be_bytes(*b"FCMT"));
+        match decode_any(fcmt, b"\x05Hello") {
+            Some(Ok(Decoded::Fcmt(fcmt))) => assert_eq!(fcmt.comment(), "Hello"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(matches!(
+            decode_any(fcmt, b""),
+            Some(Err(ParseError::BadEof))
+        ));
+
+        let mctb = FourCC(u32::from_be_bytes(*b"mctb"));
+        assert!(matches!(
+            decode_any(mctb, &[0, 0]),
+            Some(Ok(Decoded::Mctb(_)))
+        ));
+
+        let text = FourCC(u32::from_be_bytes(*b"TEXT"));
+        assert!(decode_any(text, b"text").is_none());
+    }
+}
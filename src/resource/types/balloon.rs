@@ -0,0 +1,211 @@
+//! Extraction of Balloon Help text.
+//!
+//! Balloon Help was introduced in System 7. The balloons for menus are described by `'hmnu'`
+//! resources and the balloon for an application's icon in the Finder by an `'hfdr'` resource.
+//! These are made up of components, one per item, each of which holds the help content or refers
+//! to where it is stored. Most applications keep the text in `'STR#'` string lists and refer to
+//! it by resource ID and index.
+//!
+//! ### Reference:
+//!
+//! [Inside Macintosh: More Macintosh Toolbox](https://archive.org/details/inside-macintosh-1992-1994/1993-more_macintosh_toolbox/)
+//! Chapter 3, Help Manager
+
+use crate::binary::read::ReadScope;
+use crate::error::ParseError;
+use crate::resource::ResourceFork;
+use crate::FourCC;
+
+use super::StringList;
+
+/// A component that refers to strings in a `'STR#'` resource (`kHMStringResItem`)
+const STRING_RES_ITEM: u16 = 3;
+
+/// Length of the header that precedes the components of a help resource
+const HEADER_LEN: usize = 12;
+
+/// Extraction of Balloon Help text from a resource fork.
+pub struct BalloonHelp;
+
+/// A help string referenced from a help resource, returned by [BalloonHelp::extract_strings].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HelpString {
+    /// Type of the help resource, `'hmnu'` or `'hfdr'`
+    pub rsrc_type: FourCC,
+    /// ID of the help resource
+    pub rsrc_id: i16,
+    /// Index of the component in the help resource that refers to the string, starting from 0
+    pub component: usize,
+    /// ID of the `'STR#'` resource holding the string
+    pub str_list_id: i16,
+    /// Index of the string in the `'STR#'` resource, starting from 1
+    pub str_index: u16,
+    /// The help text
+    pub text: String,
+}
+
+impl BalloonHelp {
+    /// The help resource types that are searched.
+    pub const TYPES: [FourCC; 2] = [FourCC::new(*b"hmnu"), FourCC::new(*b"hfdr")];
+
+    /// Find the help strings referenced by the `'hmnu'` and `'hfdr'` resources in `fork`.
+    ///
+    /// Only strings stored in `'STR#'` resources are returned. References to `'STR#'` resources
+    /// or strings that don't exist are skipped, as are help resources that can't be parsed. When
+    /// a component refers to the same string more than once, such as for both the enabled and
+    /// checked states of a menu item, it is only returned once.
+    pub fn extract_strings<'a>(fork: &'a ResourceFork<'a>) -> Vec<HelpString> {
+        let str_list_type = FourCC::new(*b"STR#");
+        let mut strings = Vec::new();
+        for item in fork.resource_types() {
+            let rsrc_type = item.resource_type();
+            if !Self::TYPES.contains(&rsrc_type) {
+                continue;
+            }
+            for resource in fork.resources(item) {
+                let mut references = Vec::new();
+                // Anything following a malformed component is ignored
+                let _ = string_references(resource.data(), &mut references);
+                for (component, str_list_id, str_index) in references {
+                    let text = fork
+                        .get_resource(str_list_type, str_list_id)
+                        .and_then(|str_list| {
+                            let index = usize::from(str_index).checked_sub(1)?;
+                            let str_list = StringList::parse(str_list.data()).ok()?;
+                            str_list.strings().nth(index).map(|text| text.string())
+                        });
+                    if let Some(text) = text {
+                        strings.push(HelpString {
+                            rsrc_type,
+                            rsrc_id: resource.id(),
+                            component,
+                            str_list_id,
+                            str_index,
+                            text,
+                        })
+                    }
+                }
+            }
+        }
+        strings
+    }
+}
+
+/// Collect the `(component, 'STR#' ID, index)` references in the help resource `data`.
+fn string_references(
+    data: &[u8],
+    references: &mut Vec<(usize, i16, u16)>,
+) -> Result<(), ParseError> {
+    let mut ctxt = ReadScope::new(data).ctxt();
+    // Version, options, balloon definition function, variation code, and component count. The
+    // count isn't used as components are read until the end of the data.
+    let _ = ctxt.read_slice(HEADER_LEN)?;
+    let mut component = 0;
+    while !ctxt.scope().data().is_empty() {
+        // The length includes the length and type words
+        let len = usize::from(ctxt.read_u16be()?);
+        ctxt.check(len >= 4)?;
+        let kind = ctxt.read_u16be()?;
+        let mut component_ctxt = ReadScope::new(ctxt.read_slice(len - 4)?).ctxt();
+        if kind == STRING_RES_ITEM {
+            // One pair in 'hfdr', one for each menu item state in 'hmnu'
+            let start = references.len();
+            while let (Ok(id), Ok(index)) =
+                (component_ctxt.read_i16be(), component_ctxt.read_u16be())
+            {
+                let reference = (component, id, index);
+                if index != 0 && !references[start..].contains(&reference) {
+                    references.push(reference);
+                }
+            }
+        }
+        component += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::resource_fork;
+
+    /// Build a help resource from `(type, data)` components
+    fn help_resource(components: &[(u16, &[u8])]) -> Vec<u8> {
+        let mut data = vec![0, 2, 0, 0, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&(components.len() as u16).to_be_bytes());
+        for (kind, component) in components {
+            data.extend_from_slice(&(component.len() as u16 + 4).to_be_bytes());
+            data.extend_from_slice(&kind.to_be_bytes());
+            data.extend_from_slice(component);
+        }
+        data
+    }
+
+    /// Data for a string resource component referring to `pairs`
+    fn string_res_item(pairs: &[(i16, u16)]) -> Vec<u8> {
+        pairs
+            .iter()
+            .flat_map(|(id, index)| [id.to_be_bytes(), index.to_be_bytes()])
+            .flatten()
+            .collect()
+    }
+
+    #[test]
+    fn test_extract_strings() {
+        const SKIP_ITEM: u16 = 256;
+        let title = string_res_item(&[(128, 1), (128, 1), (0, 0), (0, 0)]);
+        // Refers to a missing 'STR#', and to a string past the end of the list
+        let item = string_res_item(&[(128, 2), (129, 1), (128, 3), (128, 2)]);
+        let hmnu = help_resource(&[
+            (SKIP_ITEM, &[]),
+            (STRING_RES_ITEM, &title),
+            (STRING_RES_ITEM, &item),
+        ]);
+        let hfdr = help_resource(&[(STRING_RES_ITEM, &string_res_item(&[(128, 2)]))]);
+        let str_list = b"\x00\x02\x05Files\x0CSaves \x8Aditor";
+        let data = resource_fork(&[
+            (*b"hmnu", 128, None, &hmnu),
+            (*b"STR#", 128, None, str_list),
+            (*b"hfdr", -5696, None, &hfdr),
+        ]);
+        let fork = ResourceFork::new(&data).unwrap();
+
+        let strings = BalloonHelp::extract_strings(&fork);
+        let found: Vec<_> = strings
+            .iter()
+            .map(|string| {
+                (
+                    string.rsrc_type.to_string(),
+                    string.rsrc_id,
+                    string.component,
+                    string.str_index,
+                    string.text.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (String::from("hmnu"), 128, 1, 1, "Files"),
+                (String::from("hmnu"), 128, 2, 2, "Saves \u{e4}ditor"),
+                (String::from("hfdr"), -5696, 0, 2, "Saves \u{e4}ditor"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_malformed() {
+        // Components before a bad length are still returned
+        let mut hmnu = help_resource(&[(STRING_RES_ITEM, &string_res_item(&[(128, 1)]))]);
+        hmnu.extend_from_slice(&[0, 2, 0, 3]);
+        let data = resource_fork(&[
+            (*b"hmnu", 128, None, &hmnu),
+            (*b"hmnu", 129, None, b"\x00\x02"),
+            (*b"STR#", 128, None, b"\x00\x01\x04Help"),
+        ]);
+        let fork = ResourceFork::new(&data).unwrap();
+        let strings = BalloonHelp::extract_strings(&fork);
+        assert_eq!(strings.len(), 1);
+        assert_eq!(strings[0].text, "Help");
+    }
+}
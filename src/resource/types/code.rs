@@ -0,0 +1,60 @@
+//! Heuristics for resources that contain executable code.
+
+/// Returns `true` if `data` plausibly begins with 68k machine code.
+///
+/// Resources such as `INIT`, `CODE`, `cdev`, and `PACK` contain raw code with no header, so there
+/// is nothing to parse. However, compilers and assemblers of the era emitted a small set of
+/// recognisable opening instructions. This checks for those:
+///
+/// * `BRA` — a branch over a header or data to the entry point
+/// * `JMP`/`JSR` (PC relative)
+/// * `LINK A6` — a stack frame set up by most high level language compilers
+/// * `MOVEM.L <regs>,-(SP)` — saving registers
+/// * `MOVE.L An,-(SP)` — saving a single address register
+///
+/// This is a heuristic: a `false` result does not mean the resource isn't code, and arbitrary data
+/// can start with one of these words.
+pub fn looks_like_68k_code(data: &[u8]) -> bool {
+    let word = match data {
+        [hi, lo, ..] => u16::from_be_bytes([*hi, *lo]),
+        _ => return false,
+    };
+
+    match word {
+        // BRA.W with a 16-bit displacement that must follow
+        0x6000 => data.len() >= 4,
+        // BRA.S with a non-zero, even displacement (0xFF is BRA.L, which is 68020+)
+        0x6001..=0x60FE => word & 1 == 0,
+        // JMP (d16,PC) and JSR (d16,PC)
+        0x4EFA | 0x4EBA => data.len() >= 4,
+        // LINK A6,#d16
+        0x4E56 => data.len() >= 4,
+        // MOVEM.L <register list>,-(SP)
+        0x48E7 => data.len() >= 4,
+        // MOVE.L An,-(SP)
+        0x2F08..=0x2F0F => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_68k_code() {
+        // BRA.S *+$C
+        assert!(looks_like_68k_code(&[0x60, 0x0A, 0x00, 0x00]));
+        // LINK A6,#0; MOVEM.L D3-D7/A2-A4,-(SP)
+        assert!(looks_like_68k_code(&[0x4E, 0x56, 0x00, 0x00, 0x48, 0xE7]));
+        // MOVE.L A4,-(SP)
+        assert!(looks_like_68k_code(&[0x2F, 0x0C]));
+        // JMP (d16,PC) without its displacement
+        assert!(!looks_like_68k_code(&[0x4E, 0xFA]));
+
+        assert!(!looks_like_68k_code(&[]));
+        assert!(!looks_like_68k_code(&[0x4E]));
+        assert!(!looks_like_68k_code(b"TEXT"));
+        assert!(!looks_like_68k_code(&[0x00, 0x00, 0x00, 0x00]));
+    }
+}
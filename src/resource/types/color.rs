@@ -0,0 +1,23 @@
+//! Colour types shared by resource decoders.
+
+use crate::binary::read::ReadFrom;
+use crate::binary::U16Be;
+
+/// A QuickDraw colour (`RGBColor`), with 16 bits per component.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct RgbColor {
+    /// Red component
+    pub red: u16,
+    /// Green component
+    pub green: u16,
+    /// Blue component
+    pub blue: u16,
+}
+
+impl ReadFrom for RgbColor {
+    type ReadType = (U16Be, U16Be, U16Be);
+
+    fn from((red, green, blue): (u16, u16, u16)) -> Self {
+        RgbColor { red, green, blue }
+    }
+}
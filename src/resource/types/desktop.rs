@@ -0,0 +1,234 @@
+//! Decoders for the resources of the Finder's Desktop file.
+//!
+//! Before the desktop database, each volume had an invisible "Desktop" file. Its resource fork
+//! holds Finder comments in `'FCMT'` resources and the locations of applications in `'APPL'`
+//! resources.
+
+use core::fmt;
+#[cfg(not(feature = "no_std"))]
+use std::borrow::Cow;
+
+#[cfg(feature = "no_std")]
+use heapless::String;
+
+use crate::binary::read::{ReadBinary, ReadCtxt, ReadScope};
+use crate::error::ParseError;
+#[cfg(not(feature = "no_std"))]
+use crate::macroman::decode_macroman;
+use crate::macroman::FromMacRoman;
+use crate::FourCC;
+
+/// A Finder comment (`'FCMT'`) resource.
+///
+/// The resource ID is a hash of the file the comment belongs to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Fcmt<'a> {
+    comment: &'a [u8],
+}
+
+/// An application list (`'APPL'`) resource.
+///
+/// This maps creator codes to the applications that handle them.
+#[derive(Copy, Clone)]
+pub struct Appl<'a> {
+    scope: ReadScope<'a>,
+}
+
+/// An entry in an [Appl] resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ApplEntry<'a> {
+    /// Creator code of the application
+    pub creator: FourCC,
+    /// ID of the directory containing the application
+    pub dir_id: u32,
+    name: &'a [u8],
+}
+
+/// An iterator over the entries of an [Appl] resource.
+///
+/// Created with [Appl::entries].
+pub struct ApplEntries<'a> {
+    ctxt: ReadCtxt<'a>,
+}
+
+impl<'a> Fcmt<'a> {
+    /// Parse a `'FCMT'` resource from its data.
+    pub fn parse(data: &'a [u8]) -> Result<Fcmt<'a>, ParseError> {
+        ReadScope::new(data).read::<Fcmt<'_>>()
+    }
+}
+
+impl Fcmt<'_> {
+    /// The comment text.
+    #[cfg(not(feature = "no_std"))]
+    pub fn comment(&self) -> String {
+        String::from_macroman(self.comment)
+    }
+
+    /// The comment text.
+    ///
+    /// Returns `None` if the `N` parameter is too small to hold the UTF-8 string.
+    #[cfg(feature = "no_std")]
+    pub fn comment<const N: usize>(&self) -> Option<String<N>> {
+        String::try_from_macroman(self.comment)
+    }
+
+    /// The comment text.
+    ///
+    /// Unlike [Self::comment] this only allocates when the comment contains non-ASCII characters.
+    #[cfg(not(feature = "no_std"))]
+    pub fn comment_str(&self) -> Cow<'_, str> {
+        decode_macroman(self.comment)
+    }
+
+    /// The raw bytes of the comment.
+    pub fn comment_bytes(&self) -> &[u8] {
+        self.comment
+    }
+}
+
+impl ReadBinary for Fcmt<'_> {
+    type HostType<'a> = Fcmt<'a>;
+
+    fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
+        let len = ctxt.read_u8()?;
+        let comment = ctxt.read_slice(usize::from(len))?;
+        Ok(Fcmt { comment })
+    }
+}
+
+impl<'a> Appl<'a> {
+    /// Parse an `'APPL'` resource from its data.
+    ///
+    /// All entries are checked, so iterating over them afterwards can't fail.
+    pub fn parse(data: &'a [u8]) -> Result<Appl<'a>, ParseError> {
+        let scope = ReadScope::new(data);
+        let mut ctxt = scope.ctxt();
+        while ctxt.bytes_available() {
+            ctxt.read::<ApplEntry<'_>>()?;
+        }
+        Ok(Appl { scope })
+    }
+
+    /// Iterate over the entries in the resource.
+    pub fn entries(&self) -> ApplEntries<'a> {
+        ApplEntries {
+            ctxt: self.scope.ctxt(),
+        }
+    }
+}
+
+impl fmt::Debug for Appl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.entries()).finish()
+    }
+}
+
+impl ApplEntry<'_> {
+    /// The name of the application.
+    #[cfg(not(feature = "no_std"))]
+    pub fn name(&self) -> String {
+        String::from_macroman(self.name)
+    }
+
+    /// The name of the application.
+    ///
+    /// Returns `None` if the `N` parameter is too small to hold the UTF-8 string.
+    #[cfg(feature = "no_std")]
+    pub fn name<const N: usize>(&self) -> Option<String<N>> {
+        String::try_from_macroman(self.name)
+    }
+
+    /// The name of the application.
+    ///
+    /// Unlike [Self::name] this only allocates when the name contains non-ASCII characters.
+    #[cfg(not(feature = "no_std"))]
+    pub fn name_str(&self) -> Cow<'_, str> {
+        decode_macroman(self.name)
+    }
+
+    /// The raw bytes of the application name.
+    pub fn name_bytes(&self) -> &[u8] {
+        self.name
+    }
+}
+
+impl ReadBinary for ApplEntry<'_> {
+    type HostType<'a> = ApplEntry<'a>;
+
+    fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
+        let creator = FourCC(ctxt.read_u32be()?);
+        let dir_id = ctxt.read_u32be()?;
+        let name_len = ctxt.read_u8()?;
+        let name = ctxt.read_slice(usize::from(name_len))?;
+        // Entries are word aligned, so the name is followed by a pad byte if its length is even
+        if name_len % 2 == 0 {
+            let _ = ctxt.read_u8()?;
+        }
+        Ok(ApplEntry {
+            creator,
+            dir_id,
+            name,
+        })
+    }
+}
+
+impl<'a> Iterator for ApplEntries<'a> {
+    type Item = ApplEntry<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.ctxt.bytes_available() {
+            return None;
+        }
+        // Entries were checked when the resource was parsed
+        self.ctxt.read::<ApplEntry<'_>>().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fcmt() {
+        let fcmt = Fcmt::parse(b"\x0fCaf\x8e menu draft").unwrap();
+        assert_eq!(fcmt.comment_bytes(), b"Caf\x8e menu draft");
+        assert_eq!(fcmt.comment(), "Café menu draft");
+        assert_eq!(fcmt.comment_str(), "Café menu draft");
+
+        assert!(matches!(
+            Fcmt::parse(b"\x10too short"),
+            Err(ParseError::BadEof)
+        ));
+    }
+
+    #[test]
+    fn test_appl() {
+        #[rustfmt::skip]
+        let data = [
+            b'M', b'S', b'W', b'D', 0x00, 0x00, 0x01, 0x2C,
+            0x04, b'W', b'o', b'r', b'd', 0x00,
+            b't', b't', b'x', b't', 0x00, 0x00, 0x00, 0x02,
+            0x0B, b'T', b'e', b'a', b'c', b'h', b'T', b'e', b'x', b't', b' ', b'1',
+        ];
+        let appl = Appl::parse(&data).unwrap();
+        let entries: Vec<_> = appl
+            .entries()
+            .map(|entry| (entry.creator.to_string(), entry.dir_id, entry.name()))
+            .collect();
+        assert_eq!(
+            entries,
+            [
+                (String::from("MSWD"), 300, String::from("Word")),
+                (String::from("ttxt"), 2, String::from("TeachText 1")),
+            ]
+        );
+
+        // Truncated entry
+        assert!(matches!(
+            Appl::parse(&data[..data.len() - 1]),
+            Err(ParseError::BadEof)
+        ));
+        assert_eq!(Appl::parse(&[]).unwrap().entries().count(), 0);
+    }
+}
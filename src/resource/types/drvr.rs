@@ -0,0 +1,156 @@
+//! Decoder for device driver (`'DRVR'`) resources.
+//!
+//! ### Reference:
+//!
+//! [Inside Macintosh: Devices](https://archive.org/details/inside-macintosh-1992-1994/1994-devices/)
+//! The Driver Resource 1-24 (pp. 50)
+
+#[cfg(not(feature = "no_std"))]
+use std::borrow::Cow;
+
+#[cfg(feature = "no_std")]
+use heapless::String;
+
+use crate::binary::read::{ReadBinary, ReadCtxt, ReadScope};
+use crate::error::ParseError;
+#[cfg(not(feature = "no_std"))]
+use crate::macroman::decode_macroman;
+use crate::macroman::FromMacRoman;
+
+/// The header of a device driver (`'DRVR'`) resource.
+///
+/// Desk accessories are also stored as `'DRVR'` resources, conventionally with a name starting
+/// with a NUL byte.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Drvr<'a> {
+    /// Driver flags (`drvrFlags`)
+    pub flags: u16,
+    /// Number of ticks between periodic actions (`drvrDelay`)
+    pub delay: u16,
+    /// Desk accessory event mask (`drvrEMask`)
+    pub event_mask: u16,
+    /// Menu ID of the menu associated with a desk accessory (`drvrMenu`)
+    pub menu_id: i16,
+    /// Offset to the open routine (`drvrOpen`)
+    pub open: u16,
+    /// Offset to the prime routine (`drvrPrime`)
+    pub prime: u16,
+    /// Offset to the control routine (`drvrCtl`)
+    pub control: u16,
+    /// Offset to the status routine (`drvrStatus`)
+    pub status: u16,
+    /// Offset to the close routine (`drvrClose`)
+    pub close: u16,
+    name: &'a [u8],
+}
+
+impl<'a> Drvr<'a> {
+    /// Parse the header of a `'DRVR'` resource from its data.
+    pub fn parse(data: &'a [u8]) -> Result<Drvr<'a>, ParseError> {
+        ReadScope::new(data).read::<Drvr<'_>>()
+    }
+}
+
+impl Drvr<'_> {
+    /// The name of the driver.
+    #[cfg(not(feature = "no_std"))]
+    pub fn name(&self) -> String {
+        String::from_macroman(self.name)
+    }
+
+    /// The name of the driver.
+    ///
+    /// Returns `None` if the `N` parameter is too small to hold the UTF-8 string.
+    #[cfg(feature = "no_std")]
+    pub fn name<const N: usize>(&self) -> Option<String<N>> {
+        String::try_from_macroman(self.name)
+    }
+
+    /// The name of the driver.
+    ///
+    /// Unlike [Self::name] this only allocates when the name contains non-ASCII characters.
+    #[cfg(not(feature = "no_std"))]
+    pub fn name_str(&self) -> Cow<'_, str> {
+        decode_macroman(self.name)
+    }
+
+    /// The raw bytes of the driver name.
+    pub fn name_bytes(&self) -> &[u8] {
+        self.name
+    }
+}
+
+impl ReadBinary for Drvr<'_> {
+    type HostType<'a> = Drvr<'a>;
+
+    fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
+        let flags = ctxt.read_u16be()?;
+        let delay = ctxt.read_u16be()?;
+        let event_mask = ctxt.read_u16be()?;
+        let menu_id = ctxt.read_i16be()?;
+        let open = ctxt.read_u16be()?;
+        let prime = ctxt.read_u16be()?;
+        let control = ctxt.read_u16be()?;
+        let status = ctxt.read_u16be()?;
+        let close = ctxt.read_u16be()?;
+        let name_len = ctxt.read_u8()?;
+        let name = ctxt.read_slice(usize::from(name_len))?;
+
+        Ok(Drvr {
+            flags,
+            delay,
+            event_mask,
+            menu_id,
+            open,
+            prime,
+            control,
+            status,
+            close,
+            name,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        #[rustfmt::skip]
+        let data = [
+            0x4F, 0x00, // flags: dNeedLock, dStatEnable, dCtlEnable, dWritEnable, dReadEnable
+            0x00, 0x00, // delay
+            0x00, 0x00, // event mask
+            0xFF, 0xE0, // menu ID
+            0x00, 0x24, // open
+            0x00, 0x2A, // prime
+            0x00, 0x30, // control
+            0x00, 0x36, // status
+            0x00, 0x3C, // close
+            0x05, b'.', b'S', b'o', b'n', b'y',
+            0x4E, 0x75, // RTS
+        ];
+        let drvr = Drvr::parse(&data).unwrap();
+        assert_eq!(drvr.flags, 0x4F00);
+        assert_eq!(drvr.delay, 0);
+        assert_eq!(drvr.event_mask, 0);
+        assert_eq!(drvr.menu_id, -32);
+        assert_eq!(
+            [drvr.open, drvr.prime, drvr.control, drvr.status, drvr.close],
+            [0x24, 0x2A, 0x30, 0x36, 0x3C]
+        );
+        assert_eq!(drvr.name_bytes(), b".Sony");
+        assert_eq!(drvr.name(), ".Sony");
+        assert_eq!(drvr.name_str(), ".Sony");
+    }
+
+    #[test]
+    fn test_parse_truncated() {
+        assert!(matches!(Drvr::parse(&[0; 18]), Err(ParseError::BadEof)));
+        // Name length runs past the end of the data
+        let mut data = [0; 20];
+        data[18] = 2;
+        assert!(matches!(Drvr::parse(&data), Err(ParseError::BadEof)));
+    }
+}
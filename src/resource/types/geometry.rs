@@ -0,0 +1,184 @@
+//! QuickDraw geometry types shared by resource decoders.
+
+use core::fmt;
+
+use crate::binary::read::ReadFrom;
+use crate::binary::I16Be;
+
+/// A QuickDraw point (`Point`), stored vertical coordinate first.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Point {
+    /// Vertical coordinate
+    pub v: i16,
+    /// Horizontal coordinate
+    pub h: i16,
+}
+
+/// A QuickDraw rectangle (`Rect`), defined by its top-left and bottom-right corners.
+///
+/// Coordinates may be negative. A rectangle with `bottom` above `top` or `right` left of `left`
+/// is inverted: [Rect::is_valid] returns `false` and the corresponding dimension is zero.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Rect {
+    /// Top edge
+    pub top: i16,
+    /// Left edge
+    pub left: i16,
+    /// Bottom edge
+    pub bottom: i16,
+    /// Right edge
+    pub right: i16,
+}
+
+impl Point {
+    /// Create a new point.
+    pub const fn new(v: i16, h: i16) -> Point {
+        Point { v, h }
+    }
+}
+
+impl Rect {
+    /// Create a new rectangle.
+    pub const fn new(top: i16, left: i16, bottom: i16, right: i16) -> Rect {
+        Rect {
+            top,
+            left,
+            bottom,
+            right,
+        }
+    }
+
+    /// The top-left corner.
+    pub const fn top_left(&self) -> Point {
+        Point::new(self.top, self.left)
+    }
+
+    /// The bottom-right corner.
+    pub const fn bottom_right(&self) -> Point {
+        Point::new(self.bottom, self.right)
+    }
+
+    /// The width of the rectangle, zero if it is inverted horizontally.
+    pub fn width(&self) -> u16 {
+        extent(self.left, self.right)
+    }
+
+    /// The height of the rectangle, zero if it is inverted vertically.
+    pub fn height(&self) -> u16 {
+        extent(self.top, self.bottom)
+    }
+
+    /// Returns `false` if the bottom is above the top or the right is left of the left.
+    ///
+    /// Empty rectangles, where the edges are equal, are valid.
+    pub fn is_valid(&self) -> bool {
+        self.bottom >= self.top && self.right >= self.left
+    }
+
+    /// Returns `true` if the rectangle encloses no points.
+    pub fn is_empty(&self) -> bool {
+        self.width() == 0 || self.height() == 0
+    }
+}
+
+/// Distance from `start` to `end`, or zero if `end` is before `start`.
+fn extent(start: i16, end: i16) -> u16 {
+    // The difference of two i16 values always fits in 0..=u16::MAX when it is not negative
+    u16::try_from(i32::from(end) - i32::from(start)).unwrap_or(0)
+}
+
+impl ReadFrom for Point {
+    type ReadType = (I16Be, I16Be);
+
+    fn from((v, h): (i16, i16)) -> Self {
+        Point { v, h }
+    }
+}
+
+impl ReadFrom for Rect {
+    type ReadType = (Point, Point);
+
+    fn from((top_left, bottom_right): (Point, Point)) -> Self {
+        Rect::new(top_left.v, top_left.h, bottom_right.v, bottom_right.h)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.v, self.h)
+    }
+}
+
+impl fmt::Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "({}, {}, {}, {})",
+            self.top, self.left, self.bottom, self.right
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::read::ReadScope;
+
+    #[test]
+    fn test_read() {
+        let data = [0x00, 0x28, 0xFF, 0xF6, 0x01, 0x2C, 0x00, 0xC8];
+        let rect = ReadScope::new(&data).read::<Rect>().unwrap();
+        assert_eq!(rect, Rect::new(40, -10, 300, 200));
+        assert_eq!(rect.top_left(), Point::new(40, -10));
+        assert_eq!(rect.bottom_right(), Point::new(300, 200));
+
+        let point = ReadScope::new(&data[4..]).read::<Point>().unwrap();
+        assert_eq!(point, Point::new(300, 200));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let rect = Rect::new(40, -10, 300, 200);
+        assert_eq!(rect.width(), 210);
+        assert_eq!(rect.height(), 260);
+        assert!(rect.is_valid());
+        assert!(!rect.is_empty());
+
+        // Negative coordinates
+        let rect = Rect::new(-50, -40, -10, -20);
+        assert_eq!(rect.width(), 20);
+        assert_eq!(rect.height(), 40);
+        assert!(rect.is_valid());
+
+        // Full coordinate range
+        let rect = Rect::new(i16::MIN, i16::MIN, i16::MAX, i16::MAX);
+        assert_eq!(rect.width(), u16::MAX);
+        assert_eq!(rect.height(), u16::MAX);
+
+        let empty = Rect::new(10, 10, 10, 30);
+        assert!(empty.is_valid());
+        assert!(empty.is_empty());
+        assert_eq!(empty.width(), 20);
+        assert_eq!(empty.height(), 0);
+    }
+
+    #[test]
+    fn test_inverted() {
+        let rect = Rect::new(100, 0, 50, 20);
+        assert!(!rect.is_valid());
+        assert!(rect.is_empty());
+        assert_eq!(rect.height(), 0);
+        assert_eq!(rect.width(), 20);
+
+        let rect = Rect::new(i16::MAX, i16::MAX, i16::MIN, i16::MIN);
+        assert!(!rect.is_valid());
+        assert_eq!(rect.width(), 0);
+        assert_eq!(rect.height(), 0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Point::new(-1, 2).to_string(), "(-1, 2)");
+        assert_eq!(Rect::new(0, -4, 342, 512).to_string(), "(0, -4, 342, 512)");
+    }
+}
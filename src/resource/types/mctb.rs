@@ -0,0 +1,195 @@
+//! Decoder for menu color information table (`'mctb'`) resources.
+//!
+//! ### Reference:
+//!
+//! [Inside Macintosh: Macintosh Toolbox Essentials](https://archive.org/details/inside-macintosh-1992-1994/1992-macintosh_toolbox_essentials/)
+//! The Menu Color Information Table Resource 3-156 (pp. 264)
+
+use core::fmt;
+
+use crate::binary::read::{ReadArray, ReadBinary, ReadCtxt, ReadFrom, ReadScope};
+use crate::binary::I16Be;
+use crate::error::ParseError;
+
+use super::RgbColor;
+
+/// A menu color information table (`'mctb'`) resource.
+///
+/// The resource ID of a `'mctb'` resource matches the ID of the `'MENU'` resource it applies to,
+/// except for the menu bar entry, which is stored in `'mctb'` 0.
+pub struct Mctb<'a> {
+    entries: ReadArray<'a, MctbEntry>,
+}
+
+/// An entry in a menu color information table.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MctbEntry {
+    /// The menu bar, menu title, or menu item that this entry applies to
+    pub target: MenuColorTarget,
+    /// The colors for the target.
+    ///
+    /// The meaning of each color depends on the target. For example, the colors of a menu item
+    /// are its mark, name, keyboard equivalent, and background colors. See the reference in the
+    /// module documentation for details.
+    pub colors: [RgbColor; 4],
+}
+
+/// What a [MctbEntry] applies to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MenuColorTarget {
+    /// The menu bar and default colors for all menus (menu ID 0, item 0)
+    MenuBar,
+    /// The title of a menu (item 0)
+    Title {
+        /// The ID of the menu
+        menu_id: i16,
+    },
+    /// An item in a menu
+    Item {
+        /// The ID of the menu
+        menu_id: i16,
+        /// The item number, starting from 1
+        item: i16,
+    },
+}
+
+impl<'a> Mctb<'a> {
+    /// Parse a `'mctb'` resource from its data.
+    pub fn parse(data: &'a [u8]) -> Result<Mctb<'a>, ParseError> {
+        ReadScope::new(data).read::<Mctb<'_>>()
+    }
+}
+
+impl Mctb<'_> {
+    /// The number of entries in the table.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the table has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate over the entries in the table.
+    pub fn entries(&self) -> impl Iterator<Item = MctbEntry> + '_ {
+        self.entries.iter()
+    }
+}
+
+impl fmt::Debug for Mctb<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.entries()).finish()
+    }
+}
+
+impl ReadBinary for Mctb<'_> {
+    type HostType<'a> = Mctb<'a>;
+
+    fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
+        let count = ctxt.read_i16be()?;
+        ctxt.check(count >= 0)?;
+        let entries = ctxt.read_array::<MctbEntry>(count as usize)?;
+        Ok(Mctb { entries })
+    }
+}
+
+impl ReadFrom for MctbEntry {
+    #[allow(clippy::type_complexity)]
+    type ReadType = (
+        (I16Be, I16Be),
+        ((RgbColor, RgbColor), (RgbColor, RgbColor)),
+        I16Be,
+    );
+
+    fn from(
+        ((menu_id, item), ((color1, color2), (color3, color4)), _reserved): (
+            (i16, i16),
+            ((RgbColor, RgbColor), (RgbColor, RgbColor)),
+            i16,
+        ),
+    ) -> Self {
+        let target = match (menu_id, item) {
+            (0, 0) => MenuColorTarget::MenuBar,
+            (menu_id, 0) => MenuColorTarget::Title { menu_id },
+            (menu_id, item) => MenuColorTarget::Item { menu_id, item },
+        };
+        MctbEntry {
+            target,
+            colors: [color1, color2, color3, color4],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rgb(red: u16, green: u16, blue: u16) -> RgbColor {
+        RgbColor { red, green, blue }
+    }
+
+    #[test]
+    fn test_parse() {
+        #[rustfmt::skip]
+        let data = [
+            0x00, 0x02, // count
+            // Menu bar
+            0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0x00, 0x00,
+            // Menu 128, item 3
+            0x00, 0x80, 0x00, 0x03,
+            0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0xFF, 0xFF, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF,
+            0x80, 0x00, 0x80, 0x00, 0x80, 0x00,
+            0x00, 0x00,
+        ];
+        let mctb = Mctb::parse(&data).unwrap();
+        assert_eq!(mctb.len(), 2);
+        let entries: Vec<_> = mctb.entries().collect();
+        let black = rgb(0, 0, 0);
+        let white = rgb(0xFFFF, 0xFFFF, 0xFFFF);
+        assert_eq!(
+            entries,
+            [
+                MctbEntry {
+                    target: MenuColorTarget::MenuBar,
+                    colors: [black, white, black, white],
+                },
+                MctbEntry {
+                    target: MenuColorTarget::Item {
+                        menu_id: 128,
+                        item: 3
+                    },
+                    colors: [
+                        rgb(0xFFFF, 0, 0),
+                        rgb(0, 0xFFFF, 0),
+                        rgb(0, 0, 0xFFFF),
+                        rgb(0x8000, 0x8000, 0x8000)
+                    ],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_bad_count() {
+        // Count says one entry but there's no data for it
+        assert!(matches!(Mctb::parse(&[0, 1]), Err(ParseError::BadEof)));
+        assert!(matches!(
+            Mctb::parse(&[0, 1, 0, 0]),
+            Err(ParseError::BadEof)
+        ));
+        // Negative count
+        assert!(matches!(
+            Mctb::parse(&[0xFF, 0xFF]),
+            Err(ParseError::BadValue)
+        ));
+        assert_eq!(Mctb::parse(&[0, 0]).unwrap().len(), 0);
+    }
+}
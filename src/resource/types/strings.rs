@@ -0,0 +1,212 @@
+//! Decoders for string (`'STR '`) and string list (`'STR#'`) resources.
+//!
+//! ### Reference:
+//!
+//! [Inside Macintosh: More Macintosh Toolbox](https://archive.org/details/inside-macintosh-1992-1994/1993-more_macintosh_toolbox/)
+//! Resource Manager Reference, The String Resource and The String List Resource
+
+use core::fmt;
+#[cfg(not(feature = "no_std"))]
+use std::borrow::Cow;
+#[cfg(not(feature = "no_std"))]
+use std::vec::Vec;
+
+#[cfg(feature = "no_std")]
+use heapless::String;
+
+use crate::binary::read::{ReadBinary, ReadCtxt, ReadScope};
+use crate::error::ParseError;
+#[cfg(not(feature = "no_std"))]
+use crate::macroman::decode_macroman;
+use crate::macroman::{DebugMacRoman, FromMacRoman};
+
+/// A string (`'STR '`) resource, a single Pascal string.
+///
+/// Any data following the string is ignored, as many string resources have unused bytes after
+/// the string.
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub struct Str<'a> {
+    string: &'a [u8],
+}
+
+/// A string list (`'STR#'`) resource, a count followed by that many Pascal strings.
+#[derive(Copy, Clone)]
+pub struct StringList<'a> {
+    scope: ReadScope<'a>,
+    count: u16,
+}
+
+/// An iterator over the strings of a [StringList] resource.
+///
+/// Created with [StringList::strings].
+pub struct StringListIter<'a> {
+    ctxt: ReadCtxt<'a>,
+    remaining: u16,
+}
+
+impl<'a> Str<'a> {
+    /// Parse a `'STR '` resource from its data.
+    pub fn parse(data: &'a [u8]) -> Result<Str<'a>, ParseError> {
+        ReadScope::new(data).read::<Str<'_>>()
+    }
+}
+
+impl Str<'_> {
+    /// The string decoded from MacRoman.
+    #[cfg(not(feature = "no_std"))]
+    pub fn string(&self) -> String {
+        String::from_macroman(self.string)
+    }
+
+    /// The string decoded from MacRoman.
+    ///
+    /// Returns `None` if the `N` parameter is too small to hold the UTF-8 string.
+    #[cfg(feature = "no_std")]
+    pub fn string<const N: usize>(&self) -> Option<String<N>> {
+        String::try_from_macroman(self.string)
+    }
+
+    /// The string decoded from MacRoman.
+    ///
+    /// Unlike [Self::string] this only allocates when the string contains non-ASCII characters.
+    #[cfg(not(feature = "no_std"))]
+    pub fn string_str(&self) -> Cow<'_, str> {
+        decode_macroman(self.string)
+    }
+
+    /// The raw bytes of the string.
+    pub fn string_bytes(&self) -> &[u8] {
+        self.string
+    }
+}
+
+impl fmt::Debug for Str<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Str")
+            .field(&DebugMacRoman(self.string))
+            .finish()
+    }
+}
+
+impl ReadBinary for Str<'_> {
+    type HostType<'a> = Str<'a>;
+
+    fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
+        let len = ctxt.read_u8()?;
+        let string = ctxt.read_slice(usize::from(len))?;
+        Ok(Str { string })
+    }
+}
+
+impl<'a> StringList<'a> {
+    /// Parse a `'STR#'` resource from its data.
+    ///
+    /// All the strings are checked, so iterating over them afterwards can't fail. Any data
+    /// following the last string is ignored.
+    pub fn parse(data: &'a [u8]) -> Result<StringList<'a>, ParseError> {
+        let mut ctxt = ReadScope::new(data).ctxt();
+        let count = ctxt.read_u16be()?;
+        let scope = ctxt.scope();
+        for _ in 0..count {
+            ctxt.read::<Str<'_>>()?;
+        }
+        Ok(StringList { scope, count })
+    }
+
+    /// Iterate over the strings in the list.
+    pub fn strings(&self) -> StringListIter<'a> {
+        StringListIter {
+            ctxt: self.scope.ctxt(),
+            remaining: self.count,
+        }
+    }
+
+    /// The strings in the list decoded from MacRoman.
+    #[cfg(not(feature = "no_std"))]
+    pub fn to_strings(&self) -> Vec<String> {
+        self.strings().map(|string| string.string()).collect()
+    }
+}
+
+impl StringList<'_> {
+    /// The number of strings in the list.
+    pub fn len(&self) -> usize {
+        usize::from(self.count)
+    }
+
+    /// Returns `true` if the list holds no strings.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+}
+
+impl fmt::Debug for StringList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.strings()).finish()
+    }
+}
+
+impl<'a> Iterator for StringListIter<'a> {
+    type Item = Str<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        // Strings were checked when the resource was parsed
+        self.ctxt.read::<Str<'_>>().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = usize::from(self.remaining);
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_str() {
+        let string = Str::parse(b"\x0bCaf\x8e \xa5 Men\x9f").unwrap();
+        assert_eq!(string.string_bytes(), b"Caf\x8e \xa5 Men\x9f");
+        assert_eq!(string.string(), "Café • Menü");
+        assert_eq!(string.string_str(), "Café • Menü");
+
+        // Trailing data is ignored
+        let string = Str::parse(b"\x05Hello\x00\xff garbage").unwrap();
+        assert_eq!(string.string(), "Hello");
+        assert_eq!(Str::parse(b"\x00").unwrap().string(), "");
+
+        assert!(matches!(Str::parse(b"\x06Hello"), Err(ParseError::BadEof)));
+        assert!(matches!(Str::parse(b""), Err(ParseError::BadEof)));
+    }
+
+    #[test]
+    fn test_string_list() {
+        let data = b"\x00\x03\x03One\x00\x0aCaf\x8e cr\x8fme\x00\x00garbage";
+        let list = StringList::parse(data).unwrap();
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.to_strings(), ["One", "", "Café crème"]);
+        assert_eq!(list.strings().size_hint(), (3, Some(3)));
+        let second = list.strings().nth(1).unwrap();
+        assert_eq!(second.string_bytes(), b"");
+
+        let empty = StringList::parse(b"\x00\x00").unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.strings().count(), 0);
+
+        // Count claims more strings than are present
+        assert!(matches!(
+            StringList::parse(b"\x00\x04\x03One\x03Two"),
+            Err(ParseError::BadEof)
+        ));
+        assert!(matches!(
+            StringList::parse(b"\x00\x01\x05Four"),
+            Err(ParseError::BadEof)
+        ));
+        assert!(matches!(
+            StringList::parse(b"\x00"),
+            Err(ParseError::BadEof)
+        ));
+    }
+}
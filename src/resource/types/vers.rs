@@ -0,0 +1,224 @@
+//! Decoder for version (`'vers'`) resources.
+//!
+//! The Finder shows the version of a file from its `'vers'` resource with ID 1. The `'vers'`
+//! resource with ID 2 gives the version of the product the file belongs to.
+//!
+//! ### Reference:
+//!
+//! [Inside Macintosh: Macintosh Toolbox Essentials](https://archive.org/details/inside-macintosh-1992-1994/1992-macintosh_toolbox_essentials/)
+//! Finder Interface, The Version Resource
+
+#[cfg(not(feature = "no_std"))]
+use std::borrow::Cow;
+
+#[cfg(feature = "no_std")]
+use heapless::String;
+
+use crate::binary::read::{ReadBinary, ReadCtxt, ReadScope};
+use crate::error::ParseError;
+#[cfg(not(feature = "no_std"))]
+use crate::macroman::decode_macroman;
+use crate::macroman::FromMacRoman;
+
+/// A version (`'vers'`) resource.
+///
+/// The version numbers are stored in binary-coded decimal and are decoded here, so version
+/// 7.5.3 has a `major` of 7, `minor` of 5, and `bug_fix` of 3.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct VersionResource<'a> {
+    /// Major version number
+    pub major: u8,
+    /// Minor version number
+    pub minor: u8,
+    /// Bug fix version number
+    pub bug_fix: u8,
+    /// Development stage of the release
+    pub stage: DevelopmentStage,
+    /// Revision of a pre-release version, e.g. the 3 in 1.0b3
+    pub prerelease: u8,
+    /// Region code of the release, 0 (`verUS`) for the United States
+    pub region: i16,
+    short_version: &'a [u8],
+    long_version: &'a [u8],
+}
+
+/// The development stage of a [VersionResource].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DevelopmentStage {
+    /// Pre-alpha development release (`developStage`)
+    Development,
+    /// Alpha release (`alphaStage`)
+    Alpha,
+    /// Beta release (`betaStage`)
+    Beta,
+    /// Final release (`finalStage`)
+    Release,
+    /// A stage value other than the defined ones
+    Other(u8),
+}
+
+impl<'a> VersionResource<'a> {
+    /// Parse a `'vers'` resource from its data.
+    pub fn parse(data: &'a [u8]) -> Result<VersionResource<'a>, ParseError> {
+        ReadScope::new(data).read::<VersionResource<'_>>()
+    }
+}
+
+impl VersionResource<'_> {
+    /// The short version string, e.g. `7.5.3`.
+    #[cfg(not(feature = "no_std"))]
+    pub fn short_version(&self) -> String {
+        String::from_macroman(self.short_version)
+    }
+
+    /// The short version string, e.g. `7.5.3`.
+    ///
+    /// Returns `None` if the `N` parameter is too small to hold the UTF-8 string.
+    #[cfg(feature = "no_std")]
+    pub fn short_version<const N: usize>(&self) -> Option<String<N>> {
+        String::try_from_macroman(self.short_version)
+    }
+
+    /// The short version string, e.g. `7.5.3`.
+    ///
+    /// Unlike [Self::short_version] this only allocates when the string contains non-ASCII
+    /// characters.
+    #[cfg(not(feature = "no_std"))]
+    pub fn short_version_str(&self) -> Cow<'_, str> {
+        decode_macroman(self.short_version)
+    }
+
+    /// The raw bytes of the short version string.
+    pub fn short_version_bytes(&self) -> &[u8] {
+        self.short_version
+    }
+
+    /// The long version string shown in the Finder's Get Info window, e.g.
+    /// `7.5.3, © Apple Computer, Inc. 1983-96`.
+    #[cfg(not(feature = "no_std"))]
+    pub fn long_version(&self) -> String {
+        String::from_macroman(self.long_version)
+    }
+
+    /// The long version string shown in the Finder's Get Info window.
+    ///
+    /// Returns `None` if the `N` parameter is too small to hold the UTF-8 string.
+    #[cfg(feature = "no_std")]
+    pub fn long_version<const N: usize>(&self) -> Option<String<N>> {
+        String::try_from_macroman(self.long_version)
+    }
+
+    /// The long version string shown in the Finder's Get Info window.
+    ///
+    /// Unlike [Self::long_version] this only allocates when the string contains non-ASCII
+    /// characters.
+    #[cfg(not(feature = "no_std"))]
+    pub fn long_version_str(&self) -> Cow<'_, str> {
+        decode_macroman(self.long_version)
+    }
+
+    /// The raw bytes of the long version string.
+    pub fn long_version_bytes(&self) -> &[u8] {
+        self.long_version
+    }
+}
+
+impl ReadBinary for VersionResource<'_> {
+    type HostType<'a> = VersionResource<'a>;
+
+    fn read<'a>(ctxt: &mut ReadCtxt<'a>) -> Result<Self::HostType<'a>, ParseError> {
+        let major = from_bcd(ctxt.read_u8()?);
+        let minor_bug_fix = ctxt.read_u8()?;
+        let stage = DevelopmentStage::from(ctxt.read_u8()?);
+        let prerelease = ctxt.read_u8()?;
+        let region = ctxt.read_i16be()?;
+        let short_len = ctxt.read_u8()?;
+        let short_version = ctxt.read_slice(usize::from(short_len))?;
+        let long_len = ctxt.read_u8()?;
+        let long_version = ctxt.read_slice(usize::from(long_len))?;
+        Ok(VersionResource {
+            major,
+            minor: minor_bug_fix >> 4,
+            bug_fix: minor_bug_fix & 0x0F,
+            stage,
+            prerelease,
+            region,
+            short_version,
+            long_version,
+        })
+    }
+}
+
+impl From<u8> for DevelopmentStage {
+    fn from(stage: u8) -> Self {
+        match stage {
+            0x20 => DevelopmentStage::Development,
+            0x40 => DevelopmentStage::Alpha,
+            0x60 => DevelopmentStage::Beta,
+            0x80 => DevelopmentStage::Release,
+            _ => DevelopmentStage::Other(stage),
+        }
+    }
+}
+
+/// Decode a binary-coded decimal byte
+fn from_bcd(byte: u8) -> u8 {
+    (byte >> 4) * 10 + (byte & 0x0F)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `'vers'` 1 resource for a final release
+    const RELEASE: &[u8] = b"\x07\x53\x80\x00\x00\x00\
+        \x057.5.3\
+        \x257.5.3, \xa9 Apple Computer, Inc. 1983-96";
+
+    #[test]
+    fn test_vers() {
+        let vers = VersionResource::parse(RELEASE).unwrap();
+        assert_eq!(vers.major, 7);
+        assert_eq!(vers.minor, 5);
+        assert_eq!(vers.bug_fix, 3);
+        assert_eq!(vers.stage, DevelopmentStage::Release);
+        assert_eq!(vers.prerelease, 0);
+        assert_eq!(vers.region, 0);
+        assert_eq!(vers.short_version(), "7.5.3");
+        assert_eq!(vers.short_version_str(), "7.5.3");
+        assert_eq!(
+            vers.long_version(),
+            "7.5.3, \u{a9} Apple Computer, Inc. 1983-96"
+        );
+        assert_eq!(vers.long_version_bytes(), &RELEASE[13..]);
+
+        let vers = VersionResource::parse(b"\x10\x02\x60\x03\x00\x02\x0610.0.2\x00").unwrap();
+        assert_eq!((vers.major, vers.minor, vers.bug_fix), (10, 0, 2));
+        assert_eq!(vers.stage, DevelopmentStage::Beta);
+        assert_eq!(vers.prerelease, 3);
+        assert_eq!(vers.region, 2);
+        assert_eq!(vers.long_version(), "");
+
+        assert_eq!(DevelopmentStage::from(0x20), DevelopmentStage::Development);
+        assert_eq!(DevelopmentStage::from(0x40), DevelopmentStage::Alpha);
+        assert_eq!(DevelopmentStage::from(0x10), DevelopmentStage::Other(0x10));
+    }
+
+    #[test]
+    fn test_vers_truncated() {
+        let truncated = &RELEASE[..RELEASE.len() - 1];
+        assert!(matches!(
+            VersionResource::parse(truncated),
+            Err(ParseError::BadEof)
+        ));
+        // Missing the long version string entirely
+        assert!(matches!(
+            VersionResource::parse(&RELEASE[..12]),
+            Err(ParseError::BadEof)
+        ));
+        assert!(matches!(
+            VersionResource::parse(&RELEASE[..3]),
+            Err(ParseError::BadEof)
+        ));
+    }
+}
@@ -0,0 +1,369 @@
+//! Canonical summaries of files for semantic comparison.
+//!
+//! A [Snapshot] captures the metadata and content of a MacBinary file independent of how it is
+//! laid out: resources are listed in sorted order, and fork content is recorded as a digest. Two
+//! files with the same snapshot have the same content even if their bytes differ, e.g. after
+//! the resource fork has been compacted. [Snapshot::diff] lists how two snapshots differ.
+//!
+//! With the `serde` feature snapshots can be serialized, so they can be stored and compared
+//! later.
+
+use core::fmt;
+
+use crate::digest::{self, Algo, Digest};
+use crate::macroman::decode_macroman;
+use crate::{FourCC, MacBinary};
+
+/// A canonical summary of a MacBinary file.
+///
+/// Created with [MacBinary::snapshot].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot {
+    /// File name
+    pub filename: String,
+    /// File type code
+    pub file_type: FourCC,
+    /// File creator code
+    pub file_creator: FourCC,
+    /// Raw Finder flags
+    pub finder_flags: u16,
+    /// Creation date (UNIX timestamp)
+    pub created: u32,
+    /// Modification date (UNIX timestamp)
+    pub modified: u32,
+    /// Get Info comment, empty if there isn't one
+    pub comment: String,
+    /// Digest of the data fork
+    pub data_fork: Digest,
+    /// The resources, sorted by type and then ID
+    ///
+    /// Empty if there is no resource fork or it can't be parsed.
+    pub resources: Vec<ResourceSnapshot>,
+    /// Digest of the raw resource fork, only present if it is not empty and couldn't be parsed
+    pub invalid_resource_fork: Option<Digest>,
+}
+
+/// A canonical summary of a resource, part of a [Snapshot].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ResourceSnapshot {
+    /// Resource type
+    pub rsrc_type: FourCC,
+    /// Resource ID
+    pub id: i16,
+    /// Resource name, if present
+    pub name: Option<String>,
+    /// Raw resource attributes
+    pub attributes: u8,
+    /// Length of the resource data
+    pub len: usize,
+    /// Digest of the resource data
+    pub data: Digest,
+}
+
+/// A difference between two snapshots, returned by [Snapshot::diff].
+///
+/// Displays as a readable change record, e.g. `'STR ' 128: attributes changed: "0x00" -> "0x20"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Difference {
+    /// A file level field differs
+    Field {
+        /// Name of the field in [Snapshot]
+        field: &'static str,
+        /// Value in the original snapshot
+        old: String,
+        /// Value in the other snapshot
+        new: String,
+    },
+    /// A resource is only present in the other snapshot
+    ResourceAdded(ResourceSnapshot),
+    /// A resource is only present in the original snapshot
+    ResourceRemoved(ResourceSnapshot),
+    /// A resource present in both snapshots differs
+    ResourceChanged {
+        /// Resource type
+        rsrc_type: FourCC,
+        /// Resource ID
+        id: i16,
+        /// Name of the field in [ResourceSnapshot]
+        field: &'static str,
+        /// Value in the original snapshot
+        old: String,
+        /// Value in the other snapshot
+        new: String,
+    },
+}
+
+impl<'a> MacBinary<'a> {
+    /// Create a canonical summary of this file for comparison with other files.
+    ///
+    /// Digests are CRC-32.
+    pub fn snapshot(&self) -> Snapshot {
+        let rsrc = self.resource_fork();
+        let mut resources = Vec::new();
+        if let Ok(Some(rsrc)) = &rsrc {
+            for item in rsrc.resource_types() {
+                for resource in rsrc.resources(item) {
+                    resources.push(ResourceSnapshot {
+                        rsrc_type: resource.resource_type(),
+                        id: resource.id(),
+                        name: resource.name(),
+                        attributes: resource.raw_attributes(),
+                        len: resource.data().len(),
+                        data: digest::digest(Algo::Crc32, resource.data()),
+                    })
+                }
+            }
+        }
+        resources.sort_by_key(|resource| (resource.rsrc_type.0, resource.id));
+
+        Snapshot {
+            filename: self.filename(),
+            file_type: self.file_type(),
+            file_creator: self.file_creator(),
+            finder_flags: self.finder_info().finder_flags,
+            created: self.created(),
+            modified: self.modified(),
+            comment: decode_macroman(self.regions().comment.data).into_owned(),
+            data_fork: digest::digest(Algo::Crc32, self.data_fork()),
+            resources,
+            invalid_resource_fork: rsrc
+                .is_err()
+                .then(|| digest::digest(Algo::Crc32, self.resource_fork_raw())),
+        }
+    }
+}
+
+impl Snapshot {
+    /// List the differences from `self` to `other`.
+    ///
+    /// Returns an empty list if the snapshots are equal. File level fields are listed first,
+    /// followed by resources in sorted order.
+    pub fn diff(&self, other: &Snapshot) -> Vec<Difference> {
+        let mut diffs = Vec::new();
+        let mut field = |field: &'static str, old: String, new: String| {
+            if old != new {
+                diffs.push(Difference::Field { field, old, new })
+            }
+        };
+        field("filename", self.filename.clone(), other.filename.clone());
+        field(
+            "file_type",
+            self.file_type.to_string(),
+            other.file_type.to_string(),
+        );
+        field(
+            "file_creator",
+            self.file_creator.to_string(),
+            other.file_creator.to_string(),
+        );
+        field(
+            "finder_flags",
+            format!("{:#06x}", self.finder_flags),
+            format!("{:#06x}", other.finder_flags),
+        );
+        field(
+            "created",
+            self.created.to_string(),
+            other.created.to_string(),
+        );
+        field(
+            "modified",
+            self.modified.to_string(),
+            other.modified.to_string(),
+        );
+        field("comment", self.comment.clone(), other.comment.clone());
+        field(
+            "data_fork",
+            self.data_fork.to_string(),
+            other.data_fork.to_string(),
+        );
+        field(
+            "invalid_resource_fork",
+            display_option(self.invalid_resource_fork.as_ref()),
+            display_option(other.invalid_resource_fork.as_ref()),
+        );
+
+        // Both lists are sorted so they can be merged
+        let key = |resource: &ResourceSnapshot| (resource.rsrc_type.0, resource.id);
+        let mut old = self.resources.iter().peekable();
+        let mut new = other.resources.iter().peekable();
+        loop {
+            match (old.peek(), new.peek()) {
+                (Some(a), Some(b)) if key(a) == key(b) => {
+                    a.diff(b, &mut diffs);
+                    old.next();
+                    new.next();
+                }
+                (Some(a), Some(b)) if key(a) < key(b) => {
+                    diffs.push(Difference::ResourceRemoved((*a).clone()));
+                    old.next();
+                }
+                (Some(a), None) => {
+                    diffs.push(Difference::ResourceRemoved((*a).clone()));
+                    old.next();
+                }
+                (_, Some(b)) => {
+                    diffs.push(Difference::ResourceAdded((*b).clone()));
+                    new.next();
+                }
+                (None, None) => break,
+            }
+        }
+
+        diffs
+    }
+}
+
+impl ResourceSnapshot {
+    fn diff(&self, other: &ResourceSnapshot, diffs: &mut Vec<Difference>) {
+        let mut field = |field: &'static str, old: String, new: String| {
+            if old != new {
+                diffs.push(Difference::ResourceChanged {
+                    rsrc_type: self.rsrc_type,
+                    id: self.id,
+                    field,
+                    old,
+                    new,
+                })
+            }
+        };
+        field(
+            "name",
+            display_option(self.name.as_ref()),
+            display_option(other.name.as_ref()),
+        );
+        field(
+            "attributes",
+            format!("{:#04x}", self.attributes),
+            format!("{:#04x}", other.attributes),
+        );
+        if self.len != other.len || self.data != other.data {
+            field(
+                "data",
+                format!("{} ({} bytes)", self.data, self.len),
+                format!("{} ({} bytes)", other.data, other.len),
+            );
+        }
+    }
+}
+
+fn display_option<T: fmt::Display>(value: Option<&T>) -> String {
+    match value {
+        Some(value) => value.to_string(),
+        None => String::from("none"),
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Difference::Field { field, old, new } => {
+                write!(f, "{} changed: {:?} -> {:?}", field, old, new)
+            }
+            Difference::ResourceAdded(resource) => write!(
+                f,
+                "'{}' {} added ({} bytes)",
+                resource.rsrc_type, resource.id, resource.len
+            ),
+            Difference::ResourceRemoved(resource) => write!(
+                f,
+                "'{}' {} removed ({} bytes)",
+                resource.rsrc_type, resource.id, resource.len
+            ),
+            Difference::ResourceChanged {
+                rsrc_type,
+                id,
+                field,
+                old,
+                new,
+            } => write!(
+                f,
+                "'{}' {}: {} changed: {:?} -> {:?}",
+                rsrc_type, id, field, old, new
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use crate::test::{macbinary_with_forks, read_fixture, resource_fork};
+
+    #[test]
+    fn test_snapshot() {
+        let data = read_fixture("tests/Text File.bin");
+        let snapshot = parse(&data).unwrap().snapshot();
+        assert_eq!(snapshot.filename, "Text File");
+        assert_eq!(snapshot.data_fork, Digest::Crc32(0x44e72dfd));
+        assert_eq!(snapshot.invalid_resource_fork, None);
+        let resources: Vec<_> = snapshot
+            .resources
+            .iter()
+            .map(|resource| (resource.rsrc_type.to_string(), resource.id, resource.len))
+            .collect();
+        assert_eq!(
+            resources,
+            [
+                (String::from("BBST"), 128, 1048),
+                (String::from("MPSR"), 1005, 72)
+            ]
+        );
+        assert!(snapshot.diff(&snapshot).is_empty());
+    }
+
+    #[test]
+    fn test_diff() {
+        let rsrc = resource_fork(&[
+            (*b"STR ", 128, Some(b"greeting"), b"\x05hello"),
+            (*b"ICN#", 128, None, &[0; 256]),
+        ]);
+        let changed = resource_fork(&[
+            (*b"STR ", 128, Some(b"greeting"), b"\x05howdy"),
+            (*b"ICN#", 128, None, &[0; 256]),
+        ]);
+        let data = macbinary_with_forks(b"data", &rsrc);
+        let changed_data = macbinary_with_forks(b"data", &changed);
+        let snapshot = parse(&data).unwrap().snapshot();
+        let changed = parse(&changed_data).unwrap().snapshot();
+
+        let diffs = snapshot.diff(&changed);
+        assert_eq!(diffs.len(), 1);
+        match &diffs[0] {
+            Difference::ResourceChanged {
+                rsrc_type,
+                id,
+                field,
+                ..
+            } => {
+                assert_eq!(rsrc_type.to_string(), "STR ");
+                assert_eq!(*id, 128);
+                assert_eq!(*field, "data");
+            }
+            diff => panic!("unexpected difference {:?}", diff),
+        }
+        assert!(diffs[0]
+            .to_string()
+            .starts_with("'STR ' 128: data changed:"));
+
+        let no_rsrc = macbinary_with_forks(b"data", b"");
+        let diffs = snapshot.diff(&parse(&no_rsrc).unwrap().snapshot());
+        assert_eq!(diffs.len(), 2);
+        assert!(diffs
+            .iter()
+            .all(|diff| matches!(diff, Difference::ResourceRemoved(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        let data = read_fixture("tests/Text File.bin");
+        let snapshot = parse(&data).unwrap().snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: Snapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, snapshot);
+    }
+}
@@ -1,10 +1,131 @@
 use std::path::{Path, PathBuf};
 
+use crate::binary::NumFrom;
+
 pub fn fixture_path<P: AsRef<Path>>(path: P) -> PathBuf {
     Path::new(env!("CARGO_MANIFEST_DIR")).join(path)
 }
 
 /// Read a test fixture from a path relative to CARGO_MANIFEST_DIR
 pub fn read_fixture<P: AsRef<Path>>(path: P) -> Vec<u8> {
-    std::fs::read(&fixture_path(path)).expect("error reading file contents")
+    std::fs::read(fixture_path(path)).expect("error reading file contents")
+}
+
+/// Build a MacBinary III file with the supplied forks, using the header of `Text File.bin`
+pub fn macbinary_with_forks(data_fork: &[u8], rsrc_fork: &[u8]) -> Vec<u8> {
+    let mut data = read_fixture("tests/Text File.bin");
+    data.truncate(crate::BLOCK_SIZE);
+    data[83..87].copy_from_slice(&(data_fork.len() as u32).to_be_bytes());
+    data[87..91].copy_from_slice(&(rsrc_fork.len() as u32).to_be_bytes());
+    update_crc(&mut data);
+    for fork in [data_fork, rsrc_fork] {
+        data.extend_from_slice(fork);
+        data.resize(crate::pad_to_block(data.len() as u64).unwrap() as usize, 0);
+    }
+    data
+}
+
+/// Recalculate the CRC of a MacBinary II/III header after it has been modified
+pub fn update_crc(data: &mut [u8]) {
+    let crc = crate::calc_crc(&data[..124]);
+    data[124..126].copy_from_slice(&crc.to_be_bytes());
+}
+
+/// A resource for [resource_fork]: `(type, id, name, data)`
+pub type TestResource<'a> = ([u8; 4], i16, Option<&'a [u8]>, &'a [u8]);
+
+/// Build a resource fork containing the supplied resources
+///
+/// Resources of the same type are grouped together in the order their type first appears.
+pub fn resource_fork(resources: &[TestResource<'_>]) -> Vec<u8> {
+    let mut types: Vec<[u8; 4]> = Vec::new();
+    for (rsrc_type, ..) in resources {
+        if !types.contains(rsrc_type) {
+            types.push(*rsrc_type);
+        }
+    }
+
+    let mut rsrc_data = Vec::new();
+    let mut names = Vec::new();
+    let mut type_list = ((types.len() as u16).wrapping_sub(1))
+        .to_be_bytes()
+        .to_vec();
+    let mut reference_lists = Vec::new();
+    let ref_list_start = 2 + types.len() * 8;
+    for rsrc_type in &types {
+        let of_type: Vec<_> = resources.iter().filter(|r| &r.0 == rsrc_type).collect();
+        type_list.extend_from_slice(rsrc_type);
+        type_list.extend_from_slice(&(of_type.len() as u16 - 1).to_be_bytes());
+        type_list
+            .extend_from_slice(&((ref_list_start + reference_lists.len()) as u16).to_be_bytes());
+        for (_, id, name, data) in of_type {
+            let name_offset = match name {
+                Some(name) => {
+                    let offset = names.len() as i16;
+                    names.push(name.len() as u8);
+                    names.extend_from_slice(name);
+                    offset
+                }
+                None => -1,
+            };
+            reference_lists.extend_from_slice(&id.to_be_bytes());
+            reference_lists.extend_from_slice(&name_offset.to_be_bytes());
+            reference_lists.push(0); // attributes
+            reference_lists.extend_from_slice(&(rsrc_data.len() as u32).to_be_bytes()[1..]);
+            reference_lists.extend_from_slice(&[0; 4]);
+            rsrc_data.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            rsrc_data.extend_from_slice(data);
+        }
+    }
+
+    let data_offset = 256u32;
+    let map_offset = data_offset + rsrc_data.len() as u32;
+    let type_list_offset = 28u16;
+    let name_list_offset = type_list_offset + (type_list.len() + reference_lists.len()) as u16;
+    let map_len = u32::from(name_list_offset) + names.len() as u32;
+
+    let mut header = Vec::new();
+    for value in [data_offset, map_offset, rsrc_data.len() as u32, map_len] {
+        header.extend_from_slice(&value.to_be_bytes());
+    }
+
+    let mut fork = header.clone();
+    fork.resize(data_offset as usize, 0);
+    fork.extend_from_slice(&rsrc_data);
+    fork.extend_from_slice(&header);
+    fork.extend_from_slice(&[0; 6]); // handle and file reference number
+    fork.extend_from_slice(&[0; 2]); // attributes
+    fork.extend_from_slice(&type_list_offset.to_be_bytes());
+    fork.extend_from_slice(&name_list_offset.to_be_bytes());
+    fork.extend_from_slice(&type_list);
+    fork.extend_from_slice(&reference_lists);
+    fork.extend_from_slice(&names);
+    fork
+}
+
+/// Offset of the type list from the start of a resource fork, read from its header and map
+pub fn type_list_offset(fork: &[u8]) -> usize {
+    let map_offset = usize::num_from(u32::from_be_bytes(fork[4..8].try_into().unwrap()));
+    map_offset
+        + usize::from(u16::from_be_bytes([
+            fork[map_offset + 24],
+            fork[map_offset + 25],
+        ]))
+}
+
+/// Build a compressed resource: an extended resource header with the supplied header version,
+/// `'dcmp'` ID, and decompressed length, followed by `data`
+pub fn compressed_resource(version: u8, dcmp_id: i16, len: u32, data: &[u8]) -> Vec<u8> {
+    let mut compressed = vec![0xA8, 0x9F, 0x65, 0x72, 0x00, 0x12, version, 0x01];
+    compressed.extend_from_slice(&len.to_be_bytes());
+    if version == 8 {
+        compressed.extend_from_slice(&[0x00, 0x00]);
+        compressed.extend_from_slice(&dcmp_id.to_be_bytes());
+        compressed.extend_from_slice(&[0x00, 0x00]);
+    } else {
+        compressed.extend_from_slice(&dcmp_id.to_be_bytes());
+        compressed.extend_from_slice(&[0x00, 0x00, 0x00, 0x00]);
+    }
+    compressed.extend_from_slice(data);
+    compressed
 }
@@ -0,0 +1,287 @@
+//! Reporting of problems found in otherwise parseable files.
+//!
+//! Parsing is lenient: a file that can be read is returned even when it doesn't strictly follow
+//! the specification. [MacBinary::validate](crate::MacBinary::validate) and
+//! [ResourceFork::validate](crate::ResourceFork::validate) report the deviations as [Issues].
+
+use core::fmt;
+
+use crate::FourCC;
+
+/// Stable, machine-readable codes identifying each kind of [Issue].
+///
+/// These values won't change, so they can be relied on by scripts and other tools.
+pub mod code {
+    /// The secondary header length was ignored because the forks couldn't be read with it
+    pub const SECONDARY_HEADER_IGNORED: &str = "secondary-header-ignored";
+    /// A zero fill byte in the header is not zero
+    pub const NONZERO_ZERO_FILL: &str = "nonzero-zero-fill";
+    /// The reserved computer type and OS ID word is not zero
+    pub const NONZERO_OS_ID: &str = "nonzero-os-id";
+    /// A MacBinary I file has Finder flags set that didn't exist, or had a different meaning, at
+    /// the time
+    pub const LEGACY_FINDER_FLAGS: &str = "legacy-finder-flags";
+    /// The total unpacked length field, which is normally unused, is set
+    pub const UNPACKED_TOTAL_LEN: &str = "unpacked-total-len";
+    /// A fork is shorter than its declared length because the file is truncated
+    pub const TRUNCATED_FORK: &str = "truncated-fork";
+    /// The padding following a fork is not zero
+    pub const NONZERO_PADDING: &str = "nonzero-padding";
+    /// The padding following the resource fork is shorter than required
+    pub const MISSING_PADDING: &str = "missing-padding";
+    /// There is data following the end of the file
+    pub const TRAILING_DATA: &str = "trailing-data";
+    /// The resource fork could not be parsed
+    pub const INVALID_RESOURCE_FORK: &str = "invalid-resource-fork";
+    /// The area reserved for system use after the resource fork header is not zero
+    pub const NONZERO_RESERVED_HEADER: &str = "nonzero-reserved-header";
+    /// The reserved bytes at the start of the resource map are not zero
+    pub const NONZERO_MAP_RESERVED: &str = "nonzero-map-reserved";
+    /// A resource type contains non-printable characters
+    pub const IMPLAUSIBLE_TYPE: &str = "implausible-type";
+    /// A resource type in the type list has no resources
+    pub const EMPTY_TYPE: &str = "empty-type";
+    /// The reference list for a resource type lies outside the resource map
+    pub const REFERENCE_LIST_OUT_OF_BOUNDS: &str = "reference-list-out-of-bounds";
+    /// The data for a resource lies outside the resource data area
+    pub const RESOURCE_DATA_OUT_OF_BOUNDS: &str = "resource-data-out-of-bounds";
+    /// The length of a resource runs past the end of the resource data area
+    pub const BAD_RESOURCE_LENGTH: &str = "bad-resource-length";
+    /// A resource has a zero length name, which is distinct from having no name
+    pub const EMPTY_NAME: &str = "empty-name";
+    /// The resource name list offset lies outside the resource map
+    pub const NAME_LIST_OUT_OF_BOUNDS: &str = "name-list-out-of-bounds";
+    /// The name of a resource lies outside the resource name list
+    pub const NAME_OUT_OF_BOUNDS: &str = "name-out-of-bounds";
+    /// More than one resource has the same type and id
+    pub const DUPLICATE_RESOURCE: &str = "duplicate-resource";
+    /// The data of a resource overlaps the data of another resource
+    pub const OVERLAPPING_RESOURCE_DATA: &str = "overlapping-resource-data";
+    /// Part of the resource data area isn't used by any resource
+    pub const UNUSED_RESOURCE_DATA: &str = "unused-resource-data";
+    /// The resource map length is longer than the type, reference, and name lists
+    pub const MAP_LENGTH_MISMATCH: &str = "map-length-mismatch";
+    /// The length of a resource runs into the data of the next resource, which usually means the
+    /// length is corrupt
+    pub const RESOURCE_LENGTH_OVERLAPS: &str = "resource-length-overlaps";
+}
+
+/// How serious an [Issue] is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Unusual but harmless
+    Info,
+    /// Doesn't follow the specification, some data may be unreliable
+    Warning,
+    /// Some data can't be read
+    Error,
+}
+
+/// A problem found when validating a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// How serious the issue is
+    pub severity: Severity,
+    /// Stable identifier of the kind of issue, one of the constants in [code]
+    pub code: &'static str,
+    /// Human readable description of the issue
+    pub message: &'static str,
+    /// Offset of the problem from the start of the file, if it relates to a specific location
+    ///
+    /// For issues returned by [ResourceFork::validate](crate::ResourceFork::validate) the offset
+    /// is from the start of the resource fork.
+    pub offset: Option<usize>,
+    /// Type of the resource the issue relates to, if any
+    pub rsrc_type: Option<FourCC>,
+    /// ID of the resource the issue relates to, if any
+    pub rsrc_id: Option<i16>,
+}
+
+/// A list of validation issues.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Issues(Vec<Issue>);
+
+impl Issue {
+    pub(crate) fn new(severity: Severity, code: &'static str, message: &'static str) -> Issue {
+        Issue {
+            severity,
+            code,
+            message,
+            offset: None,
+            rsrc_type: None,
+            rsrc_id: None,
+        }
+    }
+
+    pub(crate) fn at(mut self, offset: usize) -> Issue {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub(crate) fn resource(mut self, rsrc_type: FourCC, rsrc_id: Option<i16>) -> Issue {
+        self.rsrc_type = Some(rsrc_type);
+        self.rsrc_id = rsrc_id;
+        self
+    }
+}
+
+impl Issues {
+    /// The number of issues.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no issues were found.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Iterate over the issues.
+    pub fn iter(&self) -> core::slice::Iter<'_, Issue> {
+        self.0.iter()
+    }
+
+    /// The severity of the most serious issue, or `None` if there are no issues.
+    pub fn max_severity(&self) -> Option<Severity> {
+        self.0.iter().map(|issue| issue.severity).max()
+    }
+
+    /// Returns `true` if any issue has [Severity::Error].
+    pub fn has_errors(&self) -> bool {
+        self.max_severity() == Some(Severity::Error)
+    }
+
+    /// Iterate over the issues with the supplied code.
+    pub fn with_code<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a Issue> + 'a {
+        self.0.iter().filter(move |issue| issue.code == code)
+    }
+
+    pub(crate) fn push(&mut self, issue: Issue) {
+        self.0.push(issue)
+    }
+
+    /// Append `other`, moving its offsets by `offset`.
+    pub(crate) fn append_at(&mut self, other: Issues, offset: usize) {
+        self.0.extend(other.0.into_iter().map(|mut issue| {
+            issue.offset = issue.offset.map(|issue_offset| issue_offset + offset);
+            issue
+        }))
+    }
+}
+
+impl IntoIterator for Issues {
+    type Item = Issue;
+    type IntoIter = std::vec::IntoIter<Issue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Issues {
+    type Item = &'a Issue;
+    type IntoIter = core::slice::Iter<'a, Issue>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Info => f.write_str("info"),
+            Severity::Warning => f.write_str("warning"),
+            Severity::Error => f.write_str("error"),
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}[{}]", self.severity, self.code)?;
+        if let Some(offset) = self.offset {
+            write!(f, " at offset {}", offset)?;
+        }
+        write!(f, ": {}", self.message)?;
+        match (self.rsrc_type, self.rsrc_id) {
+            (Some(rsrc_type), Some(rsrc_id)) => write!(f, " ('{}' {})", rsrc_type, rsrc_id),
+            (Some(rsrc_type), None) => write!(f, " ('{}')", rsrc_type),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for Issue {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes() {
+        assert_eq!(code::SECONDARY_HEADER_IGNORED, "secondary-header-ignored");
+        assert_eq!(code::NONZERO_ZERO_FILL, "nonzero-zero-fill");
+        assert_eq!(code::NONZERO_OS_ID, "nonzero-os-id");
+        assert_eq!(code::LEGACY_FINDER_FLAGS, "legacy-finder-flags");
+        assert_eq!(code::UNPACKED_TOTAL_LEN, "unpacked-total-len");
+        assert_eq!(code::TRUNCATED_FORK, "truncated-fork");
+        assert_eq!(code::NONZERO_PADDING, "nonzero-padding");
+        assert_eq!(code::MISSING_PADDING, "missing-padding");
+        assert_eq!(code::TRAILING_DATA, "trailing-data");
+        assert_eq!(code::INVALID_RESOURCE_FORK, "invalid-resource-fork");
+        assert_eq!(code::NONZERO_RESERVED_HEADER, "nonzero-reserved-header");
+        assert_eq!(code::NONZERO_MAP_RESERVED, "nonzero-map-reserved");
+        assert_eq!(code::IMPLAUSIBLE_TYPE, "implausible-type");
+        assert_eq!(code::EMPTY_TYPE, "empty-type");
+        assert_eq!(
+            code::REFERENCE_LIST_OUT_OF_BOUNDS,
+            "reference-list-out-of-bounds"
+        );
+        assert_eq!(
+            code::RESOURCE_DATA_OUT_OF_BOUNDS,
+            "resource-data-out-of-bounds"
+        );
+        assert_eq!(code::BAD_RESOURCE_LENGTH, "bad-resource-length");
+        assert_eq!(code::EMPTY_NAME, "empty-name");
+        assert_eq!(code::NAME_LIST_OUT_OF_BOUNDS, "name-list-out-of-bounds");
+        assert_eq!(code::NAME_OUT_OF_BOUNDS, "name-out-of-bounds");
+        assert_eq!(code::DUPLICATE_RESOURCE, "duplicate-resource");
+        assert_eq!(code::OVERLAPPING_RESOURCE_DATA, "overlapping-resource-data");
+        assert_eq!(code::UNUSED_RESOURCE_DATA, "unused-resource-data");
+        assert_eq!(code::MAP_LENGTH_MISMATCH, "map-length-mismatch");
+        assert_eq!(code::RESOURCE_LENGTH_OVERLAPS, "resource-length-overlaps");
+    }
+
+    #[test]
+    fn test_issues() {
+        let mut issues = Issues::default();
+        assert_eq!(issues.max_severity(), None);
+        assert!(!issues.has_errors());
+
+        issues.push(Issue::new(Severity::Info, code::NONZERO_OS_ID, "os id").at(126));
+        issues.push(Issue::new(Severity::Warning, code::NONZERO_PADDING, "padding").at(142));
+        assert_eq!(issues.max_severity(), Some(Severity::Warning));
+        assert!(!issues.has_errors());
+
+        let mut rsrc_issues = Issues::default();
+        rsrc_issues.push(
+            Issue::new(Severity::Error, code::RESOURCE_DATA_OUT_OF_BOUNDS, "data")
+                .at(300)
+                .resource(FourCC(u32::from_be_bytes(*b"STR ")), Some(128)),
+        );
+        issues.append_at(rsrc_issues, 256);
+        assert_eq!(issues.len(), 3);
+        assert!(issues.has_errors());
+
+        let found: Vec<_> = issues
+            .with_code(code::RESOURCE_DATA_OUT_OF_BOUNDS)
+            .collect();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].offset, Some(556));
+        assert_eq!(
+            found[0].to_string(),
+            "error[resource-data-out-of-bounds] at offset 556: data ('STR ' 128)"
+        );
+    }
+}
@@ -1,42 +1,87 @@
+//! WebAssembly bindings.
+//!
+//! `parse_macbinary` returns the whole file, including fork and resource data, and requires the
+//! `wasm-full` feature (enabled by default). `detect_macbinary` and `parse_header_js` only need
+//! `wasm-bindgen` and `js-sys`, so they are all that's built with `--no-default-features
+//! --features wasm-min`.
+
+use js_sys::{Object, Reflect};
+#[cfg(feature = "wasm-full")]
 use serde::Serialize;
 use wasm_bindgen::prelude::*;
 
 use crate::ParseError;
+#[cfg(feature = "wasm-full")]
+use crate::{FourCC, Stats};
 
+#[cfg(feature = "wasm-full")]
 #[derive(Serialize)]
 struct MacBinaryFile {
     name: String,
     #[serde(with = "serde_bytes")]
     data_fork: Vec<u8>,
     rsrc_fork_len: usize,
+    resource_type_count: usize,
+    resource_count: usize,
+    resource_types: Vec<ResourceTypeSummary>,
     resources: Vec<Resource>,
     created: u32,
     modified: u32,
     #[serde(rename = "type")]
-    type_: String,
-    creator: String,
+    type_: FourCC,
+    creator: FourCC,
+    stats: Stats,
 }
 
+#[cfg(feature = "wasm-full")]
 #[derive(Serialize)]
 struct Resource {
     #[serde(rename = "type")]
-    type_: String,
+    type_: FourCC,
     id: i16,
     name: Option<String>,
     #[serde(with = "serde_bytes")]
     data: Vec<u8>,
 }
 
+/// The [TypeStats](crate::resource::TypeStats) of a resource type, which are covered by the tests
+/// of `ResourceFork::stats` on the host.
+#[cfg(feature = "wasm-full")]
+#[derive(Serialize)]
+struct ResourceTypeSummary {
+    #[serde(rename = "type")]
+    type_: FourCC,
+    count: usize,
+    #[serde(rename = "totalBytes")]
+    total_bytes: u64,
+}
+
+#[cfg(feature = "wasm-full")]
 #[wasm_bindgen]
 pub fn parse_macbinary(val: JsValue) -> Result<JsValue, JsValue> {
     let data: serde_bytes::ByteBuf = serde_wasm_bindgen::from_value(val)?;
     let file = crate::parse(&data)?;
 
     let mut resources = Vec::new();
+    let mut resource_types = Vec::new();
+    let mut resource_type_count = 0;
+    let mut resource_count = 0;
     if let Some(rsrc) = file.resource_fork()? {
+        let stats = rsrc.stats();
+        resource_type_count = stats.types.len();
+        resource_count = stats.resource_count;
+        resource_types = stats
+            .types
+            .into_iter()
+            .map(|stats| ResourceTypeSummary {
+                type_: stats.rsrc_type,
+                count: stats.count,
+                total_bytes: stats.total_len,
+            })
+            .collect();
         for item in rsrc.resource_types() {
             resources.extend(rsrc.resources(item).map(|resource| Resource {
-                type_: item.resource_type().to_string(),
+                type_: resource.resource_type(),
                 id: resource.id(),
                 name: resource.name(),
                 data: resource.data().to_vec(),
@@ -48,16 +93,58 @@ pub fn parse_macbinary(val: JsValue) -> Result<JsValue, JsValue> {
         name: file.filename(),
         data_fork: file.data_fork().to_vec(),
         rsrc_fork_len: file.resource_fork_raw().len(),
+        resource_type_count,
+        resource_count,
+        resource_types,
         resources,
         created: file.created(),
         modified: file.modified(),
-        creator: file.file_creator().to_string(),
-        type_: file.file_type().to_string(),
+        creator: file.file_creator(),
+        type_: file.file_type(),
+        stats: file.stats(),
     };
-    let js = serde_wasm_bindgen::to_value(&res)?;
+    // Serialize `None` as `null` rather than `undefined` so that an unnamed resource (`null`) is
+    // distinguishable from one with an empty name (`""`) in the same way in every consumer.
+    let serializer = serde_wasm_bindgen::Serializer::new().serialize_missing_as_null(true);
+    let js = res.serialize(&serializer)?;
     Ok(js)
 }
 
+/// Returns the MacBinary version (1, 2, or 3) of the data, or `undefined` if it isn't MacBinary.
+#[wasm_bindgen]
+pub fn detect_macbinary(data: &[u8]) -> Option<u8> {
+    crate::detect(data).map(|version| version.as_u8())
+}
+
+/// Parse the header of a MacBinary file, returning its metadata as a plain object.
+#[wasm_bindgen]
+pub fn parse_header_js(data: &[u8]) -> Result<JsValue, JsValue> {
+    let file = crate::parse(data)?;
+
+    let obj = Object::new();
+    set(&obj, "version", JsValue::from(file.version() as u8))?;
+    set(&obj, "name", JsValue::from(file.filename()))?;
+    set(&obj, "type", JsValue::from(file.file_type().to_string()))?;
+    set(
+        &obj,
+        "creator",
+        JsValue::from(file.file_creator().to_string()),
+    )?;
+    set(&obj, "created", JsValue::from(file.created()))?;
+    set(&obj, "modified", JsValue::from(file.modified()))?;
+    set(&obj, "data_fork_len", JsValue::from(file.data_fork().len()))?;
+    set(
+        &obj,
+        "rsrc_fork_len",
+        JsValue::from(file.resource_fork_raw().len()),
+    )?;
+    Ok(obj.into())
+}
+
+fn set(obj: &Object, key: &str, value: JsValue) -> Result<(), JsValue> {
+    Reflect::set(obj, &JsValue::from(key), &value).map(|_| ())
+}
+
 impl From<ParseError> for JsValue {
     fn from(err: ParseError) -> JsValue {
         JsValue::from(err.to_string())
@@ -0,0 +1,59 @@
+//! Fork lengths that don't fit in `usize` on 32-bit targets such as wasm32.
+//!
+//! This doesn't use the standard test harness, which isn't supported on
+//! `wasm32-unknown-unknown`, so that it can also be run there:
+//!
+//!     cargo build --test large_forks --target wasm32-unknown-unknown
+//!     node tests/run-wasm.js target/wasm32-unknown-unknown/debug/deps/large_forks-*.wasm
+//!
+//! Any failure panics, which traps on wasm32.
+
+use crc::{Crc, CRC_16_XMODEM};
+use macbinary::{encoded_len_from_header, parse, parse_with_options, ParseError, ParseOptions};
+
+const CRC: Crc<u16> = Crc::<u16>::new(&CRC_16_XMODEM);
+
+/// `Text File.bin` with the supplied fork lengths in its header
+fn with_fork_lens(data_fork_len: u32, rsrc_fork_len: u32) -> Vec<u8> {
+    let mut data = include_bytes!("Text File.bin").to_vec();
+    data[83..87].copy_from_slice(&data_fork_len.to_be_bytes());
+    data[87..91].copy_from_slice(&rsrc_fork_len.to_be_bytes());
+    let crc = CRC.checksum(&data[..124]);
+    data[124..126].copy_from_slice(&crc.to_be_bytes());
+    data
+}
+
+fn main() {
+    for len in [0x8000_0000, 0xFFFF_FF80, u32::MAX] {
+        for (data_fork_len, rsrc_fork_len) in [(len, 0), (0, len), (len, len)] {
+            let data = with_fork_lens(data_fork_len, rsrc_fork_len);
+            let expected =
+                128 + ((u64::from(data_fork_len) + 127) & !127) + u64::from(rsrc_fork_len);
+            assert_eq!(
+                parse(&data).err(),
+                Some(ParseError::Truncated {
+                    expected,
+                    actual: data.len() as u64,
+                })
+            );
+
+            let options = ParseOptions::new().allow_truncated(true);
+            let file = parse_with_options(&data, options).unwrap();
+            assert_eq!(file.data_fork_declared_len(), data_fork_len);
+            assert_eq!(file.resource_fork_declared_len(), rsrc_fork_len);
+            assert!(!file.data_fork_complete() || !file.resource_fork_complete());
+        }
+    }
+
+    // The padded lengths of both forks add up to more than 4 GiB
+    let data = with_fork_lens(0x8000_0000, 0x8000_0000);
+    let encoded_len = encoded_len_from_header(&data);
+    if cfg!(target_pointer_width = "32") {
+        assert_eq!(encoded_len, Err(ParseError::Overflow));
+    } else {
+        assert_eq!(encoded_len.map(|len| len as u64), Ok(128 + 0x1_0000_0000));
+    }
+    // The padded length can't be represented in the header's 32 bits
+    let data = with_fork_lens(u32::MAX, 0);
+    assert_eq!(encoded_len_from_header(&data), Err(ParseError::Overflow));
+}
@@ -0,0 +1,23 @@
+// Run a WebAssembly binary built for wasm32-unknown-unknown that doesn't use any JavaScript
+// imports, such as the large_forks test. Imports that wasm-bindgen would normally provide are
+// stubbed out, and fail if they are called.
+const fs = require("fs");
+
+for (const path of process.argv.slice(2)) {
+  const module = new WebAssembly.Module(fs.readFileSync(path));
+  const imports = {};
+  for (const { module: name, name: field, kind } of WebAssembly.Module.imports(module)) {
+    imports[name] = imports[name] || {};
+    if (kind === "function") {
+      imports[name][field] = () => {
+        throw new Error(`${field} is not available`);
+      };
+    }
+  }
+  const instance = new WebAssembly.Instance(module, imports);
+  const status = instance.exports.main(0, 0);
+  if (status !== 0) {
+    throw new Error(`${path} exited with status ${status}`);
+  }
+  console.log(`${path}: ok`);
+}